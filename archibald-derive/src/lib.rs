@@ -0,0 +1,315 @@
+//! Archibald Derive - derive macros for archibald's data traits
+//!
+//! `#[derive(IntoInsertData)]` and `#[derive(IntoUpdateData)]` turn a struct
+//! with named fields into the column/value lists used by `insert(..).values(..)`
+//! and `update(..).set(..)`. Columns follow the struct's field order.
+//! `#[derive(Table)]` generates typed column constants for the struct's table.
+//!
+//! Field attributes:
+//! - `#[archibald(rename = "column")]` - use a different column name
+//! - `#[archibald(skip)]` - leave the field out entirely
+//! - `#[archibald(skip_if_none)]` - leave an `Option` field out when it is `None`,
+//!   for partial updates or to fall back to the column's database default
+//!
+//! # Examples
+//! ```
+//! use archibald::builder::{IntoInsertData, IntoUpdateData};
+//! use archibald_derive::{IntoInsertData, IntoUpdateData};
+//!
+//! #[derive(IntoInsertData, IntoUpdateData)]
+//! struct UserChanges {
+//!     #[archibald(skip)]
+//!     id: i32,
+//!     #[archibald(rename = "full_name")]
+//!     name: String,
+//!     #[archibald(skip_if_none)]
+//!     email: Option<String>,
+//! }
+//!
+//! let changes = UserChanges { id: 1, name: "Jane".to_string(), email: None };
+//! let data = changes.into_update_data();
+//! assert_eq!(data, vec![("full_name".to_string(), "Jane".into())]);
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{Data, DeriveInput, Field, Fields, Ident, LitStr, parse_macro_input};
+
+/// Derive `archibald::builder::IntoInsertData` for a struct with named fields
+#[proc_macro_derive(IntoInsertData, attributes(archibald))]
+pub fn derive_into_insert_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_insert(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `archibald::builder::IntoUpdateData` for a struct with named fields
+#[proc_macro_derive(IntoUpdateData, attributes(archibald))]
+pub fn derive_into_update_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_update(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `archibald::Table` and a module of typed column constants
+///
+/// The table name comes from `#[archibald(table = "...")]`, defaulting to the
+/// struct name in snake_case. The generated module is named after the table
+/// and holds one `archibald::Column<FieldType>` per field; `rename` changes
+/// the column name, while `skip` and `skip_if_none` only affect insert and
+/// update data, so those fields still get a column.
+///
+/// # Examples
+/// ```
+/// use archibald::{from, QueryBuilder};
+/// use archibald_derive::Table;
+///
+/// #[derive(Table)]
+/// #[archibald(table = "users")]
+/// struct User {
+///     id: i32,
+///     #[archibald(rename = "email_address")]
+///     email: String,
+/// }
+///
+/// let query = from("users").select(users::email).where_((users::id, 1));
+/// assert_eq!(
+///     query.to_sql().unwrap(),
+///     "SELECT users.email_address FROM users WHERE users.id = ?"
+/// );
+/// ```
+#[proc_macro_derive(Table, attributes(archibald))]
+pub fn derive_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_table(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A struct field mapped to a column
+struct ColumnField {
+    ident: Ident,
+    column: String,
+    skip_if_none: bool,
+}
+
+fn expand_insert(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = column_fields(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let capacity = fields.len();
+    let pushes = fields.iter().map(|field| {
+        push_column(field, |column, value| {
+            quote! {
+                columns.push(::std::string::String::from(#column));
+                values.push(#value);
+            }
+        })
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::archibald::builder::IntoInsertData for #name #ty_generics
+        #where_clause
+        {
+            fn into_insert_data(
+                self,
+            ) -> (::std::vec::Vec<::std::string::String>, ::std::vec::Vec<::archibald::Value>) {
+                let mut columns = ::std::vec::Vec::with_capacity(#capacity);
+                let mut values = ::std::vec::Vec::with_capacity(#capacity);
+                #(#pushes)*
+                (columns, values)
+            }
+        }
+    })
+}
+
+fn expand_update(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = column_fields(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let capacity = fields.len();
+    let pushes = fields.iter().map(|field| {
+        push_column(field, |column, value| {
+            quote! {
+                data.push((::std::string::String::from(#column), #value));
+            }
+        })
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::archibald::builder::IntoUpdateData for #name #ty_generics
+        #where_clause
+        {
+            fn into_update_data(
+                self,
+            ) -> ::std::vec::Vec<(::std::string::String, ::archibald::Value)> {
+                let mut data = ::std::vec::Vec::with_capacity(#capacity);
+                #(#pushes)*
+                data
+            }
+        }
+    })
+}
+
+fn expand_table(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let table = table_name(input)?;
+    let module = syn::parse_str::<Ident>(&table).map_err(|_| {
+        syn::Error::new_spanned(
+            &input.ident,
+            format!("table name `{}` can't be used as a module name", table),
+        )
+    })?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut constants = Vec::new();
+    for field in named_fields(input)? {
+        let attrs = field_attrs(field)?;
+        let ident = field.ident.clone().expect("named fields have identifiers");
+        let column = attrs.rename.unwrap_or_else(|| ident.to_string());
+        let ty = &field.ty;
+        constants.push(quote! {
+            pub const #ident: ::archibald::Column<#ty> = ::archibald::Column::new(#table, #column);
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::archibald::Table for #name #ty_generics #where_clause {
+            const TABLE_NAME: &'static str = #table;
+        }
+
+        #[allow(non_upper_case_globals, dead_code)]
+        #vis mod #module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #(#constants)*
+        }
+    })
+}
+
+/// Table name from `#[archibald(table = "...")]`, or the snake_cased struct name
+fn table_name(input: &DeriveInput) -> syn::Result<String> {
+    let mut table = None;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("archibald")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                let name: LitStr = meta.value()?.parse()?;
+                table = Some(name.value());
+                Ok(())
+            } else {
+                Err(meta.error("expected `table`"))
+            }
+        })?;
+    }
+
+    Ok(table.unwrap_or_else(|| snake_case(&input.ident.to_string())))
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Generate the code adding one field, guarding `skip_if_none` fields
+fn push_column<F>(field: &ColumnField, push: F) -> TokenStream2
+where
+    F: Fn(&str, TokenStream2) -> TokenStream2,
+{
+    let ident = &field.ident;
+    if field.skip_if_none {
+        let value = quote!(::std::convert::Into::<::archibald::Value>::into(value));
+        let body = push(&field.column, value);
+        quote! {
+            if let ::std::option::Option::Some(value) = self.#ident {
+                #body
+            }
+        }
+    } else {
+        let value = quote!(::std::convert::Into::<::archibald::Value>::into(self.#ident));
+        push(&field.column, value)
+    }
+}
+
+/// Options set through `#[archibald(...)]` on a field
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    skip: bool,
+    skip_if_none: bool,
+}
+
+fn field_attrs(field: &Field) -> syn::Result<FieldAttrs> {
+    let mut attrs = FieldAttrs::default();
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("archibald")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let name: LitStr = meta.value()?.parse()?;
+                attrs.rename = Some(name.value());
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                attrs.skip = true;
+                Ok(())
+            } else if meta.path.is_ident("skip_if_none") {
+                attrs.skip_if_none = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `rename`, `skip` or `skip_if_none`"))
+            }
+        })?;
+    }
+    Ok(attrs)
+}
+
+/// Named fields of a struct
+fn named_fields(input: &DeriveInput) -> syn::Result<&Punctuated<Field, Comma>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(&fields.named),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                "archibald derives require a struct with named fields",
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            "archibald derives can only be used on structs",
+        )),
+    }
+}
+
+/// Columns of a struct with named fields, in declaration order
+fn column_fields(input: &DeriveInput) -> syn::Result<Vec<ColumnField>> {
+    let mut columns = Vec::new();
+    for field in named_fields(input)? {
+        let attrs = field_attrs(field)?;
+        if attrs.skip {
+            continue;
+        }
+
+        let ident = field.ident.clone().expect("named fields have identifiers");
+        columns.push(ColumnField {
+            column: attrs.rename.unwrap_or_else(|| ident.to_string()),
+            ident,
+            skip_if_none: attrs.skip_if_none,
+        });
+    }
+
+    Ok(columns)
+}
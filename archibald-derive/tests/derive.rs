@@ -0,0 +1,111 @@
+use archibald::builder::{IntoInsertData, IntoUpdateData};
+use archibald::{insert, update, QueryBuilder, Value};
+use archibald_derive::{IntoInsertData, IntoUpdateData};
+
+#[derive(IntoInsertData, IntoUpdateData)]
+struct NewUser {
+    #[archibald(skip)]
+    id: i64,
+    #[archibald(rename = "full_name")]
+    name: String,
+    email: String,
+    age: Option<i32>,
+    #[archibald(skip_if_none)]
+    nickname: Option<String>,
+}
+
+fn jane() -> NewUser {
+    NewUser {
+        id: 7,
+        name: "Jane".to_string(),
+        email: "jane@example.com".to_string(),
+        age: None,
+        nickname: Some("jj".to_string()),
+    }
+}
+
+#[test]
+fn test_insert_data_follows_field_order() {
+    let (columns, values) = jane().into_insert_data();
+
+    assert_eq!(columns, vec!["full_name", "email", "age", "nickname"]);
+    assert_eq!(
+        values,
+        vec![
+            Value::from("Jane"),
+            Value::from("jane@example.com"),
+            Value::Null,
+            Value::from("jj"),
+        ]
+    );
+}
+
+#[test]
+fn test_skip_if_none_omits_column() {
+    let user = NewUser {
+        nickname: None,
+        ..jane()
+    };
+    let (columns, _) = user.into_insert_data();
+
+    assert_eq!(columns, vec!["full_name", "email", "age"]);
+}
+
+#[test]
+fn test_update_data() {
+    let data = jane().into_update_data();
+
+    assert_eq!(
+        data,
+        vec![
+            ("full_name".to_string(), Value::from("Jane")),
+            ("email".to_string(), Value::from("jane@example.com")),
+            ("age".to_string(), Value::Null),
+            ("nickname".to_string(), Value::from("jj")),
+        ]
+    );
+    assert_eq!(jane().id, 7);
+}
+
+#[test]
+fn test_derived_data_in_builders() {
+    assert_eq!(
+        insert("users").values(jane()).to_sql().unwrap(),
+        "INSERT INTO users (full_name, email, age, nickname) VALUES (?, ?, ?, ?)"
+    );
+
+    #[derive(IntoUpdateData)]
+    struct Rename<'a> {
+        #[archibald(skip_if_none)]
+        name: Option<&'a str>,
+        #[archibald(skip_if_none)]
+        email: Option<&'a str>,
+    }
+
+    let query = update("users")
+        .set(Rename {
+            name: None,
+            email: Some("new@example.com"),
+        })
+        .where_(("id", 7));
+    assert_eq!(
+        query.to_sql().unwrap(),
+        "UPDATE users SET email = ? WHERE id = ?"
+    );
+    assert_eq!(
+        query.parameters().unwrap(),
+        vec![Value::from("new@example.com"), Value::from(7)]
+    );
+}
+
+#[test]
+fn test_generic_struct() {
+    #[derive(IntoInsertData)]
+    struct Tagged<T: Into<Value>> {
+        tag: T,
+    }
+
+    let (columns, values) = Tagged { tag: 3i64 }.into_insert_data();
+    assert_eq!(columns, vec!["tag"]);
+    assert_eq!(values, vec![Value::I64(3)]);
+}
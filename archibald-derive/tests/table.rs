@@ -0,0 +1,48 @@
+use archibald::{from, op, QueryBuilder, Table, Value};
+
+#[derive(archibald_derive::Table)]
+#[archibald(table = "users")]
+#[allow(dead_code)]
+struct User {
+    id: i32,
+    #[archibald(rename = "email_address")]
+    email: String,
+    #[archibald(skip_if_none)]
+    nickname: Option<String>,
+}
+
+#[derive(archibald_derive::Table)]
+#[allow(dead_code)]
+struct BlogPost {
+    user_id: i32,
+    title: String,
+}
+
+#[test]
+fn test_table_name() {
+    assert_eq!(User::TABLE_NAME, "users");
+    assert_eq!(BlogPost::TABLE_NAME, "blog_post");
+}
+
+#[test]
+fn test_generated_columns() {
+    assert_eq!(users::id.qualified(), "users.id");
+    assert_eq!(users::email.name(), "email_address");
+    assert_eq!(blog_post::title.table(), "blog_post");
+}
+
+#[test]
+fn test_columns_in_builders() {
+    let query = from(User::TABLE_NAME)
+        .select((users::id, users::email))
+        .left_join(BlogPost::TABLE_NAME, users::id, blog_post::user_id)
+        .where_((users::id, op::GTE, 5))
+        .where_(users::nickname.is_null())
+        .order_by_asc(blog_post::title);
+
+    assert_eq!(
+        query.to_sql().unwrap(),
+        "SELECT users.id, users.email_address FROM users LEFT JOIN blog_post ON users.id = blog_post.user_id WHERE users.id >= ? AND users.nickname IS NULL ORDER BY blog_post.title ASC"
+    );
+    assert_eq!(query.parameters().unwrap(), vec![Value::I32(5)]);
+}
@@ -0,0 +1,46 @@
+//! Error types for archibald-migrate
+
+use thiserror::Error;
+
+/// Errors raised while running migrations
+#[derive(Error, Debug)]
+pub enum Error {
+    /// Error from the query builder or the database
+    #[error(transparent)]
+    Archibald(#[from] archibald::Error),
+
+    /// An applied migration no longer matches the recorded checksum
+    #[error("Migration {version} ({name}) has changed since it was applied")]
+    ChecksumMismatch { version: i64, name: String },
+
+    /// A migration recorded as applied is not registered with the migrator
+    #[error("Migration {version} is recorded as applied but is not registered")]
+    MissingMigration { version: i64 },
+
+    /// Two registered migrations share a version
+    #[error("Duplicate migration version {version}")]
+    DuplicateVersion { version: i64 },
+}
+
+/// Convenience Result type for migration operations
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_messages() {
+        let err = Error::ChecksumMismatch {
+            version: 3,
+            name: "add_users".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Migration 3 (add_users) has changed since it was applied"
+        );
+
+        let err: Error = archibald::Error::invalid_query("bad").into();
+        assert_eq!(err.to_string(), "Invalid query: bad");
+    }
+}
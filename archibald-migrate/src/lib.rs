@@ -0,0 +1,44 @@
+//! Archibald Migrate - schema migrations for the archibald query builder
+//!
+//! Migrations implement [`Migration`] (or use [`SqlMigration`] for plain SQL)
+//! and are registered with a [`Migrator`], which applies each one in its own
+//! transaction and records it in the `archibald_migrations` table along with
+//! a checksum, so edits to already-applied migrations are detected.
+//!
+//! # Examples
+//! ```no_run
+//! use archibald::executor::sqlite::SqlitePool;
+//! use archibald_migrate::{Migrator, SqlMigration};
+//!
+//! # async fn run() -> archibald_migrate::Result<()> {
+//! let pool = SqlitePool::new("sqlite://app.db").await?;
+//! let migrator = Migrator::new(pool)
+//!     .register(
+//!         SqlMigration::new(1, "create_users")
+//!             .up("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL)")
+//!             .down("DROP TABLE users"),
+//!     )
+//!     .register(
+//!         SqlMigration::new(2, "create_posts")
+//!             .up("CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT NOT NULL)")
+//!             .down("DROP TABLE posts"),
+//!     );
+//!
+//! migrator.latest().await?; // apply pending migrations as one batch
+//! migrator.rollback().await?; // revert the last batch
+//! migrator.to(1).await?; // migrate up or down to version 1
+//!
+//! for status in migrator.status().await? {
+//!     println!("{} {} {:?}", status.version, status.name, status.state);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod error;
+pub mod migration;
+pub mod migrator;
+
+pub use error::{Error, Result};
+pub use migration::{checksum, Migration, SqlMigration};
+pub use migrator::{AppliedMigration, MigrationState, MigrationStatus, Migrator, MIGRATIONS_TABLE};
@@ -0,0 +1,201 @@
+//! The `Migration` trait and plain SQL migrations
+
+use archibald::Transaction;
+use futures::future::BoxFuture;
+use sha2::{Digest, Sha256};
+
+/// A reversible schema change
+///
+/// `up` and `down` run inside a transaction started by the
+/// [`Migrator`](crate::Migrator), which also records the migration in the
+/// tracking table before committing.
+///
+/// # Examples
+/// ```
+/// use archibald::schema::{create_table, drop_table, CreateTable, SchemaBuilder};
+/// use archibald::Transaction;
+/// use archibald_migrate::{checksum, Migration};
+/// use futures::future::BoxFuture;
+///
+/// struct CreateUsers;
+///
+/// impl CreateUsers {
+///     fn table() -> CreateTable {
+///         create_table("users", |t| {
+///             t.increments("id");
+///             t.string("email", 255).not_null().unique();
+///         })
+///     }
+/// }
+///
+/// impl<Tx: Transaction> Migration<Tx> for CreateUsers {
+///     fn version(&self) -> i64 {
+///         20240101000000
+///     }
+///
+///     fn name(&self) -> &str {
+///         "create_users"
+///     }
+///
+///     fn checksum(&self) -> String {
+///         checksum(&[&Self::table().to_sql().unwrap(), "DROP TABLE users"])
+///     }
+///
+///     fn up<'a>(&'a self, tx: &'a mut Tx) -> BoxFuture<'a, archibald::Result<()>> {
+///         Box::pin(async move { Self::table().execute_tx(tx).await })
+///     }
+///
+///     fn down<'a>(&'a self, tx: &'a mut Tx) -> BoxFuture<'a, archibald::Result<()>> {
+///         Box::pin(async move { drop_table("users").execute_tx(tx).await })
+///     }
+/// }
+/// ```
+pub trait Migration<Tx: Transaction>: Send + Sync {
+    /// Unique, increasing version (timestamps like `20240101120000` work well)
+    fn version(&self) -> i64;
+
+    /// Human readable name
+    fn name(&self) -> &str;
+
+    /// Checksum recorded when the migration is applied
+    ///
+    /// It must change whenever `up` or `down` changes, since the
+    /// [`Migrator`](crate::Migrator) compares it with the recorded one to
+    /// detect edits to migrations that have already run. Hashing the SQL the
+    /// migration runs with [`checksum`] is usually enough.
+    fn checksum(&self) -> String;
+
+    /// Apply the migration
+    fn up<'a>(&'a self, tx: &'a mut Tx) -> BoxFuture<'a, archibald::Result<()>>;
+
+    /// Revert the migration
+    fn down<'a>(&'a self, tx: &'a mut Tx) -> BoxFuture<'a, archibald::Result<()>>;
+}
+
+/// A migration made of raw SQL statements, checksummed over its SQL
+///
+/// # Examples
+/// ```
+/// use archibald_migrate::SqlMigration;
+///
+/// let migration = SqlMigration::new(1, "create_posts")
+///     .up("CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT NOT NULL)")
+///     .up("CREATE INDEX posts_title_index ON posts (title)")
+///     .down("DROP TABLE posts");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlMigration {
+    version: i64,
+    name: String,
+    up: Vec<String>,
+    down: Vec<String>,
+}
+
+impl SqlMigration {
+    pub fn new(version: i64, name: &str) -> Self {
+        Self {
+            version,
+            name: name.to_string(),
+            up: Vec::new(),
+            down: Vec::new(),
+        }
+    }
+
+    /// Add a statement to run when migrating up
+    pub fn up(mut self, sql: &str) -> Self {
+        self.up.push(sql.to_string());
+        self
+    }
+
+    /// Add a statement to run when migrating down
+    pub fn down(mut self, sql: &str) -> Self {
+        self.down.push(sql.to_string());
+        self
+    }
+}
+
+impl<Tx: Transaction> Migration<Tx> for SqlMigration {
+    fn version(&self) -> i64 {
+        self.version
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn checksum(&self) -> String {
+        let mut parts: Vec<&str> = self.up.iter().map(String::as_str).collect();
+        parts.push("--");
+        parts.extend(self.down.iter().map(String::as_str));
+        checksum(&parts)
+    }
+
+    fn up<'a>(&'a self, tx: &'a mut Tx) -> BoxFuture<'a, archibald::Result<()>> {
+        Box::pin(run_statements(tx, &self.up))
+    }
+
+    fn down<'a>(&'a self, tx: &'a mut Tx) -> BoxFuture<'a, archibald::Result<()>> {
+        Box::pin(run_statements(tx, &self.down))
+    }
+}
+
+async fn run_statements<Tx>(tx: &mut Tx, statements: &[String]) -> archibald::Result<()>
+where
+    Tx: Transaction,
+{
+    for statement in statements {
+        tx.execute(statement, &[]).await?;
+    }
+    Ok(())
+}
+
+/// Hex-encoded SHA-256 over the given parts, each terminated by a newline
+pub fn checksum(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update(b"\n");
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Any transaction type will do for calling the trait methods
+    type Tx = archibald::executor::sqlite::SqliteTransaction;
+
+    #[test]
+    fn test_checksum_is_stable() {
+        assert_eq!(
+            checksum(&["abc"]),
+            "edeaaff3f1774ad2888673770c6d64097e391bc362d7d6fb34982ddf0efd18cb"
+        );
+        assert_ne!(checksum(&["ab", "c"]), checksum(&["a", "bc"]));
+    }
+
+    #[test]
+    fn test_sql_migration_checksum_covers_sql() {
+        let original = SqlMigration::new(1, "create_posts")
+            .up("CREATE TABLE posts (id INTEGER)")
+            .down("DROP TABLE posts");
+        let edited = SqlMigration::new(1, "create_posts")
+            .up("CREATE TABLE posts (id BIGINT)")
+            .down("DROP TABLE posts");
+
+        assert_eq!(
+            Migration::<Tx>::checksum(&original),
+            Migration::<Tx>::checksum(&original.clone())
+        );
+        assert_ne!(
+            Migration::<Tx>::checksum(&original),
+            Migration::<Tx>::checksum(&edited)
+        );
+        assert_eq!(Migration::<Tx>::checksum(&original).len(), 64);
+    }
+}
@@ -0,0 +1,547 @@
+//! Applying, reverting and inspecting migrations
+
+use crate::{Error, Migration, Result};
+use archibald::schema::{create_table, SchemaBuilder};
+use archibald::{
+    delete, from, insert, ExecutableModification, ExecutableQuery, SortDirection, Transaction,
+    TransactionalPool, Value,
+};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+/// Table recording applied migrations
+pub const MIGRATIONS_TABLE: &str = "archibald_migrations";
+
+/// A row of the [`MIGRATIONS_TABLE`]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub checksum: String,
+    pub batch: i64,
+}
+
+/// Whether a migration has run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationState {
+    /// Not applied yet
+    Pending,
+    /// Applied as part of the given batch
+    Applied { batch: i64 },
+    /// Applied, but the migration has changed since
+    ChecksumMismatch { batch: i64 },
+    /// Recorded as applied, but no longer registered with the migrator
+    Missing { batch: i64 },
+}
+
+/// Status of a single migration, as reported by [`Migrator::status`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub state: MigrationState,
+}
+
+/// Runs migrations against a pool, one transaction per migration
+///
+/// Migrations applied by one call to [`latest`](Migrator::latest) or
+/// [`to`](Migrator::to) form a batch; [`rollback`](Migrator::rollback) reverts
+/// the most recent batch.
+///
+/// # Examples
+/// ```no_run
+/// use archibald::executor::sqlite::SqlitePool;
+/// use archibald_migrate::{Migrator, SqlMigration};
+///
+/// # async fn run() -> archibald_migrate::Result<()> {
+/// let pool = SqlitePool::new("sqlite://app.db").await?;
+/// let migrator = Migrator::new(pool)
+///     .register(
+///         SqlMigration::new(1, "create_users")
+///             .up("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL)")
+///             .down("DROP TABLE users"),
+///     );
+///
+/// let applied = migrator.latest().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Migrator<P: TransactionalPool> {
+    pool: P,
+    migrations: Vec<Box<dyn Migration<P::Transaction>>>,
+}
+
+impl<P> Migrator<P>
+where
+    P: TransactionalPool,
+{
+    pub fn new(pool: P) -> Self {
+        Self {
+            pool,
+            migrations: Vec::new(),
+        }
+    }
+
+    /// Register a migration
+    pub fn register<M>(mut self, migration: M) -> Self
+    where
+        M: Migration<P::Transaction> + 'static,
+    {
+        self.migrations.push(Box::new(migration));
+        self.migrations.sort_by_key(|migration| migration.version());
+        self
+    }
+
+    /// Apply every pending migration as a new batch, returning their versions
+    pub async fn latest(&self) -> Result<Vec<i64>> {
+        self.migrate_up(None).await
+    }
+
+    /// Revert the most recent batch, returning the reverted versions
+    pub async fn rollback(&self) -> Result<Vec<i64>> {
+        let applied = self.prepare().await?;
+        let Some(batch) = applied.iter().map(|migration| migration.batch).max() else {
+            return Ok(Vec::new());
+        };
+
+        let versions: Vec<i64> = applied
+            .iter()
+            .filter(|migration| migration.batch == batch)
+            .map(|migration| migration.version)
+            .collect();
+        self.migrate_down(versions).await
+    }
+
+    /// Migrate to `version`: apply pending migrations up to and including it,
+    /// and revert applied migrations after it. `to(0)` reverts everything.
+    ///
+    /// Returns the versions that were applied or reverted.
+    pub async fn to(&self, version: i64) -> Result<Vec<i64>> {
+        let applied = self.prepare().await?;
+        let versions: Vec<i64> = applied
+            .iter()
+            .filter(|migration| migration.version > version)
+            .map(|migration| migration.version)
+            .collect();
+
+        let mut changed = self.migrate_down(versions).await?;
+        changed.extend(self.migrate_up(Some(version)).await?);
+        Ok(changed)
+    }
+
+    /// Status of every registered or applied migration, ordered by version
+    ///
+    /// A registered migration whose [`checksum`](Migration::checksum) differs
+    /// from the recorded one is reported as
+    /// [`ChecksumMismatch`](MigrationState::ChecksumMismatch).
+    pub async fn status(&self) -> Result<Vec<MigrationStatus>> {
+        self.validate_versions()?;
+        self.ensure_table().await?;
+        let applied: HashMap<i64, AppliedMigration> = self
+            .applied()
+            .await?
+            .into_iter()
+            .map(|migration| (migration.version, migration))
+            .collect();
+
+        let mut statuses: Vec<MigrationStatus> = self
+            .migrations
+            .iter()
+            .map(|migration| {
+                let state = match applied.get(&migration.version()) {
+                    None => MigrationState::Pending,
+                    Some(row) if row.checksum != migration.checksum() => {
+                        MigrationState::ChecksumMismatch { batch: row.batch }
+                    }
+                    Some(row) => MigrationState::Applied { batch: row.batch },
+                };
+                MigrationStatus {
+                    version: migration.version(),
+                    name: migration.name().to_string(),
+                    state,
+                }
+            })
+            .collect();
+
+        for row in applied.values() {
+            if self.find(row.version).is_none() {
+                statuses.push(MigrationStatus {
+                    version: row.version,
+                    name: row.name.clone(),
+                    state: MigrationState::Missing { batch: row.batch },
+                });
+            }
+        }
+
+        statuses.sort_by_key(|status| status.version);
+        Ok(statuses)
+    }
+
+    /// Create the tracking table if needed and check applied checksums
+    async fn prepare(&self) -> Result<Vec<AppliedMigration>> {
+        self.validate_versions()?;
+        self.ensure_table().await?;
+        let applied = self.applied().await?;
+
+        for row in &applied {
+            if let Some(migration) = self.find(row.version)
+                && migration.checksum() != row.checksum
+            {
+                return Err(Error::ChecksumMismatch {
+                    version: row.version,
+                    name: row.name.clone(),
+                });
+            }
+        }
+
+        Ok(applied)
+    }
+
+    async fn migrate_up(&self, target: Option<i64>) -> Result<Vec<i64>> {
+        let applied = self.prepare().await?;
+        let applied_versions: HashSet<i64> =
+            applied.iter().map(|migration| migration.version).collect();
+        let batch = applied.iter().map(|migration| migration.batch).max().unwrap_or(0) + 1;
+
+        let mut versions = Vec::new();
+        for migration in &self.migrations {
+            let version = migration.version();
+            let beyond_target = target.is_some_and(|target| version > target);
+            if applied_versions.contains(&version) || beyond_target {
+                continue;
+            }
+
+            let mut tx = self.pool.begin_transaction().await?;
+            let result = self.apply(migration.as_ref(), &mut tx, batch).await;
+            finish(tx, result).await?;
+            versions.push(version);
+        }
+
+        Ok(versions)
+    }
+
+    async fn migrate_down(&self, mut versions: Vec<i64>) -> Result<Vec<i64>> {
+        versions.sort_unstable_by(|a, b| b.cmp(a));
+
+        for &version in &versions {
+            let migration = self
+                .find(version)
+                .ok_or(Error::MissingMigration { version })?;
+
+            let mut tx = self.pool.begin_transaction().await?;
+            let result = revert(migration, &mut tx).await;
+            finish(tx, result).await?;
+        }
+
+        Ok(versions)
+    }
+
+    async fn apply(
+        &self,
+        migration: &dyn Migration<P::Transaction>,
+        tx: &mut P::Transaction,
+        batch: i64,
+    ) -> archibald::Result<()> {
+        migration.up(tx).await?;
+
+        let mut record = HashMap::new();
+        record.insert("version".to_string(), Value::from(migration.version()));
+        record.insert("name".to_string(), Value::from(migration.name()));
+        record.insert("checksum".to_string(), Value::from(migration.checksum()));
+        record.insert("batch".to_string(), Value::from(batch));
+        insert(MIGRATIONS_TABLE).values(record).execute_tx(tx).await?;
+        Ok(())
+    }
+
+    async fn ensure_table(&self) -> Result<()> {
+        create_table(MIGRATIONS_TABLE, |t| {
+            t.big_integer("version").primary();
+            t.string("name", 255).not_null();
+            t.string("checksum", 64).not_null();
+            t.big_integer("batch").not_null();
+            t.timestamp("applied_at")
+                .not_null()
+                .default_raw("CURRENT_TIMESTAMP");
+        })
+        .if_not_exists()
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn applied(&self) -> Result<Vec<AppliedMigration>> {
+        let rows = from(MIGRATIONS_TABLE)
+            .select(("version", "name", "checksum", "batch"))
+            .order_by("version", SortDirection::Asc)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows)
+    }
+
+    fn find(&self, version: i64) -> Option<&dyn Migration<P::Transaction>> {
+        self.migrations
+            .iter()
+            .find(|migration| migration.version() == version)
+            .map(|migration| migration.as_ref())
+    }
+
+    fn validate_versions(&self) -> Result<()> {
+        // Migrations are kept sorted, so duplicates are neighbours
+        for pair in self.migrations.windows(2) {
+            if pair[0].version() == pair[1].version() {
+                return Err(Error::DuplicateVersion {
+                    version: pair[0].version(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn revert<Tx>(migration: &dyn Migration<Tx>, tx: &mut Tx) -> archibald::Result<()>
+where
+    Tx: Transaction,
+{
+    migration.down(tx).await?;
+    delete(MIGRATIONS_TABLE)
+        .where_(("version", migration.version()))
+        .execute_tx(tx)
+        .await?;
+    Ok(())
+}
+
+/// Commit on success, roll back on failure
+async fn finish<Tx>(tx: Tx, result: archibald::Result<()>) -> Result<()>
+where
+    Tx: Transaction,
+{
+    match result {
+        Ok(()) => Ok(tx.commit().await?),
+        Err(e) => {
+            let _ = tx.rollback().await; // Ignore rollback errors
+            Err(e.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SqlMigration;
+    use archibald::executor::sqlite::{SqlitePool, SqliteTransaction};
+    use archibald::schema::{alter_table, AlterTable};
+    use archibald::ConnectionPool;
+    use futures::future::BoxFuture;
+
+    /// A single-connection in-memory pool, so every query sees the same database
+    async fn memory_pool() -> SqlitePool {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        SqlitePool::from_pool(pool)
+    }
+
+    fn create_users() -> SqlMigration {
+        SqlMigration::new(1, "create_users")
+            .up("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL)")
+            .down("DROP TABLE users")
+    }
+
+    struct AddNickname {
+        length: u32,
+    }
+
+    impl AddNickname {
+        fn change(&self) -> AlterTable {
+            let length = self.length;
+            alter_table("users", |t| {
+                t.string("nickname", length);
+            })
+        }
+    }
+
+    impl Migration<SqliteTransaction> for AddNickname {
+        fn version(&self) -> i64 {
+            2
+        }
+
+        fn name(&self) -> &str {
+            "add_nickname"
+        }
+
+        fn checksum(&self) -> String {
+            crate::checksum(&[&self.change().to_sql().unwrap()])
+        }
+
+        fn up<'a>(&'a self, tx: &'a mut SqliteTransaction) -> BoxFuture<'a, archibald::Result<()>> {
+            Box::pin(async move { self.change().execute_tx(tx).await })
+        }
+
+        fn down<'a>(
+            &'a self,
+            tx: &'a mut SqliteTransaction,
+        ) -> BoxFuture<'a, archibald::Result<()>> {
+            Box::pin(async move {
+                alter_table("users", |t| t.drop_column("nickname"))
+                    .execute_tx(tx)
+                    .await
+            })
+        }
+    }
+
+    fn create_posts() -> SqlMigration {
+        SqlMigration::new(3, "create_posts")
+            .up("CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL)")
+            .down("DROP TABLE posts")
+    }
+
+    async fn table_exists(pool: &SqlitePool, table: &str) -> bool {
+        let row: Option<serde_json::Value> = pool
+            .fetch_optional(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?",
+                &[Value::from(table)],
+            )
+            .await
+            .unwrap();
+        row.is_some()
+    }
+
+    #[tokio::test]
+    async fn test_latest_and_rollback_batches() {
+        let pool = memory_pool().await;
+        let migrator = Migrator::new(pool.clone())
+            .register(AddNickname { length: 50 })
+            .register(create_users());
+
+        assert_eq!(migrator.latest().await.unwrap(), vec![1, 2]);
+        assert!(migrator.latest().await.unwrap().is_empty());
+
+        let migrator = migrator.register(create_posts());
+        assert_eq!(migrator.latest().await.unwrap(), vec![3]);
+        assert!(table_exists(&pool, "posts").await);
+
+        let statuses = migrator.status().await.unwrap();
+        let states: Vec<MigrationState> = statuses.into_iter().map(|s| s.state).collect();
+        assert_eq!(
+            states,
+            vec![
+                MigrationState::Applied { batch: 1 },
+                MigrationState::Applied { batch: 1 },
+                MigrationState::Applied { batch: 2 },
+            ]
+        );
+
+        // Only the last batch is reverted
+        assert_eq!(migrator.rollback().await.unwrap(), vec![3]);
+        assert!(!table_exists(&pool, "posts").await);
+        assert!(table_exists(&pool, "users").await);
+
+        assert_eq!(migrator.rollback().await.unwrap(), vec![2, 1]);
+        assert!(!table_exists(&pool, "users").await);
+        assert!(migrator.rollback().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_to_version() {
+        let pool = memory_pool().await;
+        let migrator = Migrator::new(pool.clone())
+            .register(create_users())
+            .register(AddNickname { length: 50 })
+            .register(create_posts());
+
+        assert_eq!(migrator.to(2).await.unwrap(), vec![1, 2]);
+        assert!(!table_exists(&pool, "posts").await);
+
+        assert_eq!(migrator.to(3).await.unwrap(), vec![3]);
+        assert_eq!(migrator.to(1).await.unwrap(), vec![3, 2]);
+        assert_eq!(migrator.to(0).await.unwrap(), vec![1]);
+        assert!(!table_exists(&pool, "users").await);
+    }
+
+    #[tokio::test]
+    async fn test_failed_migration_is_rolled_back() {
+        let pool = memory_pool().await;
+        let broken = SqlMigration::new(2, "broken")
+            .up("CREATE TABLE widgets (id INTEGER PRIMARY KEY)")
+            .up("THIS IS NOT SQL")
+            .down("DROP TABLE widgets");
+        let migrator = Migrator::new(pool.clone()).register(create_users()).register(broken);
+
+        assert!(migrator.latest().await.is_err());
+        assert!(table_exists(&pool, "users").await);
+        assert!(!table_exists(&pool, "widgets").await);
+
+        let statuses = migrator.status().await.unwrap();
+        assert_eq!(statuses[0].state, MigrationState::Applied { batch: 1 });
+        assert_eq!(statuses[1].state, MigrationState::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_checksum_mismatch_and_missing_migrations() {
+        let pool = memory_pool().await;
+        Migrator::new(pool.clone())
+            .register(create_users())
+            .register(create_posts())
+            .latest()
+            .await
+            .unwrap();
+
+        let edited = SqlMigration::new(1, "create_users")
+            .up("CREATE TABLE users (id INTEGER PRIMARY KEY)")
+            .down("DROP TABLE users");
+        let migrator = Migrator::new(pool.clone()).register(edited);
+
+        let statuses = migrator.status().await.unwrap();
+        assert_eq!(
+            statuses[0].state,
+            MigrationState::ChecksumMismatch { batch: 1 }
+        );
+        assert_eq!(statuses[1].state, MigrationState::Missing { batch: 1 });
+
+        assert!(matches!(
+            migrator.latest().await,
+            Err(Error::ChecksumMismatch { version: 1, .. })
+        ));
+
+        let migrator = Migrator::new(pool.clone()).register(create_users());
+        assert!(matches!(
+            migrator.rollback().await,
+            Err(Error::MissingMigration { version: 3 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_edited_custom_migration_is_detected() {
+        let pool = memory_pool().await;
+        Migrator::new(pool.clone())
+            .register(create_users())
+            .register(AddNickname { length: 50 })
+            .latest()
+            .await
+            .unwrap();
+
+        let migrator = Migrator::new(pool.clone())
+            .register(create_users())
+            .register(AddNickname { length: 80 });
+        let statuses = migrator.status().await.unwrap();
+        assert_eq!(statuses[1].state, MigrationState::ChecksumMismatch { batch: 1 });
+        assert!(matches!(
+            migrator.latest().await,
+            Err(Error::ChecksumMismatch { version: 2, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_versions() {
+        let migrator = Migrator::new(memory_pool().await)
+            .register(create_users())
+            .register(SqlMigration::new(1, "also_one"));
+
+        assert!(matches!(
+            migrator.latest().await,
+            Err(Error::DuplicateVersion { version: 1 })
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+//! Row decoding throughput on an in-memory SQLite table
+//!
+//! Compares decoding rows into a struct through an intermediate
+//! `serde_json::Value` (how every row was decoded before rows were read
+//! directly), the serde `Deserializer` over the row, and a hand-written
+//! `FromRow` impl.
+//!
+//! Run with `cargo bench -p archibald --features sqlite --bench row_decoding`.
+
+use archibald::executor::sqlite::SqlitePool;
+use archibald::{ConnectionPool, FromRow, Result, Row, Value};
+use serde::Deserialize;
+use std::future::Future;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ROWS: i64 = 10_000;
+const ITERATIONS: usize = 25;
+const QUERY: &str = "SELECT id, name, email, score, active, avatar FROM users";
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+struct User {
+    id: i64,
+    name: String,
+    email: String,
+    score: f64,
+    active: bool,
+    avatar: Vec<u8>,
+}
+
+impl FromRow for User {
+    fn from_row(row: &Row<'_>) -> Result<Self> {
+        Ok(Self {
+            id: row.get_index(0)?,
+            name: row.get_index(1)?,
+            email: row.get_index(2)?,
+            score: row.get_index(3)?,
+            active: row.get_index(4)?,
+            avatar: row.get_index(5)?,
+        })
+    }
+}
+
+async fn setup() -> SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("in-memory database");
+    let pool = SqlitePool::from_pool(pool);
+
+    pool.execute(
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            email TEXT NOT NULL,
+            score REAL NOT NULL,
+            active BOOLEAN NOT NULL,
+            avatar BLOB NOT NULL
+        )",
+        &[],
+    )
+    .await
+    .unwrap();
+    for id in 1..=ROWS {
+        pool.execute(
+            "INSERT INTO users (id, name, email, score, active, avatar) VALUES (?, ?, ?, ?, ?, ?)",
+            &[
+                Value::I64(id),
+                Value::from(format!("user {}", id)),
+                Value::from(format!("user{}@example.com", id)),
+                Value::F64(id as f64 / 3.0),
+                Value::Bool(id % 2 == 0),
+                Value::Bytes(id.to_le_bytes().to_vec()),
+            ],
+        )
+        .await
+        .unwrap();
+    }
+    pool
+}
+
+/// Median wall time of `ITERATIONS` runs, after one warm-up run
+async fn measure<F, Fut>(mut run: F) -> Duration
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = usize>,
+{
+    black_box(run().await);
+    let mut times = Vec::with_capacity(ITERATIONS);
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        assert_eq!(black_box(run().await), ROWS as usize);
+        times.push(start.elapsed());
+    }
+    times.sort();
+    times[ITERATIONS / 2]
+}
+
+fn report(name: &str, time: Duration, baseline: Duration) {
+    println!(
+        "{:<24} {:>10.2?} {:>9.0} ns/row {:>6.2}x",
+        name,
+        time,
+        time.as_nanos() as f64 / ROWS as f64,
+        baseline.as_secs_f64() / time.as_secs_f64()
+    );
+}
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        let pool = setup().await;
+
+        let json = measure(|| async {
+            let rows: Vec<serde_json::Value> = pool.fetch_all(QUERY, &[]).await.unwrap();
+            let users: Vec<User> = rows
+                .into_iter()
+                .map(|row| serde_json::from_value(row).unwrap())
+                .collect();
+            users.len()
+        })
+        .await;
+        let direct = measure(|| async {
+            let users: Vec<User> = pool.fetch_all(QUERY, &[]).await.unwrap();
+            users.len()
+        })
+        .await;
+        let from_row = measure(|| async {
+            let users: Vec<User> = pool.fetch_all_as(QUERY, &[]).await.unwrap();
+            users.len()
+        })
+        .await;
+
+        println!("decoding {} rows, median of {} runs", ROWS, ITERATIONS);
+        report("json round trip", json, json);
+        report("serde deserializer", direct, json);
+        report("FromRow", from_row, json);
+    });
+}
@@ -11,6 +11,7 @@ struct User {
     age: i32,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct NewUser {
     name: String,
@@ -69,9 +70,9 @@ impl ConnectionPool for MockPool {
             let user: T = serde_json::from_value(user_json)?;
             Ok(user)
         } else {
-            return Err(archibald::Error::sql_generation(
+            Err(archibald::Error::sql_generation(
                 "No mock data for this type",
-            ));
+            ))
         }
     }
 
@@ -16,7 +16,7 @@ fn main() {
 
     println!("1. Basic SELECT:");
     println!("   SQL: {}", select_query.to_sql().unwrap());
-    println!("   Parameters: {:?}\n", select_query.parameters());
+    println!("   Parameters: {:?}\n", select_query.parameters().unwrap());
 
     // Subqueries - WHERE IN
     let subquery_in = from("users").select(("id", "name")).where_in(
@@ -68,7 +68,7 @@ fn main() {
     let insert_query = insert("users").values(user_data);
     println!("4. INSERT:");
     println!("   SQL: {}", insert_query.to_sql().unwrap());
-    println!("   Parameters: {:?}\n", insert_query.parameters());
+    println!("   Parameters: {:?}\n", insert_query.parameters().unwrap());
 
     // UPDATE
     let mut updates = HashMap::new();
@@ -82,7 +82,7 @@ fn main() {
 
     println!("5. UPDATE:");
     println!("   SQL: {}", update_query.to_sql().unwrap());
-    println!("   Parameters: {:?}\n", update_query.parameters());
+    println!("   Parameters: {:?}\n", update_query.parameters().unwrap());
 
     // DELETE
     let delete_query =
@@ -92,7 +92,7 @@ fn main() {
 
     println!("6. DELETE:");
     println!("   SQL: {}", delete_query.to_sql().unwrap());
-    println!("   Parameters: {:?}\n", delete_query.parameters());
+    println!("   Parameters: {:?}\n", delete_query.parameters().unwrap());
 
     // Custom operators for advanced database features
     let postgres_fts_query = from("documents")
@@ -118,7 +118,7 @@ fn main() {
 
     println!("8. Complex WHERE (AND/OR combinations):");
     println!("   SQL: {}", complex_query.to_sql().unwrap());
-    println!("   Parameters: {:?}\n", complex_query.parameters());
+    println!("   Parameters: {:?}\n", complex_query.parameters().unwrap());
 
     // Deferred validation example
     println!("9. Deferred Validation:");
@@ -1,5 +1,8 @@
+#[cfg(feature = "sqlite")]
 use archibald::{delete, from, insert, op, update, QueryBuilder};
+#[cfg(feature = "sqlite")]
 use archibald::{ColumnSelector, SortDirection};
+#[cfg(feature = "sqlite")]
 use std::collections::HashMap;
 
 // Note: Import SqlitePool if you need to actually connect to a database
@@ -26,7 +29,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         println!("1. Basic SELECT:");
         println!("   SQL: {}", select_query.to_sql()?);
-        println!("   Parameters: {:?}\n", select_query.parameters());
+        println!("   Parameters: {:?}\n", select_query.parameters().unwrap());
 
         // JOINs with aggregations
         let join_query = from("users")
@@ -55,7 +58,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let insert_query = insert("users").values(user_data);
         println!("3. INSERT:");
         println!("   SQL: {}", insert_query.to_sql()?);
-        println!("   Parameters: {:?}\n", insert_query.parameters());
+        println!("   Parameters: {:?}\n", insert_query.parameters().unwrap());
 
         // UPDATE
         let mut updates = HashMap::new();
@@ -69,7 +72,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         println!("4. UPDATE:");
         println!("   SQL: {}", update_query.to_sql()?);
-        println!("   Parameters: {:?}\n", update_query.parameters());
+        println!("   Parameters: {:?}\n", update_query.parameters().unwrap());
 
         // DELETE
         let delete_query = delete("users").where_(("age", op::LT, 13)).or_where((
@@ -80,7 +83,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         println!("5. DELETE:");
         println!("   SQL: {}", delete_query.to_sql()?);
-        println!("   Parameters: {:?}\n", delete_query.parameters());
+        println!("   Parameters: {:?}\n", delete_query.parameters().unwrap());
 
         // SQLite-specific features
         println!("6. SQLite-specific considerations:");
@@ -113,7 +116,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let insert_json_query = insert("users").values(json_data);
         println!("8. INSERT with JSON (SQLite stores as TEXT):");
         println!("   SQL: {}", insert_json_query.to_sql()?);
-        println!("   Parameters: {:?}\n", insert_json_query.parameters());
+        println!("   Parameters: {:?}\n", insert_json_query.parameters().unwrap());
 
         println!("=== All examples completed successfully! ===");
         println!("Note: This example demonstrates SQL generation only.");
@@ -14,6 +14,7 @@ struct User {
     balance: i32,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct Account {
     id: i32,
@@ -60,9 +61,9 @@ impl ConnectionPool for MockPool {
             let user: T = serde_json::from_value(user_json)?;
             Ok(user)
         } else {
-            return Err(archibald::Error::sql_generation(
+            Err(archibald::Error::sql_generation(
                 "No mock data for this type",
-            ));
+            ))
         }
     }
 
@@ -115,9 +116,9 @@ impl Transaction for MockTransaction {
             let user: T = serde_json::from_value(user_json)?;
             Ok(user)
         } else {
-            return Err(archibald::Error::sql_generation(
+            Err(archibald::Error::sql_generation(
                 "No mock data for this type",
-            ));
+            ))
         }
     }
 
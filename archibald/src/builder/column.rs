@@ -0,0 +1,316 @@
+//! Typed column references
+//!
+//! A [`Column<T>`] names a column of a table and records the Rust type of its
+//! values, so conditions built from it only accept values of that type. They
+//! are usually generated with `#[derive(Table)]` from `archibald-derive`, but
+//! can be declared by hand:
+//!
+//! ```
+//! use archibald::{from, Column, QueryBuilder};
+//!
+//! #[allow(non_upper_case_globals)]
+//! mod users {
+//!     use archibald::Column;
+//!     pub const id: Column<i32> = Column::new("users", "id");
+//!     pub const email: Column<String> = Column::new("users", "email");
+//! }
+//!
+//! let query = from("users")
+//!     .select((users::id, users::email))
+//!     .where_(users::email.eq("jane@example.com"))
+//!     .order_by_asc(users::id);
+//!
+//! assert_eq!(
+//!     query.to_sql().unwrap(),
+//!     "SELECT users.id, users.email FROM users WHERE users.email = ? ORDER BY users.id ASC"
+//! );
+//! ```
+//!
+//! Values of the wrong type are rejected at compile time:
+//!
+//! ```compile_fail
+//! # use archibald::{from, Column};
+//! # #[allow(non_upper_case_globals)]
+//! # mod users {
+//! #     pub const email: archibald::Column<String> = archibald::Column::new("users", "email");
+//! # }
+//! from("users").select("id").where_((users::email, 42));
+//! ```
+
+use super::common::IntoCondition;
+use crate::{ColumnSelector, Operator, Value};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A table whose columns are known at compile time
+pub trait Table {
+    /// Name of the table in the database
+    const TABLE_NAME: &'static str;
+}
+
+/// A column of `table` whose values have the Rust type `T`
+///
+/// Columns render qualified (`users.email`), which keeps them unambiguous in
+/// joins. Use [`name`](Column::name) where a bare column name is required,
+/// such as INSERT column lists.
+pub struct Column<T> {
+    table: &'static str,
+    name: &'static str,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Column<T> {
+    pub const fn new(table: &'static str, name: &'static str) -> Self {
+        Self {
+            table,
+            name,
+            _type: PhantomData,
+        }
+    }
+
+    /// The table the column belongs to
+    pub fn table(&self) -> &'static str {
+        self.table
+    }
+
+    /// The bare column name
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The column qualified with its table, e.g. `users.email`
+    pub fn qualified(&self) -> String {
+        format!("{}.{}", self.table, self.name)
+    }
+
+    /// Select the column under an alias
+    pub fn as_alias(self, alias: &str) -> ColumnSelector {
+        ColumnSelector::column(&self.qualified()).as_alias(alias)
+    }
+
+    fn condition(self, operator: Operator, value: Value) -> ColumnCondition {
+        ColumnCondition {
+            column: self.qualified(),
+            operator,
+            value,
+        }
+    }
+}
+
+impl<T> Column<T>
+where
+    T: Into<Value>,
+{
+    /// `column = value`
+    pub fn eq<V: Into<T>>(self, value: V) -> ColumnCondition {
+        self.condition(Operator::EQ, value.into().into())
+    }
+
+    /// `column != value`
+    pub fn ne<V: Into<T>>(self, value: V) -> ColumnCondition {
+        self.condition(Operator::NEQ, value.into().into())
+    }
+
+    /// `column > value`
+    pub fn gt<V: Into<T>>(self, value: V) -> ColumnCondition {
+        self.condition(Operator::GT, value.into().into())
+    }
+
+    /// `column >= value`
+    pub fn gte<V: Into<T>>(self, value: V) -> ColumnCondition {
+        self.condition(Operator::GTE, value.into().into())
+    }
+
+    /// `column < value`
+    pub fn lt<V: Into<T>>(self, value: V) -> ColumnCondition {
+        self.condition(Operator::LT, value.into().into())
+    }
+
+    /// `column <= value`
+    pub fn lte<V: Into<T>>(self, value: V) -> ColumnCondition {
+        self.condition(Operator::LTE, value.into().into())
+    }
+
+    /// `column IN (...)`
+    pub fn is_in<I, V>(self, values: I) -> ColumnCondition
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<T>,
+    {
+        self.condition(Operator::IN, list(values))
+    }
+
+    /// `column NOT IN (...)`
+    pub fn not_in<I, V>(self, values: I) -> ColumnCondition
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<T>,
+    {
+        self.condition(Operator::NOT_IN, list(values))
+    }
+}
+
+impl<T> Column<Option<T>> {
+    /// `column IS NULL`
+    pub fn is_null(self) -> ColumnCondition {
+        self.condition(Operator::IS_NULL, Value::Null)
+    }
+
+    /// `column IS NOT NULL`
+    pub fn is_not_null(self) -> ColumnCondition {
+        self.condition(Operator::IS_NOT_NULL, Value::Null)
+    }
+}
+
+impl Column<String> {
+    /// `column LIKE pattern`
+    pub fn like(self, pattern: &str) -> ColumnCondition {
+        self.condition(Operator::LIKE, pattern.into())
+    }
+}
+
+fn list<T, I, V>(values: I) -> Value
+where
+    T: Into<Value>,
+    I: IntoIterator<Item = V>,
+    V: Into<T>,
+{
+    Value::Array(values.into_iter().map(|v| v.into().into()).collect())
+}
+
+// Manual impls: derives would needlessly require `T: Clone` etc.
+impl<T> Clone for Column<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Column<T> {}
+
+impl<T> PartialEq for Column<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.table == other.table && self.name == other.name
+    }
+}
+
+impl<T> Eq for Column<T> {}
+
+impl<T> fmt::Debug for Column<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Column")
+            .field("table", &self.table)
+            .field("name", &self.name)
+            .field("type", &std::any::type_name::<T>())
+            .finish()
+    }
+}
+
+/// A condition on a typed column, created by methods such as [`Column::eq`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnCondition {
+    column: String,
+    operator: Operator,
+    value: Value,
+}
+
+impl IntoCondition for ColumnCondition {
+    fn into_condition(self) -> (String, Operator, Value) {
+        (self.column, self.operator, self.value)
+    }
+}
+
+// Typed shorthand equality: where_((users::age, 18))
+impl<T, V> IntoCondition for (Column<T>, V)
+where
+    T: Into<Value>,
+    V: Into<T>,
+{
+    fn into_condition(self) -> (String, Operator, Value) {
+        (self.0.qualified(), Operator::EQ, self.1.into().into())
+    }
+}
+
+// Typed explicit operators: where_((users::age, op::GT, 18))
+impl<T, O, V> IntoCondition for (Column<T>, O, V)
+where
+    T: Into<Value>,
+    O: crate::IntoOperator,
+    V: Into<T>,
+{
+    fn into_condition(self) -> (String, Operator, Value) {
+        (self.0.qualified(), self.1.into_operator(), self.2.into().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::PostgresDialect;
+    use crate::{QueryBuilder, from, op};
+
+    #[allow(non_upper_case_globals)]
+    mod users {
+        use super::Column;
+        pub const id: Column<i32> = Column::new("users", "id");
+        pub const name: Column<String> = Column::new("users", "name");
+        pub const nickname: Column<Option<String>> = Column::new("users", "nickname");
+    }
+
+    #[allow(non_upper_case_globals)]
+    mod posts {
+        use super::Column;
+        pub const user_id: Column<i32> = Column::new("posts", "user_id");
+        pub const title: Column<String> = Column::new("posts", "title");
+    }
+
+    #[test]
+    fn test_typed_conditions() {
+        let query = from("users")
+            .select((users::id, users::name.as_alias("n")))
+            .where_((users::id, op::GT, 10))
+            .where_(users::name.like("J%"))
+            .where_(users::nickname.is_not_null())
+            .or_where(users::id.is_in(vec![1, 2]));
+
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"SELECT "users"."id", "users"."name" AS "n" FROM "users" WHERE "users"."id" > $1 AND "users"."name" LIKE $2 AND "users"."nickname" IS NOT NULL OR "users"."id" = ANY($3)"#
+        );
+        assert_eq!(
+            query.parameters().unwrap(),
+            vec![Value::I32(10), Value::from("J%"), Value::I32(1), Value::I32(2)]
+        );
+    }
+
+    #[test]
+    fn test_typed_joins_grouping_and_ordering() {
+        let query = from("users")
+            .select((users::name, "COUNT(*)"))
+            .inner_join("posts", users::id, posts::user_id)
+            .where_((posts::title, "Hello"))
+            .group_by(users::name)
+            .order_by_desc(users::name);
+
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "SELECT users.name, COUNT(*) FROM users INNER JOIN posts ON users.id = posts.user_id WHERE posts.title = ? GROUP BY users.name ORDER BY users.name DESC"
+        );
+    }
+
+    #[test]
+    fn test_optional_columns_accept_plain_values() {
+        let (column, operator, value) = (users::nickname, "jj".to_string()).into_condition();
+
+        assert_eq!(column, "users.nickname");
+        assert_eq!(operator, Operator::EQ);
+        assert_eq!(value, Value::from("jj"));
+    }
+
+    #[test]
+    fn test_column_accessors() {
+        assert_eq!(users::name.table(), "users");
+        assert_eq!(users::name.name(), "name");
+        assert_eq!(users::name, users::name);
+        assert!(format!("{:?}", users::id).contains("i32"));
+    }
+}
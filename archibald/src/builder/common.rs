@@ -1,14 +1,65 @@
 //! Common types and traits shared across all query builders
 
-use crate::{IntoOperator, Operator, Result, Value};
+use super::raw::Raw;
+use super::select::{Subquery, SubqueryCondition};
+use super::table_ref::TableRef;
+use crate::dialect::{Dialect, GenericDialect};
+use crate::{Error, IntoOperator, Operator, Result, Value};
 
 /// Core trait for all query builders
 pub trait QueryBuilder {
-    /// Generate the SQL query string
-    fn to_sql(&self) -> Result<String>;
+    /// Generate the SQL query string using the generic dialect
+    fn to_sql(&self) -> Result<String> {
+        self.to_sql_with(&GenericDialect)
+    }
+
+    /// Generate the SQL query string for a specific dialect
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder};
+    /// use archibald::dialect::PostgresDialect;
+    ///
+    /// let sql = from("users").select("name").where_(("id", 1)).to_sql_with(&PostgresDialect).unwrap();
+    /// assert_eq!(sql, r#"SELECT "name" FROM "users" WHERE "id" = $1"#);
+    /// ```
+    fn to_sql_with(&self, dialect: &dyn Dialect) -> Result<String> {
+        Ok(self.to_sql_with_params(dialect)?.0)
+    }
 
-    /// Get the parameters for the query
-    fn parameters(&self) -> &[Value];
+    /// Generate the SQL query string for a specific dialect together with the
+    /// parameters bound to its placeholders, in placeholder order
+    ///
+    /// Some dialects bind values differently (PostgreSQL binds an IN list as a
+    /// single array), so executors should use these parameters rather than
+    /// [`parameters`](QueryBuilder::parameters).
+    fn to_sql_with_params(&self, dialect: &dyn Dialect) -> Result<(String, Vec<Value>)>;
+
+    /// Get the parameters for the query, in the order of the placeholders
+    /// emitted by [`to_sql`](QueryBuilder::to_sql)
+    ///
+    /// Parameters are collected by rendering the whole query tree (select list,
+    /// joins, WHERE, subqueries and HAVING), so they always line up with the
+    /// SQL. Fails with the same error as [`to_sql`](QueryBuilder::to_sql) when
+    /// the query cannot be rendered.
+    ///
+    /// Breaking change: this used to return `Vec<Value>` and silently drop
+    /// render errors. Callers now have to handle the `Result`.
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, op, QueryBuilder, Value};
+    ///
+    /// let query = from("users")
+    ///     .select("id")
+    ///     .where_(("age", op::GT, 18))
+    ///     .where_in("id", from("orders").select("user_id").where_(("total", op::GT, 100)));
+    ///
+    /// assert_eq!(query.parameters().unwrap(), vec![Value::I32(18), Value::I32(100)]);
+    /// ```
+    fn parameters(&self) -> Result<Vec<Value>> {
+        self.to_sql_with_params(&GenericDialect).map(|(_, params)| params)
+    }
 
     /// Clone the builder (for immutable chaining)
     fn clone_builder(&self) -> Self
@@ -16,6 +67,78 @@ pub trait QueryBuilder {
         Self: Sized;
 }
 
+/// Rendering state shared by a query and all of its subqueries
+///
+/// Collects bound values as placeholders are emitted, so nested queries
+/// continue the numbering of the outer query (`$1, $2, ...` on PostgreSQL)
+/// and the parameters always line up with the placeholders.
+pub(crate) struct RenderContext<'a> {
+    pub dialect: &'a dyn Dialect,
+    params: Vec<Value>,
+}
+
+impl<'a> RenderContext<'a> {
+    pub fn new(dialect: &'a dyn Dialect) -> Self {
+        Self {
+            dialect,
+            params: Vec::new(),
+        }
+    }
+
+    /// Bind a value, returning the placeholder to emit for it
+    pub fn bind(&mut self, value: &Value) -> String {
+        self.params.push(value.clone());
+        self.dialect.placeholder(self.params.len())
+    }
+
+    /// Render `column IN (...)` / `column NOT IN (...)` for a list of values
+    ///
+    /// Each element gets its own placeholder, unless the dialect can bind the
+    /// whole list as one array (`column = ANY($1)`). An empty list can never
+    /// match, so it renders as a constant predicate.
+    pub fn in_list(&mut self, column: &str, negated: bool, value: &Value) -> String {
+        let items = match value {
+            Value::Array(items) => items.as_slice(),
+            other => std::slice::from_ref(other),
+        };
+
+        if items.is_empty() {
+            return self.dialect.boolean_literal(negated).to_string();
+        }
+
+        if self.dialect.supports_array_parameters() && value.array_element_type().is_some() {
+            let placeholder = self.bind(value);
+            return if negated {
+                format!("{} <> ALL({})", column, placeholder)
+            } else {
+                format!("{} = ANY({})", column, placeholder)
+            };
+        }
+
+        let placeholders: Vec<String> = items.iter().map(|item| self.bind(item)).collect();
+        let keyword = if negated { "NOT IN" } else { "IN" };
+        format!("{} {} ({})", column, keyword, placeholders.join(", "))
+    }
+
+    /// Consume the context, returning the bound values in placeholder order
+    pub fn into_params(self) -> Vec<Value> {
+        self.params
+    }
+
+    /// Quote a table or column reference
+    pub fn ident(&self, reference: &str) -> String {
+        self.dialect.quote_reference(reference)
+    }
+
+    /// Render `expr AS alias` when an alias is present
+    pub fn aliased(&self, expr: String, alias: Option<&String>) -> String {
+        match alias {
+            Some(alias) => format!("{} AS {}", expr, self.ident(alias)),
+            None => expr,
+        }
+    }
+}
+
 /// Trait for conditions that can be used in WHERE clauses
 pub trait IntoCondition {
     fn into_condition(self) -> (String, Operator, Value);
@@ -51,6 +174,18 @@ pub struct WhereCondition {
     pub connector: WhereConnector,
 }
 
+impl WhereCondition {
+    /// Whether this is an `IN` / `NOT IN` condition over a list of values
+    pub fn is_in_list(&self) -> bool {
+        self.operator == Operator::IN || self.operator == Operator::NOT_IN
+    }
+
+    /// Whether this is an `IS NULL` / `IS NOT NULL` check, which binds no value
+    pub fn is_null_check(&self) -> bool {
+        self.operator == Operator::IS_NULL || self.operator == Operator::IS_NOT_NULL
+    }
+}
+
 /// How WHERE conditions are connected
 #[derive(Debug, Clone, PartialEq)]
 pub enum WhereConnector {
@@ -58,6 +193,347 @@ pub enum WhereConnector {
     Or,
 }
 
+impl WhereConnector {
+    fn as_sql(&self) -> &'static str {
+        match self {
+            WhereConnector::And => " AND ",
+            WhereConnector::Or => " OR ",
+        }
+    }
+}
+
+/// A node in a WHERE condition tree: a single condition, a comparison of two
+/// columns, a subquery predicate, a parenthesized group of nodes or a raw SQL
+/// fragment
+#[derive(Debug, Clone)]
+pub enum WhereNode {
+    Condition(WhereCondition),
+    Columns {
+        left: String,
+        operator: Operator,
+        right: String,
+        connector: WhereConnector,
+    },
+    Subquery(SubqueryCondition),
+    Group {
+        conditions: Vec<WhereNode>,
+        connector: WhereConnector,
+    },
+    Raw {
+        raw: Raw,
+        connector: WhereConnector,
+    },
+}
+
+impl WhereNode {
+    /// A node comparing two columns for equality
+    pub(crate) fn column_equals(left: &str, right: &str, connector: WhereConnector) -> Self {
+        WhereNode::Columns {
+            left: left.to_string(),
+            operator: Operator::EQ,
+            right: right.to_string(),
+            connector,
+        }
+    }
+
+    /// A node testing a column against a subquery (`IN`, `NOT IN`), or the
+    /// subquery alone (`EXISTS`, `NOT EXISTS`) when `column` is empty
+    pub(crate) fn subquery(
+        column: String,
+        operator: Operator,
+        subquery: Subquery,
+        connector: WhereConnector,
+    ) -> Self {
+        WhereNode::Subquery(SubqueryCondition {
+            column,
+            operator,
+            subquery,
+            connector,
+        })
+    }
+
+    /// How this node is connected to the node before it
+    pub fn connector(&self) -> &WhereConnector {
+        match self {
+            WhereNode::Condition(condition) => &condition.connector,
+            WhereNode::Columns { connector, .. } => connector,
+            WhereNode::Subquery(condition) => &condition.connector,
+            WhereNode::Group { connector, .. } => connector,
+            WhereNode::Raw { connector, .. } => connector,
+        }
+    }
+
+    /// Whether this node renders to nothing (a group without conditions)
+    fn is_empty(&self) -> bool {
+        match self {
+            WhereNode::Condition(_)
+            | WhereNode::Columns { .. }
+            | WhereNode::Subquery(_)
+            | WhereNode::Raw { .. } => false,
+            WhereNode::Group { conditions, .. } => conditions.iter().all(WhereNode::is_empty),
+        }
+    }
+
+    fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        match self {
+            WhereNode::Condition(condition) => render_value_comparison(
+                &condition.column,
+                &condition.operator,
+                &condition.value,
+                ctx,
+            ),
+            WhereNode::Columns { left, operator, right, .. } => {
+                operator.validate()?;
+                Ok(format!("{} {} {}", ctx.ident(left), operator.as_str(), ctx.ident(right)))
+            }
+            WhereNode::Subquery(condition) => condition.render(ctx),
+            WhereNode::Group { conditions, .. } => {
+                Ok(format!("({})", render_where_nodes(conditions, ctx)?))
+            }
+            WhereNode::Raw { raw, .. } => raw.render(ctx),
+        }
+    }
+}
+
+/// Render `column <operator> value`, binding the value
+///
+/// IN and NOT IN expand to one placeholder per list element (or a single
+/// array parameter where the dialect supports it), and IS NULL checks bind
+/// nothing.
+pub(crate) fn render_value_comparison(
+    column: &str,
+    operator: &Operator,
+    value: &Value,
+    ctx: &mut RenderContext,
+) -> Result<String> {
+    operator.validate()?;
+    let column = ctx.ident(column);
+    if *operator == Operator::IN || *operator == Operator::NOT_IN {
+        return Ok(ctx.in_list(&column, *operator == Operator::NOT_IN, value));
+    }
+    if *operator == Operator::IS_NULL || *operator == Operator::IS_NOT_NULL {
+        return Ok(format!("{} {}", column, operator.as_str()));
+    }
+    Ok(format!("{} {} {}", column, operator.as_str(), ctx.bind(value)))
+}
+
+/// Render a list of WHERE nodes joined by their connectors (without the WHERE keyword)
+pub(crate) fn render_where_nodes(nodes: &[WhereNode], ctx: &mut RenderContext) -> Result<String> {
+    let mut sql = String::new();
+    for node in nodes.iter().filter(|node| !node.is_empty()) {
+        if !sql.is_empty() {
+            sql.push_str(node.connector().as_sql());
+        }
+        sql.push_str(&node.render(ctx)?);
+    }
+    Ok(sql)
+}
+
+/// Builder for a parenthesized group of WHERE conditions
+///
+/// Passed to the closure given to `where_group` / `or_where_group`.
+///
+/// # Examples
+/// ```
+/// use archibald::{from, op, QueryBuilder};
+///
+/// let query = from("users")
+///     .select("*")
+///     .where_(("active", true))
+///     .where_group(|q| q.where_(("role", "admin")).or_where(("age", op::GT, 65)));
+///
+/// assert_eq!(
+///     query.to_sql().unwrap(),
+///     "SELECT * FROM users WHERE active = ? AND (role = ? OR age > ?)"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WhereGroupBuilder {
+    conditions: Vec<WhereNode>,
+}
+
+impl WhereGroupBuilder {
+    /// Create an empty group
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push_condition<C>(mut self, condition: C, connector: WhereConnector) -> Self
+    where
+        C: IntoCondition,
+    {
+        let (column, operator, value) = condition.into_condition();
+        self.conditions.push(WhereNode::Condition(WhereCondition {
+            column,
+            operator,
+            value,
+            connector,
+        }));
+        self
+    }
+
+    /// Add a WHERE condition to the group
+    pub fn where_<C>(self, condition: C) -> Self
+    where
+        C: IntoCondition,
+    {
+        self.push_condition(condition, WhereConnector::And)
+    }
+
+    /// Add an AND WHERE condition to the group (same as where_)
+    pub fn and_where<C>(self, condition: C) -> Self
+    where
+        C: IntoCondition,
+    {
+        self.where_(condition)
+    }
+
+    /// Add an OR WHERE condition to the group
+    pub fn or_where<C>(self, condition: C) -> Self
+    where
+        C: IntoCondition,
+    {
+        self.push_condition(condition, WhereConnector::Or)
+    }
+
+    /// Add a raw SQL condition to the group
+    pub fn where_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.conditions.push(WhereNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::And,
+        });
+        self
+    }
+
+    /// Add a raw SQL condition to the group joined with OR
+    pub fn or_where_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.conditions.push(WhereNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::Or,
+        });
+        self
+    }
+
+    fn push_subquery<Q>(
+        mut self,
+        column: String,
+        operator: Operator,
+        subquery: Q,
+        connector: WhereConnector,
+    ) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        self.conditions
+            .push(WhereNode::subquery(column, operator, subquery.into(), connector));
+        self
+    }
+
+    /// Add a WHERE IN condition with a subquery to the group
+    pub fn where_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        self.push_subquery(column.into_column_ref(), Operator::IN, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE IN condition with a subquery to the group joined with OR
+    pub fn or_where_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        self.push_subquery(column.into_column_ref(), Operator::IN, subquery, WhereConnector::Or)
+    }
+
+    /// Add a WHERE NOT IN condition with a subquery to the group
+    pub fn where_not_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        let column = column.into_column_ref();
+        self.push_subquery(column, Operator::NOT_IN, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE NOT IN condition with a subquery to the group joined with OR
+    pub fn or_where_not_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        let column = column.into_column_ref();
+        self.push_subquery(column, Operator::NOT_IN, subquery, WhereConnector::Or)
+    }
+
+    /// Add a WHERE EXISTS condition with a subquery to the group
+    pub fn where_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        self.push_subquery(String::new(), Operator::EXISTS, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE EXISTS condition with a subquery to the group joined with OR
+    pub fn or_where_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        self.push_subquery(String::new(), Operator::EXISTS, subquery, WhereConnector::Or)
+    }
+
+    /// Add a WHERE NOT EXISTS condition with a subquery to the group
+    pub fn where_not_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        self.push_subquery(String::new(), Operator::NOT_EXISTS, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE NOT EXISTS condition with a subquery to the group joined with OR
+    pub fn or_where_not_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        self.push_subquery(String::new(), Operator::NOT_EXISTS, subquery, WhereConnector::Or)
+    }
+
+    /// Add a nested group joined with AND
+    pub fn where_group<F>(mut self, build: F) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        self.conditions
+            .push(build(WhereGroupBuilder::new()).into_node(WhereConnector::And));
+        self
+    }
+
+    /// Add a nested group joined with OR
+    pub fn or_where_group<F>(mut self, build: F) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        self.conditions
+            .push(build(WhereGroupBuilder::new()).into_node(WhereConnector::Or));
+        self
+    }
+
+    /// Turn the group into a tree node with the given connector
+    pub fn into_node(self, connector: WhereConnector) -> WhereNode {
+        WhereNode::Group {
+            conditions: self.conditions,
+            connector,
+        }
+    }
+}
+
 /// Aggregation function types
 #[derive(Debug, Clone, PartialEq)]
 pub enum AggregateFunction {
@@ -113,42 +589,60 @@ impl IntoColumns for Vec<&str> {
     }
 }
 
-// For tuples
-impl IntoColumns for (&str, &str) {
+impl<T> IntoColumns for super::column::Column<T> {
     fn into_columns(self) -> Vec<String> {
-        vec![self.0.to_string(), self.1.to_string()]
+        vec![self.qualified()]
     }
 }
 
-impl IntoColumns for (&str, &str, &str) {
-    fn into_columns(self) -> Vec<String> {
-        vec![self.0.to_string(), self.1.to_string(), self.2.to_string()]
+/// A single column reference: a string or a typed [`Column`](crate::Column)
+pub trait IntoColumnRef {
+    fn into_column_ref(self) -> String;
+}
+
+impl IntoColumnRef for &str {
+    fn into_column_ref(self) -> String {
+        self.to_string()
     }
 }
 
-impl IntoColumns for (&str, &str, &str, &str) {
-    fn into_columns(self) -> Vec<String> {
-        vec![
-            self.0.to_string(),
-            self.1.to_string(),
-            self.2.to_string(),
-            self.3.to_string(),
-        ]
+impl IntoColumnRef for String {
+    fn into_column_ref(self) -> String {
+        self
     }
 }
 
-impl IntoColumns for (&str, &str, &str, &str, &str) {
-    fn into_columns(self) -> Vec<String> {
-        vec![
-            self.0.to_string(),
-            self.1.to_string(),
-            self.2.to_string(),
-            self.3.to_string(),
-            self.4.to_string(),
-        ]
+impl IntoColumnRef for &String {
+    fn into_column_ref(self) -> String {
+        self.clone()
     }
 }
 
+impl<T> IntoColumnRef for super::column::Column<T> {
+    fn into_column_ref(self) -> String {
+        self.qualified()
+    }
+}
+
+// Tuples of column references, which may mix strings and typed columns
+macro_rules! impl_into_columns_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: IntoColumnRef),+> IntoColumns for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_columns(self) -> Vec<String> {
+                let ($($name,)+) = self;
+                vec![$($name.into_column_ref()),+]
+            }
+        }
+    };
+}
+
+impl_into_columns_for_tuple!(A, B);
+impl_into_columns_for_tuple!(A, B, C);
+impl_into_columns_for_tuple!(A, B, C, D);
+impl_into_columns_for_tuple!(A, B, C, D, E);
+impl_into_columns_for_tuple!(A, B, C, D, E, F);
+
 /// JOIN types
 #[derive(Debug, Clone, PartialEq)]
 pub enum JoinType {
@@ -178,21 +672,252 @@ pub enum JoinConnector {
     Or,
 }
 
+/// The right-hand side of a JOIN ON condition
+#[derive(Debug, Clone, PartialEq)]
+pub enum JoinOperand {
+    /// Another column, as in `users.id = orders.user_id`
+    Column(String),
+    /// A value bound as a parameter, as in `orders.status = ?`
+    Value(Value),
+}
+
 /// A condition in a JOIN ON clause
 #[derive(Debug, Clone, PartialEq)]
 pub struct JoinCondition {
     pub left_column: String,
     pub operator: Operator,
-    pub right_column: String,
+    pub right: JoinOperand,
     pub connector: JoinConnector,
 }
 
+impl JoinCondition {
+    fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        match &self.right {
+            JoinOperand::Column(right_column) => {
+                self.operator.validate()?;
+                Ok(format!(
+                    "{} {} {}",
+                    ctx.ident(&self.left_column),
+                    self.operator.as_str(),
+                    ctx.ident(right_column)
+                ))
+            }
+            JoinOperand::Value(value) => {
+                render_value_comparison(&self.left_column, &self.operator, value, ctx)
+            }
+        }
+    }
+}
+
 /// A complete JOIN clause with table and conditions
-#[derive(Debug, Clone, PartialEq)]
+///
+/// A join has either ON conditions or USING columns (or neither, for CROSS
+/// JOIN).
+#[derive(Debug, Clone)]
 pub struct JoinClause {
     pub join_type: JoinType,
-    pub table: String,
+    pub table: TableRef,
     pub on_conditions: Vec<JoinCondition>,
+    pub using_columns: Vec<String>,
+}
+
+impl JoinClause {
+    /// Render the join, starting with the join type
+    pub(crate) fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        let mut sql = format!("{} JOIN {}", self.join_type, self.table.render(ctx)?);
+
+        if !self.on_conditions.is_empty() && !self.using_columns.is_empty() {
+            return Err(Error::invalid_query(format!(
+                "JOIN {} cannot have both ON conditions and USING columns",
+                self.table.reference()
+            )));
+        }
+
+        if self.join_type != JoinType::Cross
+            && self.on_conditions.is_empty()
+            && self.using_columns.is_empty()
+        {
+            return Err(Error::invalid_query(format!(
+                "{} JOIN {} needs ON conditions or USING columns",
+                self.join_type,
+                self.table.reference()
+            )));
+        }
+
+        if !self.using_columns.is_empty() {
+            let columns: Vec<String> = self.using_columns.iter().map(|c| ctx.ident(c)).collect();
+            sql.push_str(&format!(" USING ({})", columns.join(", ")));
+        }
+
+        for (i, condition) in self.on_conditions.iter().enumerate() {
+            sql.push_str(match (i, &condition.connector) {
+                (0, _) => " ON ",
+                (_, JoinConnector::And) => " AND ",
+                (_, JoinConnector::Or) => " OR ",
+            });
+            sql.push_str(&condition.render(ctx)?);
+        }
+
+        Ok(sql)
+    }
+}
+
+/// Builder for the conditions of a JOIN
+///
+/// Passed to the closure given to `join_with` / `left_join_with`. Conditions
+/// are rendered in the order they are added, and values are bound as
+/// parameters in that order too.
+///
+/// # Examples
+/// ```
+/// use archibald::{from, QueryBuilder};
+///
+/// let query = from("users")
+///     .join_with("orders", |j| {
+///         j.on("users.id", "=", "orders.user_id")
+///             .and_on_val("orders.status", "paid")
+///             .or_on("users.id", "=", "orders.gifted_to")
+///     })
+///     .select("users.id");
+/// assert_eq!(
+///     query.to_sql().unwrap(),
+///     "SELECT users.id FROM users INNER JOIN orders ON users.id = orders.user_id AND orders.status = ? OR users.id = orders.gifted_to"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct JoinBuilder {
+    clause: JoinClause,
+}
+
+impl JoinBuilder {
+    pub(crate) fn new(join_type: JoinType, table: TableRef) -> Self {
+        Self {
+            clause: JoinClause {
+                join_type,
+                table,
+                on_conditions: Vec::new(),
+                using_columns: Vec::new(),
+            },
+        }
+    }
+
+    fn push(
+        mut self,
+        left_column: String,
+        operator: Operator,
+        right: JoinOperand,
+        connector: JoinConnector,
+    ) -> Self {
+        self.clause.on_conditions.push(JoinCondition {
+            left_column,
+            operator,
+            right,
+            connector,
+        });
+        self
+    }
+
+    /// Add a condition comparing two columns
+    pub fn on<L, O, R>(self, left_column: L, operator: O, right_column: R) -> Self
+    where
+        L: IntoColumnRef,
+        O: IntoOperator,
+        R: IntoColumnRef,
+    {
+        let (left, right) = (left_column.into_column_ref(), right_column.into_column_ref());
+        self.push(left, operator.into_operator(), JoinOperand::Column(right), JoinConnector::And)
+    }
+
+    /// Add a condition comparing two columns, joined with AND (same as on)
+    pub fn and_on<L, O, R>(self, left_column: L, operator: O, right_column: R) -> Self
+    where
+        L: IntoColumnRef,
+        O: IntoOperator,
+        R: IntoColumnRef,
+    {
+        self.on(left_column, operator, right_column)
+    }
+
+    /// Add a condition comparing two columns, joined with OR
+    pub fn or_on<L, O, R>(self, left_column: L, operator: O, right_column: R) -> Self
+    where
+        L: IntoColumnRef,
+        O: IntoOperator,
+        R: IntoColumnRef,
+    {
+        let (left, right) = (left_column.into_column_ref(), right_column.into_column_ref());
+        self.push(left, operator.into_operator(), JoinOperand::Column(right), JoinConnector::Or)
+    }
+
+    /// Add a `column = value` condition, binding the value
+    pub fn on_val<C, V>(self, column: C, value: V) -> Self
+    where
+        C: IntoColumnRef,
+        V: Into<Value>,
+    {
+        let right = JoinOperand::Value(value.into());
+        self.push(column.into_column_ref(), Operator::EQ, right, JoinConnector::And)
+    }
+
+    /// Add a `column = value` condition joined with AND (same as on_val)
+    pub fn and_on_val<C, V>(self, column: C, value: V) -> Self
+    where
+        C: IntoColumnRef,
+        V: Into<Value>,
+    {
+        self.on_val(column, value)
+    }
+
+    /// Add a `column = value` condition joined with OR
+    pub fn or_on_val<C, V>(self, column: C, value: V) -> Self
+    where
+        C: IntoColumnRef,
+        V: Into<Value>,
+    {
+        let right = JoinOperand::Value(value.into());
+        self.push(column.into_column_ref(), Operator::EQ, right, JoinConnector::Or)
+    }
+
+    /// Add a condition on a value with any operator, joined with AND
+    ///
+    /// Takes the same tuples as `where_`, such as `("orders.total", op::GT, 100)`.
+    pub fn and_on_where<C>(self, condition: C) -> Self
+    where
+        C: IntoCondition,
+    {
+        let (column, operator, value) = condition.into_condition();
+        self.push(column, operator, JoinOperand::Value(value), JoinConnector::And)
+    }
+
+    /// Add a condition on a value with any operator, joined with OR
+    pub fn or_on_where<C>(self, condition: C) -> Self
+    where
+        C: IntoCondition,
+    {
+        let (column, operator, value) = condition.into_condition();
+        self.push(column, operator, JoinOperand::Value(value), JoinConnector::Or)
+    }
+
+    /// Join on columns with the same name in both tables: `USING (columns)`
+    pub fn using<C>(mut self, columns: C) -> Self
+    where
+        C: IntoColumns,
+    {
+        self.clause.using_columns.extend(columns.into_columns());
+        self
+    }
+
+    pub(crate) fn build(self) -> JoinClause {
+        self.clause
+    }
+}
+
+/// A JOIN in a query: a structured clause or a raw SQL fragment holding the
+/// whole join (`LEFT JOIN ... ON ...`)
+#[derive(Debug, Clone)]
+pub enum JoinNode {
+    Clause(JoinClause),
+    Raw(Raw),
 }
 
 /// Sort direction for ORDER BY clauses
@@ -218,6 +943,27 @@ pub struct OrderByClause {
     pub direction: SortDirection,
 }
 
+/// An ORDER BY item: a column with a direction or a raw SQL fragment
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderByNode {
+    Clause(OrderByClause),
+    Raw(Raw),
+}
+
+/// Render ORDER BY items separated by commas (without the ORDER BY keyword)
+pub(crate) fn render_order_by(nodes: &[OrderByNode], ctx: &mut RenderContext) -> Result<String> {
+    let mut parts = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        parts.push(match node {
+            OrderByNode::Clause(clause) => {
+                format!("{} {}", ctx.ident(&clause.column), clause.direction)
+            }
+            OrderByNode::Raw(raw) => raw.render(ctx)?,
+        });
+    }
+    Ok(parts.join(", "))
+}
+
 /// A GROUP BY clause
 #[derive(Debug, Clone, PartialEq)]
 pub struct GroupByClause {
@@ -233,6 +979,26 @@ pub struct HavingCondition {
     pub connector: WhereConnector,
 }
 
+/// A HAVING item: a single condition or a raw SQL fragment
+#[derive(Debug, Clone, PartialEq)]
+pub enum HavingNode {
+    Condition(HavingCondition),
+    Raw {
+        raw: Raw,
+        connector: WhereConnector,
+    },
+}
+
+impl HavingNode {
+    /// How this node is connected to the node before it
+    pub fn connector(&self) -> &WhereConnector {
+        match self {
+            HavingNode::Condition(condition) => &condition.connector,
+            HavingNode::Raw { connector, .. } => connector,
+        }
+    }
+}
+
 /// Trait to convert various types into column selectors
 pub trait IntoColumnSelectors {
     fn into_column_selectors(self) -> Vec<crate::ColumnSelector>;
@@ -284,76 +1050,73 @@ impl IntoColumnSelectors for Vec<crate::ColumnSelector> {
     }
 }
 
-// Tuple implementations for IntoColumnSelectors
-impl IntoColumnSelectors for (&str, &str) {
+impl IntoColumnSelectors for Raw {
     fn into_column_selectors(self) -> Vec<crate::ColumnSelector> {
-        vec![
-            crate::ColumnSelector::Column { name: self.0.to_string(), alias: None },
-            crate::ColumnSelector::Column { name: self.1.to_string(), alias: None },
-        ]
+        vec![crate::ColumnSelector::raw(self)]
     }
 }
 
-impl IntoColumnSelectors for (&str, &str, &str) {
+impl<T> IntoColumnSelectors for super::column::Column<T> {
     fn into_column_selectors(self) -> Vec<crate::ColumnSelector> {
-        vec![
-            crate::ColumnSelector::Column { name: self.0.to_string(), alias: None },
-            crate::ColumnSelector::Column { name: self.1.to_string(), alias: None },
-            crate::ColumnSelector::Column { name: self.2.to_string(), alias: None },
-        ]
+        vec![crate::ColumnSelector::column(&self.qualified())]
     }
 }
 
-impl IntoColumnSelectors for (&str, &str, &str, &str) {
-    fn into_column_selectors(self) -> Vec<crate::ColumnSelector> {
-        vec![
-            crate::ColumnSelector::Column { name: self.0.to_string(), alias: None },
-            crate::ColumnSelector::Column { name: self.1.to_string(), alias: None },
-            crate::ColumnSelector::Column { name: self.2.to_string(), alias: None },
-            crate::ColumnSelector::Column { name: self.3.to_string(), alias: None },
-        ]
+/// A single selected column: a string, a typed column or a
+/// [`ColumnSelector`](crate::ColumnSelector)
+pub trait IntoColumnSelector {
+    fn into_column_selector(self) -> crate::ColumnSelector;
+}
+
+impl IntoColumnSelector for &str {
+    fn into_column_selector(self) -> crate::ColumnSelector {
+        crate::ColumnSelector::Column { name: self.to_string(), alias: None }
     }
 }
 
-impl IntoColumnSelectors for (&str, &str, &str, &str, &str) {
-    fn into_column_selectors(self) -> Vec<crate::ColumnSelector> {
-        vec![
-            crate::ColumnSelector::Column { name: self.0.to_string(), alias: None },
-            crate::ColumnSelector::Column { name: self.1.to_string(), alias: None },
-            crate::ColumnSelector::Column { name: self.2.to_string(), alias: None },
-            crate::ColumnSelector::Column { name: self.3.to_string(), alias: None },
-            crate::ColumnSelector::Column { name: self.4.to_string(), alias: None },
-        ]
+impl IntoColumnSelector for String {
+    fn into_column_selector(self) -> crate::ColumnSelector {
+        crate::ColumnSelector::Column { name: self, alias: None }
     }
 }
 
-// Support mixed tuples with ColumnSelectors
-impl IntoColumnSelectors for (&str, crate::ColumnSelector) {
-    fn into_column_selectors(self) -> Vec<crate::ColumnSelector> {
-        vec![crate::ColumnSelector::Column { name: self.0.to_string(), alias: None }, self.1]
+impl IntoColumnSelector for crate::ColumnSelector {
+    fn into_column_selector(self) -> crate::ColumnSelector {
+        self
     }
 }
 
-impl IntoColumnSelectors for (&str, crate::ColumnSelector, crate::ColumnSelector) {
-    fn into_column_selectors(self) -> Vec<crate::ColumnSelector> {
-        vec![
-            crate::ColumnSelector::Column { name: self.0.to_string(), alias: None },
-            self.1,
-            self.2,
-        ]
+impl<T> IntoColumnSelector for super::column::Column<T> {
+    fn into_column_selector(self) -> crate::ColumnSelector {
+        crate::ColumnSelector::column(&self.qualified())
     }
 }
 
-impl IntoColumnSelectors for (crate::ColumnSelector, &str, crate::ColumnSelector) {
-    fn into_column_selectors(self) -> Vec<crate::ColumnSelector> {
-        vec![
-            self.0,
-            crate::ColumnSelector::Column { name: self.1.to_string(), alias: None },
-            self.2,
-        ]
+impl IntoColumnSelector for Raw {
+    fn into_column_selector(self) -> crate::ColumnSelector {
+        crate::ColumnSelector::raw(self)
     }
 }
 
+// Tuples of selectors, which may mix strings, typed columns and ColumnSelectors
+macro_rules! impl_into_column_selectors_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: IntoColumnSelector),+> IntoColumnSelectors for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_column_selectors(self) -> Vec<crate::ColumnSelector> {
+                let ($($name,)+) = self;
+                vec![$($name.into_column_selector()),+]
+            }
+        }
+    };
+}
+
+impl_into_column_selectors_for_tuple!(A, B);
+impl_into_column_selectors_for_tuple!(A, B, C);
+impl_into_column_selectors_for_tuple!(A, B, C, D);
+impl_into_column_selectors_for_tuple!(A, B, C, D, E);
+impl_into_column_selectors_for_tuple!(A, B, C, D, E, F);
+
 // Forward declarations - these will be defined in select.rs
 // pub struct Subquery;
 // pub struct SubqueryCondition;
@@ -404,4 +1167,168 @@ mod tests {
         let cols = vec!["name", "age"].into_columns();
         assert_eq!(cols, vec!["name", "age"]);
     }
+
+    mod placeholder_properties {
+        use super::*;
+        use crate::dialect::{MySqlDialect, PostgresDialect, SqliteDialect};
+        use crate::{delete, from, insert, update, ColumnSelector, SelectBuilderComplete};
+        use proptest::prelude::*;
+        use std::collections::HashMap;
+
+        const COLUMNS: [&str; 4] = ["a", "b", "c", "d"];
+
+        type Condition = (String, Operator, Value);
+
+        fn dialects() -> [&'static dyn Dialect; 4] {
+            [&GenericDialect, &PostgresDialect, &SqliteDialect, &MySqlDialect]
+        }
+
+        /// Assert that the SQL has one placeholder per parameter and, for
+        /// numbered placeholders, that they run `$1..$n` in order
+        fn assert_placeholders_match(sql: &str, params: &[Value], dialect: &dyn Dialect) {
+            if dialect.placeholder(2) == "?" {
+                assert_eq!(sql.matches('?').count(), params.len(), "{}", sql);
+                return;
+            }
+
+            let numbers: Vec<usize> = sql
+                .split('$')
+                .skip(1)
+                .map(|rest| {
+                    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+                    digits.parse().unwrap()
+                })
+                .collect();
+            let expected: Vec<usize> = (1..=params.len()).collect();
+            assert_eq!(numbers, expected, "{}", sql);
+        }
+
+        fn check<Q: QueryBuilder>(query: &Q) {
+            for dialect in dialects() {
+                let (sql, params) = query.to_sql_with_params(dialect).unwrap();
+                assert_placeholders_match(&sql, &params, dialect);
+            }
+            let placeholders = query.to_sql().unwrap().matches('?').count();
+            assert_eq!(query.parameters().unwrap().len(), placeholders);
+        }
+
+        fn scalar() -> impl Strategy<Value = Value> {
+            prop_oneof![
+                any::<i32>().prop_map(Value::from),
+                any::<bool>().prop_map(Value::from),
+                "[a-z]{0,6}".prop_map(Value::from),
+            ]
+        }
+
+        fn condition() -> impl Strategy<Value = Condition> {
+            let column = prop::sample::select(&COLUMNS[..]).prop_map(String::from);
+            prop_oneof![
+                (column.clone(), prop::sample::select(vec![op::EQ, op::GT, op::NEQ]), scalar()),
+                (
+                    column,
+                    prop::sample::select(vec![op::IN, op::NOT_IN]),
+                    prop::collection::vec(any::<i32>(), 0..4).prop_map(Value::from),
+                ),
+            ]
+        }
+
+        fn conditions() -> impl Strategy<Value = Vec<Condition>> {
+            prop::collection::vec(condition(), 1..5)
+        }
+
+        fn set_data() -> impl Strategy<Value = HashMap<String, Value>> {
+            prop::collection::hash_map(
+                prop::sample::select(&COLUMNS[..]).prop_map(String::from),
+                scalar(),
+                1..4,
+            )
+        }
+
+        fn as_condition((column, operator, value): &Condition) -> (&str, Operator, Value) {
+            (column.as_str(), operator.clone(), value.clone())
+        }
+
+        fn filtered_select(table: &str, conditions: &[Condition]) -> SelectBuilderComplete {
+            conditions.iter().fold(from(table).select("id"), |query, condition| {
+                query.where_(as_condition(condition))
+            })
+        }
+
+        proptest! {
+            #[test]
+            fn select_placeholders_match_parameters(
+                outer in conditions(),
+                grouped in conditions(),
+                inner in conditions(),
+                column_sub in conditions(),
+                having in scalar(),
+            ) {
+                let mut query = from("t")
+                    .select((
+                        "a",
+                        ColumnSelector::subquery_as(filtered_select("s", &column_sub), "sub"),
+                    ))
+                    .where_in("b", filtered_select("u", &inner));
+
+                for (i, condition) in outer.iter().enumerate() {
+                    query = if i % 2 == 0 {
+                        query.where_(as_condition(condition))
+                    } else {
+                        query.or_where(as_condition(condition))
+                    };
+                }
+
+                let query = query
+                    .where_group(|group| {
+                        grouped
+                            .iter()
+                            .fold(group, |group, condition| group.or_where(as_condition(condition)))
+                    })
+                    .where_exists(filtered_select("v", &inner))
+                    .group_by("a")
+                    .having(("COUNT(*)", op::GT, having));
+
+                check(&query);
+            }
+
+            #[test]
+            fn insert_placeholders_match_parameters(
+                rows in prop::collection::vec(set_data(), 1..4),
+            ) {
+                // Every row needs the columns of the first one
+                let columns: Vec<String> = rows[0].keys().cloned().collect();
+                let rows: Vec<HashMap<String, Value>> = rows
+                    .into_iter()
+                    .map(|row| {
+                        columns
+                            .iter()
+                            .map(|c| (c.clone(), row.get(c).cloned().unwrap_or(Value::Null)))
+                            .collect()
+                    })
+                    .collect();
+
+                check(&insert("t").values_many(rows));
+            }
+
+            #[test]
+            fn update_placeholders_match_parameters(data in set_data(), filters in conditions()) {
+                let query = filters[1..].iter().fold(
+                    update("t").set(data).where_(as_condition(&filters[0])),
+                    |query, condition| query.or_where(as_condition(condition)),
+                );
+
+                check(&query);
+            }
+
+            #[test]
+            fn delete_placeholders_match_parameters(filters in conditions()) {
+                let query = filters[1..].iter().fold(
+                    delete("t").where_(as_condition(&filters[0])),
+                    |query, condition| query.and_where(as_condition(condition)),
+                );
+
+                check(&query);
+            }
+        }
+    }
 }
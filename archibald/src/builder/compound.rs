@@ -0,0 +1,387 @@
+//! Compound SELECT queries combined with set operators
+
+use super::common::{
+    IntoColumnRef, OrderByClause, OrderByNode, QueryBuilder, RenderContext, SortDirection,
+    render_order_by,
+};
+use super::raw::Raw;
+use super::select::{SelectBuilderComplete, SelectQuery};
+use crate::dialect::Dialect;
+use crate::{Result, Value};
+
+/// Operator combining two SELECT queries
+#[derive(Debug, Clone, PartialEq)]
+pub enum SetOperator {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+
+impl std::fmt::Display for SetOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SetOperator::Union => write!(f, "UNION"),
+            SetOperator::UnionAll => write!(f, "UNION ALL"),
+            SetOperator::Intersect => write!(f, "INTERSECT"),
+            SetOperator::Except => write!(f, "EXCEPT"),
+        }
+    }
+}
+
+/// SELECT queries combined with set operators
+///
+/// Created with [`union`](SelectBuilderComplete::union),
+/// [`union_all`](SelectBuilderComplete::union_all),
+/// [`intersect`](SelectBuilderComplete::intersect) or
+/// [`except`](SelectBuilderComplete::except); more queries can be chained onto
+/// it. Operators apply left to right, so `a.union(b).intersect(c)` means
+/// `(a UNION b) INTERSECT c`. Parameters are numbered across all of the
+/// queries.
+///
+/// ORDER BY, LIMIT and OFFSET on the compound builder apply to the combined
+/// result. A combined query with its own ORDER BY or LIMIT is parenthesized
+/// (on SQLite, which doesn't allow that, it is selected from as a subquery).
+///
+/// # Examples
+/// ```
+/// use archibald::{from, QueryBuilder};
+/// use archibald::dialect::PostgresDialect;
+///
+/// let query = from("customers")
+///     .select("email")
+///     .where_(("active", true))
+///     .union_all(from("leads").select("email"))
+///     .union_all(from("partners").select("email").where_(("active", true)));
+/// assert_eq!(
+///     query.to_sql_with(&PostgresDialect).unwrap(),
+///     r#"SELECT "email" FROM "customers" WHERE "active" = $1 UNION ALL SELECT "email" FROM "leads" UNION ALL SELECT "email" FROM "partners" WHERE "active" = $2"#
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompoundSelectBuilder {
+    first: SelectQuery,
+    rest: Vec<(SetOperator, SelectQuery)>,
+    order_by_clauses: Vec<OrderByNode>,
+    limit_value: Option<u64>,
+    offset_value: Option<u64>,
+}
+
+impl CompoundSelectBuilder {
+    pub(crate) fn new<Q>(first: SelectBuilderComplete, operator: SetOperator, next: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        Self {
+            first: first.into(),
+            rest: vec![(operator, next.into())],
+            order_by_clauses: Vec::new(),
+            limit_value: None,
+            offset_value: None,
+        }
+    }
+
+    fn push<Q>(mut self, operator: SetOperator, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.rest.push((operator, query.into()));
+        self
+    }
+
+    /// Append another query with UNION, removing duplicate rows
+    pub fn union<Q>(self, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.push(SetOperator::Union, query)
+    }
+
+    /// Append another query with UNION ALL
+    pub fn union_all<Q>(self, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.push(SetOperator::UnionAll, query)
+    }
+
+    /// Keep only the rows also returned by another query (INTERSECT)
+    pub fn intersect<Q>(self, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.push(SetOperator::Intersect, query)
+    }
+
+    /// Remove the rows returned by another query (EXCEPT)
+    pub fn except<Q>(self, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.push(SetOperator::Except, query)
+    }
+
+    /// Order the combined result
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder, SortDirection};
+    ///
+    /// let query = from("customers")
+    ///     .select(("id", "email"))
+    ///     .union(from("leads").select(("id", "email")))
+    ///     .order_by("email", SortDirection::Asc)
+    ///     .limit(10)
+    ///     .offset(20);
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT id, email FROM customers UNION SELECT id, email FROM leads ORDER BY email ASC LIMIT 10 OFFSET 20"
+    /// );
+    /// ```
+    pub fn order_by<C>(mut self, column: C, direction: SortDirection) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        self.order_by_clauses
+            .push(OrderByNode::Clause(OrderByClause {
+                column: column.into_column_ref(),
+                direction,
+            }));
+        self
+    }
+
+    /// Order the combined result ascending (convenience method)
+    pub fn order_by_asc<C>(self, column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        self.order_by(column, SortDirection::Asc)
+    }
+
+    /// Order the combined result descending (convenience method)
+    pub fn order_by_desc<C>(self, column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        self.order_by(column, SortDirection::Desc)
+    }
+
+    /// Order the combined result by a raw SQL expression, including its direction
+    pub fn order_by_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.order_by_clauses.push(OrderByNode::Raw(raw.into()));
+        self
+    }
+
+    /// Limit the number of combined rows
+    pub fn limit(mut self, count: u64) -> Self {
+        self.limit_value = Some(count);
+        self
+    }
+
+    /// Skip rows of the combined result
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset_value = Some(offset);
+        self
+    }
+
+    /// Render the combined queries, continuing the outer placeholder numbering
+    pub(crate) fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        let nested = ctx.dialect.supports_parenthesized_set_operands();
+
+        let mut sql = render_operand(&self.first, ctx)?;
+        // Whether `sql` is a chain containing UNION or EXCEPT, which INTERSECT
+        // binds more tightly than on dialects with operator precedence
+        let mut loose = false;
+        for (operator, query) in &self.rest {
+            if *operator == SetOperator::Intersect && loose && nested {
+                sql = format!("({})", sql);
+            }
+            loose |= *operator != SetOperator::Intersect;
+
+            sql.push_str(&format!(" {} ", operator));
+            sql.push_str(&render_operand(query, ctx)?);
+        }
+
+        // ORDER BY clause
+        if !self.order_by_clauses.is_empty() {
+            sql.push_str(" ORDER BY ");
+            sql.push_str(&render_order_by(&self.order_by_clauses, ctx)?);
+        }
+
+        // LIMIT / OFFSET clauses
+        sql.push_str(
+            &ctx.dialect
+                .limit_offset(self.limit_value, self.offset_value),
+        );
+
+        Ok(sql)
+    }
+}
+
+/// Render one of the combined queries
+///
+/// A query that would change meaning when spliced into the chain (one with
+/// its own ORDER BY, LIMIT, OFFSET or WITH, or a compound query) is wrapped
+/// in parentheses, or selected from as a subquery where parenthesized operands
+/// aren't allowed.
+fn render_operand(query: &SelectQuery, ctx: &mut RenderContext) -> Result<String> {
+    let wrap = match query {
+        SelectQuery::Select(select) => {
+            !select.order_by_clauses.is_empty()
+                || select.limit_value.is_some()
+                || select.offset_value.is_some()
+                || !select.ctes.is_empty()
+        }
+        SelectQuery::Compound(_) => true,
+        SelectQuery::Raw(_) => false,
+    };
+
+    let sql = query.render(ctx)?;
+    Ok(if !wrap {
+        sql
+    } else if ctx.dialect.supports_parenthesized_set_operands() {
+        format!("({})", sql)
+    } else {
+        format!("SELECT * FROM ({})", sql)
+    })
+}
+
+impl QueryBuilder for CompoundSelectBuilder {
+    fn to_sql_with_params(&self, dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
+        let mut ctx = RenderContext::new(dialect);
+        let sql = self.render(&mut ctx)?;
+        Ok((sql, ctx.into_params()))
+    }
+
+    fn clone_builder(&self) -> Self {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MySqlDialect, PostgresDialect, SqliteDialect};
+    use crate::{from, op};
+
+    #[test]
+    fn test_union_all_numbers_placeholders_across_queries() {
+        let query = from("orders")
+            .select("id")
+            .where_(("total", op::GT, 100))
+            .union_all(
+                from("refunds")
+                    .select("order_id")
+                    .where_(("amount", op::GT, 50)),
+            )
+            .union_all(from("disputes").select("order_id").where_(("open", true)));
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"SELECT "id" FROM "orders" WHERE "total" > $1 UNION ALL SELECT "order_id" FROM "refunds" WHERE "amount" > $2 UNION ALL SELECT "order_id" FROM "disputes" WHERE "open" = $3"#
+        );
+        assert_eq!(
+            params,
+            vec![Value::I32(100), Value::I32(50), Value::Bool(true)]
+        );
+    }
+
+    #[test]
+    fn test_set_operators() {
+        let a = from("a").select("id");
+        let b = from("b").select("id");
+
+        assert_eq!(
+            a.clone().union(b.clone()).to_sql().unwrap(),
+            "SELECT id FROM a UNION SELECT id FROM b"
+        );
+        assert_eq!(
+            a.clone().intersect(b.clone()).to_sql().unwrap(),
+            "SELECT id FROM a INTERSECT SELECT id FROM b"
+        );
+        assert_eq!(
+            a.except(b).to_sql().unwrap(),
+            "SELECT id FROM a EXCEPT SELECT id FROM b"
+        );
+    }
+
+    #[test]
+    fn test_outer_order_by_limit_offset() {
+        let query = from("posts")
+            .select(("id", "title"))
+            .where_(("published", true))
+            .union(
+                from("drafts")
+                    .select(("id", "title"))
+                    .where_(("author_id", 3)),
+            )
+            .order_by_desc("id")
+            .order_by_raw("LOWER(title)")
+            .limit(5)
+            .offset(10);
+
+        let (sql, params) = query.to_sql_with_params(&MySqlDialect).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT `id`, `title` FROM `posts` WHERE `published` = ? UNION SELECT `id`, `title` FROM `drafts` WHERE `author_id` = ? ORDER BY `id` DESC, LOWER(title) LIMIT 5 OFFSET 10"
+        );
+        assert_eq!(params, vec![Value::Bool(true), Value::I32(3)]);
+    }
+
+    #[test]
+    fn test_operands_with_their_own_limit_are_wrapped() {
+        let top = from("scores")
+            .select("player")
+            .order_by_desc("points")
+            .limit(3);
+        let query = from("admins").select("player").union(top);
+
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"SELECT "player" FROM "admins" UNION (SELECT "player" FROM "scores" ORDER BY "points" DESC LIMIT 3)"#
+        );
+        assert_eq!(
+            query.to_sql_with(&SqliteDialect).unwrap(),
+            r#"SELECT "player" FROM "admins" UNION SELECT * FROM (SELECT "player" FROM "scores" ORDER BY "points" DESC LIMIT 3)"#
+        );
+    }
+
+    #[test]
+    fn test_chains_apply_left_to_right() {
+        let query = from("a")
+            .select("id")
+            .union(from("b").select("id"))
+            .intersect(from("c").select("id"))
+            .except(from("d").select("id").intersect(from("e").select("id")));
+
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "(SELECT id FROM a UNION SELECT id FROM b) INTERSECT SELECT id FROM c EXCEPT (SELECT id FROM d INTERSECT SELECT id FROM e)"
+        );
+        // SQLite gives all set operators the same precedence
+        assert_eq!(
+            query.to_sql_with(&SqliteDialect).unwrap(),
+            r#"SELECT "id" FROM "a" UNION SELECT "id" FROM "b" INTERSECT SELECT "id" FROM "c" EXCEPT SELECT * FROM (SELECT "id" FROM "d" INTERSECT SELECT "id" FROM "e")"#
+        );
+    }
+
+    #[test]
+    fn test_compound_as_subquery() {
+        let ids = from("orders")
+            .select("user_id")
+            .union(from("carts").select("user_id"));
+        let query = from("users")
+            .select("id")
+            .where_(("active", true))
+            .where_in("id", ids);
+
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"SELECT "id" FROM "users" WHERE "active" = $1 AND "id" IN (SELECT "user_id" FROM "orders" UNION SELECT "user_id" FROM "carts")"#
+        );
+    }
+}
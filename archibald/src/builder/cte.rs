@@ -0,0 +1,207 @@
+//! Common table expressions (WITH and WITH RECURSIVE)
+
+use super::common::{IntoColumns, RenderContext};
+use super::select::SelectQuery;
+use crate::{Error, Result};
+
+/// A common table expression: `name [(columns)] AS (query)`
+#[derive(Debug, Clone)]
+pub struct Cte {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub query: SelectQuery,
+    pub recursive: bool,
+}
+
+impl Cte {
+    /// Create a plain CTE
+    pub fn new<Q>(name: &str, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        Self {
+            name: name.to_string(),
+            columns: Vec::new(),
+            query: query.into(),
+            recursive: false,
+        }
+    }
+
+    /// Create a recursive CTE with an explicit column list
+    pub fn recursive<C, Q>(name: &str, columns: C, query: Q) -> Self
+    where
+        C: IntoColumns,
+        Q: Into<SelectQuery>,
+    {
+        Self {
+            name: name.to_string(),
+            columns: columns.into_columns(),
+            query: query.into(),
+            recursive: true,
+        }
+    }
+}
+
+/// Render the WITH clause for a list of CTEs, including a trailing space
+///
+/// Renders nothing when there are no CTEs. The clause is `WITH RECURSIVE` as
+/// soon as one CTE is recursive, since the keyword applies to the whole list.
+/// CTEs are rendered first, so their parameters come before the main query's.
+pub(crate) fn render_with(ctes: &[Cte], ctx: &mut RenderContext) -> Result<String> {
+    if ctes.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut parts = Vec::with_capacity(ctes.len());
+    for (i, cte) in ctes.iter().enumerate() {
+        if ctes[..i].iter().any(|other| other.name == cte.name) {
+            return Err(Error::invalid_query(format!(
+                "WITH clause defines `{}` more than once",
+                cte.name
+            )));
+        }
+
+        let mut part = ctx.ident(&cte.name);
+        if !cte.columns.is_empty() {
+            let columns: Vec<String> = cte.columns.iter().map(|c| ctx.ident(c)).collect();
+            part.push_str(&format!(" ({})", columns.join(", ")));
+        }
+        part.push_str(&format!(" AS ({})", cte.query.render(ctx)?));
+        parts.push(part);
+    }
+
+    let keyword = if ctes.iter().any(|cte| cte.recursive) {
+        "WITH RECURSIVE"
+    } else {
+        "WITH"
+    };
+    Ok(format!("{} {} ", keyword, parts.join(", ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dialect::PostgresDialect;
+    use crate::{QueryBuilder, Value, delete, from, insert, op, raw, update};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_with_select() {
+        let query = from("recent")
+            .with(
+                "recent",
+                from("orders")
+                    .select(("id", "total"))
+                    .where_(("total", op::GT, 100)),
+            )
+            .select("*")
+            .where_(("id", op::LT, 50));
+
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "WITH recent AS (SELECT id, total FROM orders WHERE total > ?) SELECT * FROM recent WHERE id < ?"
+        );
+        assert_eq!(
+            query.parameters().unwrap(),
+            vec![Value::I32(100), Value::I32(50)]
+        );
+    }
+
+    #[test]
+    fn test_cte_parameters_come_first() {
+        let query = from("big_spenders")
+            .select("*")
+            .where_(("region", "eu"))
+            .with(
+                "big_spenders",
+                from("customers")
+                    .select("id")
+                    .where_(("spent", op::GT, 1000)),
+            )
+            .with("regions", raw("SELECT ? AS region", ["eu"]));
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"WITH "big_spenders" AS (SELECT "id" FROM "customers" WHERE "spent" > $1), "regions" AS (SELECT $2 AS region) SELECT * FROM "big_spenders" WHERE "region" = $3"#
+        );
+        assert_eq!(
+            params,
+            vec![Value::I32(1000), Value::from("eu"), Value::from("eu")]
+        );
+    }
+
+    #[test]
+    fn test_with_recursive() {
+        let anchor = from("categories")
+            .select(("id", "parent_id"))
+            .where_(("id", 1));
+        let step = from("categories")
+            .inner_join("tree", "categories.parent_id", "tree.id")
+            .select(("categories.id", "categories.parent_id"));
+        let query = from("tree")
+            .with_recursive("tree", ("id", "parent_id"), anchor.union_all(step))
+            .select("id");
+
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"WITH RECURSIVE "tree" ("id", "parent_id") AS (SELECT "id", "parent_id" FROM "categories" WHERE "id" = $1 UNION ALL SELECT "categories"."id", "categories"."parent_id" FROM "categories" INNER JOIN "tree" ON "categories"."parent_id" = "tree"."id") SELECT "id" FROM "tree""#
+        );
+    }
+
+    #[test]
+    fn test_duplicate_cte_names_are_rejected() {
+        let query = from("a")
+            .with("a", from("x").select("*"))
+            .with("a", from("y").select("*"))
+            .select("*");
+
+        assert!(query.to_sql().is_err());
+    }
+
+    #[test]
+    fn test_with_on_modifications() {
+        let stale = from("sessions").select("user_id").where_(("expired", true));
+
+        let deleted = delete("users")
+            .with("stale", stale.clone())
+            .where_raw("id IN (SELECT user_id FROM stale)");
+        assert_eq!(
+            deleted.to_sql().unwrap(),
+            "WITH stale AS (SELECT user_id FROM sessions WHERE expired = ?) DELETE FROM users WHERE id IN (SELECT user_id FROM stale)"
+        );
+
+        let mut updates = HashMap::new();
+        updates.insert("active".to_string(), Value::Bool(false));
+        let updated = update("users")
+            .with("stale", stale.clone())
+            .set(updates)
+            .where_raw(r#""id" IN (SELECT "user_id" FROM "stale")"#);
+        assert_eq!(
+            updated.to_sql_with(&PostgresDialect).unwrap(),
+            r#"WITH "stale" AS (SELECT "user_id" FROM "sessions" WHERE "expired" = $1) UPDATE "users" SET "active" = $2 WHERE "id" IN (SELECT "user_id" FROM "stale")"#
+        );
+        assert_eq!(
+            updated.parameters().unwrap(),
+            vec![Value::Bool(true), Value::Bool(false)]
+        );
+
+        let mut row = HashMap::new();
+        row.insert("note".to_string(), Value::from("cleanup"));
+        let inserted = insert("audit").with("stale", stale).values(row);
+        assert_eq!(
+            inserted.to_sql().unwrap(),
+            "WITH stale AS (SELECT user_id FROM sessions WHERE expired = ?) INSERT INTO audit (note) VALUES (?)"
+        );
+    }
+
+    #[test]
+    fn test_mysql_rejects_with_before_insert() {
+        use crate::dialect::MySqlDialect;
+
+        let mut row = HashMap::new();
+        row.insert("id".to_string(), Value::I32(1));
+        let query = insert("audit").values(row).with("x", from("y").select("*"));
+
+        assert!(query.to_sql_with(&MySqlDialect).is_err());
+    }
+}
@@ -1,29 +1,119 @@
 //! DELETE query builder module
 
-use super::common::{IntoCondition, QueryBuilder, WhereCondition, WhereConnector};
+use super::common::{
+    IntoColumns, IntoCondition, QueryBuilder, RenderContext, WhereCondition, WhereConnector,
+    WhereGroupBuilder, WhereNode, render_where_nodes,
+};
+use super::cte::{Cte, render_with};
+use super::raw::Raw;
+use super::returning::ReturningBuilder;
+use super::select::SelectQuery;
+use super::table_ref::{TableRef, render_table_list};
+use crate::dialect::{Dialect, MultiTableSyntax};
 use crate::{Error, Result, Value};
 
 /// DELETE query builder in initial state (before where_() is called)
 /// Can build conditions but cannot execute queries
 #[derive(Debug, Clone)]
 pub struct DeleteBuilderInitial {
+    ctes: Vec<Cte>,
     table_name: String,
+    using_tables: Vec<TableRef>,
 }
 
 /// DELETE query builder in complete state (after where_() is called)
 /// Can execute queries and add more WHERE conditions
 #[derive(Debug, Clone)]
 pub struct DeleteBuilderComplete {
+    ctes: Vec<Cte>,
     table_name: String,
-    where_conditions: Vec<WhereCondition>,
-    parameters: Vec<Value>,
+    using_tables: Vec<TableRef>,
+    where_conditions: Vec<WhereNode>,
 }
 
 impl DeleteBuilderInitial {
     /// Create a new DELETE query builder in initial state
     pub fn new(table: &str) -> Self {
         Self {
+            ctes: Vec::new(),
             table_name: table.to_string(),
+            using_tables: Vec::new(),
+        }
+    }
+
+    /// Add a common table expression to a WITH clause before the statement
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{delete, from, QueryBuilder};
+    ///
+    /// let query = delete("sessions")
+    ///     .with("banned", from("users").select("id").where_(("banned", true)))
+    ///     .where_raw("user_id IN (SELECT id FROM banned)");
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "WITH banned AS (SELECT id FROM users WHERE banned = ?) DELETE FROM sessions WHERE user_id IN (SELECT id FROM banned)"
+    /// );
+    /// ```
+    pub fn with<Q>(mut self, name: &str, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::new(name, query));
+        self
+    }
+
+    /// Add a recursive common table expression, rendering `WITH RECURSIVE`
+    pub fn with_recursive<C, Q>(mut self, name: &str, columns: C, query: Q) -> Self
+    where
+        C: IntoColumns,
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::recursive(name, columns, query));
+        self
+    }
+
+    /// Read another table in the statement, to decide which rows to delete
+    ///
+    /// Renders `DELETE ... USING` on PostgreSQL and a multi-table
+    /// `DELETE ... INNER JOIN` on MySQL. SQLite has no equivalent, so
+    /// rendering for it fails; filter with a subquery there instead. Relate
+    /// the tables with [`where_col`](Self::where_col).
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{delete, QueryBuilder};
+    /// use archibald::dialect::{MySqlDialect, PostgresDialect};
+    ///
+    /// let query = delete("sessions")
+    ///     .using("users")
+    ///     .where_col("sessions.user_id", "users.id")
+    ///     .and_where(("users.banned", true));
+    /// assert_eq!(
+    ///     query.to_sql_with(&PostgresDialect).unwrap(),
+    ///     r#"DELETE FROM "sessions" USING "users" WHERE "sessions"."user_id" = "users"."id" AND "users"."banned" = $1"#
+    /// );
+    /// assert_eq!(
+    ///     query.to_sql_with(&MySqlDialect).unwrap(),
+    ///     "DELETE `sessions` FROM `sessions` INNER JOIN `users` WHERE `sessions`.`user_id` = `users`.`id` AND `users`.`banned` = ?"
+    /// );
+    /// ```
+    pub fn using<T>(mut self, table: T) -> Self
+    where
+        T: Into<TableRef>,
+    {
+        self.using_tables.push(table.into());
+        self
+    }
+
+    /// Add a WHERE condition comparing two columns for equality, transitioning
+    /// to DeleteBuilderComplete
+    pub fn where_col(self, left: &str, right: &str) -> DeleteBuilderComplete {
+        DeleteBuilderComplete {
+            ctes: self.ctes,
+            table_name: self.table_name,
+            using_tables: self.using_tables,
+            where_conditions: vec![WhereNode::column_equals(left, right, WhereConnector::And)],
         }
     }
 
@@ -35,26 +125,64 @@ impl DeleteBuilderInitial {
     {
         let (column, operator, value) = condition.into_condition();
 
-        let mut where_conditions = Vec::new();
-        let mut parameters = Vec::new();
-
-        where_conditions.push(WhereCondition {
-            column,
-            operator,
-            value: value.clone(),
-            connector: WhereConnector::And,
-        });
-        parameters.push(value);
+        DeleteBuilderComplete {
+            ctes: self.ctes,
+            table_name: self.table_name,
+            using_tables: self.using_tables,
+            where_conditions: vec![WhereNode::Condition(WhereCondition {
+                column,
+                operator,
+                value,
+                connector: WhereConnector::And,
+            })],
+        }
+    }
 
+    /// Add a raw SQL WHERE condition - transitions to DeleteBuilderComplete
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{delete, raw, QueryBuilder};
+    ///
+    /// let query = delete("sessions").where_raw(raw("expires_at < ?", ["2024-01-01"]));
+    /// assert_eq!(query.to_sql().unwrap(), "DELETE FROM sessions WHERE expires_at < ?");
+    /// ```
+    pub fn where_raw<R>(self, raw: R) -> DeleteBuilderComplete
+    where
+        R: Into<Raw>,
+    {
         DeleteBuilderComplete {
+            ctes: self.ctes,
             table_name: self.table_name,
-            where_conditions,
-            parameters,
+            using_tables: self.using_tables,
+            where_conditions: vec![WhereNode::Raw {
+                raw: raw.into(),
+                connector: WhereConnector::And,
+            }],
         }
     }
 }
 
 impl DeleteBuilderComplete {
+    /// Add a common table expression to a WITH clause before the statement
+    pub fn with<Q>(mut self, name: &str, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::new(name, query));
+        self
+    }
+
+    /// Add a recursive common table expression, rendering `WITH RECURSIVE`
+    pub fn with_recursive<C, Q>(mut self, name: &str, columns: C, query: Q) -> Self
+    where
+        C: IntoColumns,
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::recursive(name, columns, query));
+        self
+    }
+
     /// Add a WHERE condition
     pub fn where_<C>(mut self, condition: C) -> Self
     where
@@ -62,13 +190,12 @@ impl DeleteBuilderComplete {
     {
         let (column, operator, value) = condition.into_condition();
 
-        self.where_conditions.push(WhereCondition {
+        self.where_conditions.push(WhereNode::Condition(WhereCondition {
             column,
             operator,
-            value: value.clone(),
+            value,
             connector: WhereConnector::And,
-        });
-        self.parameters.push(value);
+        }));
 
         self
     }
@@ -80,13 +207,12 @@ impl DeleteBuilderComplete {
     {
         let (column, operator, value) = condition.into_condition();
 
-        self.where_conditions.push(WhereCondition {
+        self.where_conditions.push(WhereNode::Condition(WhereCondition {
             column,
             operator,
-            value: value.clone(),
+            value,
             connector: WhereConnector::Or,
-        });
-        self.parameters.push(value);
+        }));
 
         self
     }
@@ -98,61 +224,157 @@ impl DeleteBuilderComplete {
     {
         self.where_(condition)
     }
+
+    /// Read another table in the statement; see [`DeleteBuilderInitial::using`]
+    pub fn using<T>(mut self, table: T) -> Self
+    where
+        T: Into<TableRef>,
+    {
+        self.using_tables.push(table.into());
+        self
+    }
+
+    /// Add a WHERE condition comparing two columns for equality
+    pub fn where_col(mut self, left: &str, right: &str) -> Self {
+        self.where_conditions.push(WhereNode::column_equals(left, right, WhereConnector::And));
+        self
+    }
+
+    /// Add a WHERE condition comparing two columns for equality, joined with OR
+    pub fn or_where_col(mut self, left: &str, right: &str) -> Self {
+        self.where_conditions.push(WhereNode::column_equals(left, right, WhereConnector::Or));
+        self
+    }
+
+    /// Add a parenthesized group of WHERE conditions joined with AND
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{delete, op};
+    ///
+    /// // DELETE FROM sessions WHERE user_id = ? AND (expired = ? OR last_seen < ?)
+    /// let query = delete("sessions")
+    ///     .where_(("user_id", 42))
+    ///     .where_group(|q| q.where_(("expired", true)).or_where(("last_seen", op::LT, "2024-01-01")));
+    /// ```
+    pub fn where_group<F>(self, build: F) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        self.push_where_group(build, WhereConnector::And)
+    }
+
+    /// Add a parenthesized group of WHERE conditions joined with OR
+    pub fn or_where_group<F>(self, build: F) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        self.push_where_group(build, WhereConnector::Or)
+    }
+
+    fn push_where_group<F>(mut self, build: F, connector: WhereConnector) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        let node = build(WhereGroupBuilder::new()).into_node(connector);
+        self.where_conditions.push(node);
+        self
+    }
+
+    /// Add a raw SQL WHERE condition
+    pub fn where_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.where_conditions.push(WhereNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::And,
+        });
+        self
+    }
+
+    /// Add a raw SQL WHERE condition joined with OR
+    pub fn or_where_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.where_conditions.push(WhereNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::Or,
+        });
+        self
+    }
+
+    /// Return columns of the deleted rows, turning this into a query that can be fetched
+    ///
+    /// Supported on PostgreSQL and SQLite 3.35+; rendering for a dialect without
+    /// RETURNING (such as MySQL) fails with [`Error::InvalidQuery`](crate::Error::InvalidQuery).
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::delete;
+    ///
+    /// let query = delete("sessions").where_(("expired", true)).returning("user_id");
+    /// ```
+    pub fn returning<C>(self, columns: C) -> ReturningBuilder<Self>
+    where
+        C: IntoColumns,
+    {
+        ReturningBuilder::new(self, columns)
+    }
 }
 
 impl QueryBuilder for DeleteBuilderInitial {
-    fn to_sql(&self) -> Result<String> {
+    fn to_sql_with_params(&self, _dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
         Err(Error::invalid_query(
             "DELETE requires WHERE condition for safety",
         ))
     }
 
-    fn parameters(&self) -> &[Value] {
-        &[]
-    }
-
     fn clone_builder(&self) -> Self {
         self.clone()
     }
 }
 
 impl QueryBuilder for DeleteBuilderComplete {
-    fn to_sql(&self) -> Result<String> {
-        // Validate all operators before generating SQL
-        for condition in &self.where_conditions {
-            condition.operator.validate()?;
+    fn to_sql_with_params(&self, dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
+        let mut ctx = RenderContext::new(dialect);
+
+        // WITH clause
+        let mut sql = render_with(&self.ctes, &mut ctx)?;
+
+        // DELETE FROM clause, with the other tables
+        let table = ctx.ident(&self.table_name);
+        if self.using_tables.is_empty() {
+            sql.push_str("DELETE FROM ");
+            sql.push_str(&table);
+        } else if !dialect.supports_delete_using() {
+            return Err(Error::invalid_query(format!(
+                "the {} dialect does not support DELETE with other tables",
+                dialect.name()
+            )));
+        } else {
+            match dialect.multi_table_syntax() {
+                MultiTableSyntax::FromUsing => {
+                    sql.push_str(&format!("DELETE FROM {} USING ", table));
+                    sql.push_str(&render_table_list(&self.using_tables, ", ", &mut ctx)?);
+                }
+                MultiTableSyntax::Joins => {
+                    sql.push_str(&format!("DELETE {} FROM {} INNER JOIN ", table, table));
+                    let tables = render_table_list(&self.using_tables, " INNER JOIN ", &mut ctx)?;
+                    sql.push_str(&tables);
+                }
+            }
         }
 
-        let mut sql = String::new();
-
-        // DELETE FROM clause
-        sql.push_str("DELETE FROM ");
-        sql.push_str(&self.table_name);
-
         // WHERE clause
-        if !self.where_conditions.is_empty() {
+        let where_sql = render_where_nodes(&self.where_conditions, &mut ctx)?;
+        if !where_sql.is_empty() {
             sql.push_str(" WHERE ");
-
-            for (i, condition) in self.where_conditions.iter().enumerate() {
-                if i > 0 {
-                    match condition.connector {
-                        WhereConnector::And => sql.push_str(" AND "),
-                        WhereConnector::Or => sql.push_str(" OR "),
-                    }
-                }
-
-                sql.push_str(&condition.column);
-                sql.push(' ');
-                sql.push_str(condition.operator.as_str());
-                sql.push_str(" ?");
-            }
+            sql.push_str(&where_sql);
         }
 
-        Ok(sql)
-    }
-
-    fn parameters(&self) -> &[Value] {
-        &self.parameters
+        Ok((sql, ctx.into_params()))
     }
 
     fn clone_builder(&self) -> Self {
@@ -200,4 +422,60 @@ mod tests {
             .to_string()
             .contains("DELETE requires WHERE condition for safety"));
     }
+
+    #[test]
+    fn test_delete_postgres_dialect() {
+        use crate::dialect::PostgresDialect;
+
+        let query = delete("users")
+            .where_(("age", op::LT, 18))
+            .or_where(("status", "inactive"));
+        let sql = query.to_sql_with(&PostgresDialect).unwrap();
+        assert_eq!(sql, r#"DELETE FROM "users" WHERE "age" < $1 OR "status" = $2"#);
+    }
+
+    #[test]
+    fn test_delete_or_where_group() {
+        let query = delete("sessions")
+            .where_(("expired", true))
+            .or_where_group(|q| q.where_(("user_id", 42)).where_(("revoked", true)));
+        let sql = query.to_sql().unwrap();
+        assert_eq!(
+            sql,
+            "DELETE FROM sessions WHERE expired = ? OR (user_id = ? AND revoked = ?)"
+        );
+        assert_eq!(query.parameters().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_delete_using_per_dialect() {
+        use crate::dialect::{MySqlDialect, PostgresDialect, SqliteDialect};
+        use crate::table;
+
+        let query = delete("sessions")
+            .where_(("sessions.expired", true))
+            .using(table("users").as_("u"))
+            .where_col("sessions.user_id", "u.id")
+            .and_where(("u.banned", true));
+
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"DELETE FROM "sessions" USING "users" AS "u" WHERE "sessions"."expired" = $1 AND "sessions"."user_id" = "u"."id" AND "u"."banned" = $2"#
+        );
+        assert_eq!(
+            query.to_sql_with(&MySqlDialect).unwrap(),
+            "DELETE `sessions` FROM `sessions` INNER JOIN `users` AS `u` WHERE `sessions`.`expired` = ? AND `sessions`.`user_id` = `u`.`id` AND `u`.`banned` = ?"
+        );
+        assert!(matches!(
+            query.to_sql_with(&SqliteDialect),
+            Err(Error::InvalidQuery { .. })
+        ));
+
+        // Without other tables SQLite is unaffected
+        let plain = delete("sessions").where_col("created_at", "expires_at");
+        assert_eq!(
+            plain.to_sql_with(&SqliteDialect).unwrap(),
+            r#"DELETE FROM "sessions" WHERE "created_at" = "expires_at""#
+        );
+    }
 }
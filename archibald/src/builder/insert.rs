@@ -1,33 +1,116 @@
 //! INSERT query builder implementations
 
-use super::common::QueryBuilder;
+use super::common::{
+    IntoColumns, IntoCondition, QueryBuilder, RenderContext, WhereCondition, WhereConnector,
+    WhereNode, render_where_nodes,
+};
+use super::cte::{Cte, render_with};
+use super::returning::ReturningBuilder;
+use super::select::{ColumnSelector, SelectBuilderComplete, SelectQuery};
+use super::update::IntoUpdateData;
+use crate::dialect::{Dialect, UpsertSyntax};
 use crate::{Error, Result, Value};
 
 /// INSERT query builder in initial state (before values() is called)
 /// Can build conditions but cannot execute queries
 #[derive(Debug, Clone)]
 pub struct InsertBuilderInitial {
+    ctes: Vec<Cte>,
     table_name: String,
 }
 
-/// INSERT query builder in complete state (after values() is called)
+/// INSERT query builder with a column list (after columns() is called)
+/// Waiting for the SELECT that produces the rows
+#[derive(Debug, Clone)]
+pub struct InsertBuilderWithColumns {
+    ctes: Vec<Cte>,
+    table_name: String,
+    columns: Vec<String>,
+}
+
+/// INSERT query builder in complete state (after values() or from_select() is called)
 /// Can execute queries but cannot call values() again
 #[derive(Debug, Clone)]
 pub struct InsertBuilderComplete {
+    ctes: Vec<Cte>,
     table_name: String,
     columns: Vec<String>,
-    values: Vec<Vec<Value>>,
-    parameters: Vec<Value>,
+    source: InsertSource,
+    on_conflict: Option<OnConflict>,
+    chunk_size: Option<usize>,
+}
+
+/// Where the inserted rows come from
+#[derive(Debug, Clone)]
+enum InsertSource {
+    /// Literal rows, each bound as parameters, with the columns they were
+    /// given for (lined up with the first row's when rendering)
+    Values(Vec<(Vec<String>, Vec<Value>)>),
+    /// The rows returned by a query (`INSERT ... SELECT`)
+    Select(SelectQuery),
+}
+
+/// Conflict handling for an INSERT (an upsert)
+#[derive(Debug, Clone)]
+struct OnConflict {
+    target: Vec<String>,
+    target_where: Vec<WhereNode>,
+    action: ConflictAction,
+}
+
+/// What to do with a row that conflicts with an existing one
+#[derive(Debug, Clone)]
+enum ConflictAction {
+    /// Keep the existing row
+    DoNothing,
+    /// Overwrite these columns with the values that were being inserted
+    Merge(Vec<String>),
+    /// Update the existing row with explicit values
+    MergeWith(Vec<(String, Value)>),
+}
+
+/// INSERT query builder choosing how to resolve a conflict
+///
+/// Created with [`InsertBuilderComplete::on_conflict`]; finish it with
+/// [`do_nothing`](Self::do_nothing), [`merge`](Self::merge) or
+/// [`merge_with`](Self::merge_with).
+#[derive(Debug, Clone)]
+pub struct OnConflictBuilder {
+    insert: InsertBuilderComplete,
+    target: Vec<String>,
+    target_where: Vec<WhereNode>,
 }
 
 impl InsertBuilderInitial {
     /// Create a new INSERT query builder in initial state
     pub fn new(table: &str) -> Self {
         Self {
+            ctes: Vec::new(),
             table_name: table.to_string(),
         }
     }
 
+    /// Add a common table expression to a WITH clause before the statement
+    ///
+    /// Not supported on MySQL, which only accepts WITH inside `INSERT ... SELECT`.
+    pub fn with<Q>(mut self, name: &str, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::new(name, query));
+        self
+    }
+
+    /// Add a recursive common table expression, rendering `WITH RECURSIVE`
+    pub fn with_recursive<C, Q>(mut self, name: &str, columns: C, query: Q) -> Self
+    where
+        C: IntoColumns,
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::recursive(name, columns, query));
+        self
+    }
+
     /// Add values for a single record, transitioning to InsertBuilderComplete
     ///
     /// # Examples
@@ -47,49 +130,135 @@ impl InsertBuilderInitial {
     {
         let (columns, values) = data.into_insert_data();
         InsertBuilderComplete {
+            ctes: self.ctes,
             table_name: self.table_name,
-            columns,
-            values: vec![values],
-            parameters: Vec::new(),
+            columns: columns.clone(),
+            source: InsertSource::Values(vec![(columns, values)]),
+            on_conflict: None,
+            chunk_size: None,
         }
     }
 
     /// Add values for multiple records, transitioning to InsertBuilderComplete
+    ///
+    /// Every record must have the same set of columns as the first; values
+    /// are matched to columns by name, so maps with differing key orders are
+    /// fine. A record with other columns fails when the query is rendered.
+    ///
+    /// Large batches are split into several statements to stay under the
+    /// database's parameter limit, see [`chunked`](InsertBuilderComplete::chunked).
+    /// A split insert can still be executed, but anything that needs a single
+    /// statement fails once the row count crosses that limit.
     pub fn values_many<T>(self, data: Vec<T>) -> InsertBuilderComplete
     where
-        T: IntoInsertData + Clone,
+        T: IntoInsertData,
     {
-        let mut columns = Vec::new();
-        let mut values_vec = Vec::new();
+        let rows: Vec<(Vec<String>, Vec<Value>)> =
+            data.into_iter().map(IntoInsertData::into_insert_data).collect();
+        let columns = rows.first().map(|(columns, _)| columns.clone()).unwrap_or_default();
 
-        if let Some(first) = data.first() {
-            let (cols, _) = first.clone().into_insert_data();
-            columns = cols;
+        InsertBuilderComplete {
+            ctes: self.ctes,
+            table_name: self.table_name,
+            columns,
+            source: InsertSource::Values(rows),
+            on_conflict: None,
+            chunk_size: None,
+        }
+    }
 
-            for item in data {
-                let (_, vals) = item.into_insert_data();
-                values_vec.push(vals);
-            }
+    /// Name the columns to insert into, for rows produced by a query
+    ///
+    /// Follow with [`from_select`](InsertBuilderWithColumns::from_select).
+    pub fn columns<C>(self, columns: C) -> InsertBuilderWithColumns
+    where
+        C: IntoColumns,
+    {
+        InsertBuilderWithColumns {
+            ctes: self.ctes,
+            table_name: self.table_name,
+            columns: columns.into_columns(),
         }
+    }
+}
 
+impl InsertBuilderWithColumns {
+    /// Add a common table expression to a WITH clause before the statement
+    ///
+    /// On MySQL the WITH clause is moved in front of the SELECT, where MySQL
+    /// accepts it.
+    pub fn with<Q>(mut self, name: &str, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::new(name, query));
+        self
+    }
+
+    /// Add a recursive common table expression, rendering `WITH RECURSIVE`
+    pub fn with_recursive<C, Q>(mut self, name: &str, columns: C, query: Q) -> Self
+    where
+        C: IntoColumns,
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::recursive(name, columns, query));
+        self
+    }
+
+    /// Insert the rows returned by a query, transitioning to InsertBuilderComplete
+    ///
+    /// The query's parameters are bound in place, and the result can still
+    /// take an upsert or RETURNING clause.
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, insert, op, QueryBuilder, Value};
+    /// use archibald::dialect::PostgresDialect;
+    ///
+    /// let query = insert("archive")
+    ///     .columns(("id", "name"))
+    ///     .from_select(from("users").select(("id", "name")).where_(("deleted", true)))
+    ///     .on_conflict("id")
+    ///     .do_nothing()
+    ///     .returning("id");
+    /// assert_eq!(
+    ///     query.to_sql_with(&PostgresDialect).unwrap(),
+    ///     r#"INSERT INTO "archive" ("id", "name") SELECT "id", "name" FROM "users" WHERE "deleted" = $1 ON CONFLICT ("id") DO NOTHING RETURNING "id""#
+    /// );
+    /// assert_eq!(query.parameters().unwrap(), vec![Value::Bool(true)]);
+    /// ```
+    pub fn from_select<Q>(self, query: Q) -> InsertBuilderComplete
+    where
+        Q: Into<SelectQuery>,
+    {
         InsertBuilderComplete {
+            ctes: self.ctes,
             table_name: self.table_name,
-            columns,
-            values: values_vec,
-            parameters: Vec::new(),
+            columns: self.columns,
+            source: InsertSource::Select(query.into()),
+            on_conflict: None,
+            chunk_size: None,
         }
     }
 }
 
-impl QueryBuilder for InsertBuilderInitial {
-    fn to_sql(&self) -> Result<String> {
+impl QueryBuilder for InsertBuilderWithColumns {
+    fn to_sql_with_params(&self, _dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
         Err(Error::invalid_query(
-            "INSERT requires values to be specified with .values()",
+            "INSERT with columns requires a query to be specified with .from_select()",
         ))
     }
 
-    fn parameters(&self) -> &[Value] {
-        &[]
+    fn clone_builder(&self) -> Self {
+        self.clone()
+    }
+}
+
+impl QueryBuilder for InsertBuilderInitial {
+    fn to_sql_with_params(&self, _dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
+        Err(Error::invalid_query(
+            "INSERT requires values to be specified with .values()",
+        ))
     }
 
     fn clone_builder(&self) -> Self {
@@ -97,42 +266,522 @@ impl QueryBuilder for InsertBuilderInitial {
     }
 }
 
-impl QueryBuilder for InsertBuilderComplete {
-    fn to_sql(&self) -> Result<String> {
-        if self.columns.is_empty() || self.values.is_empty() {
+impl InsertBuilderComplete {
+    /// Add a common table expression to a WITH clause before the statement
+    pub fn with<Q>(mut self, name: &str, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::new(name, query));
+        self
+    }
+
+    /// Add a recursive common table expression, rendering `WITH RECURSIVE`
+    pub fn with_recursive<C, Q>(mut self, name: &str, columns: C, query: Q) -> Self
+    where
+        C: IntoColumns,
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::recursive(name, columns, query));
+        self
+    }
+
+    /// Handle rows that conflict with an existing unique key
+    ///
+    /// The columns name the conflict target (the unique index). MySQL always
+    /// uses the table's unique keys and ignores them.
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{insert, QueryBuilder};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut data = HashMap::new();
+    /// data.insert("email".to_string(), "john@example.com".into());
+    ///
+    /// let query = insert("users").values(data).on_conflict("email").do_nothing();
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "INSERT INTO users (email) VALUES (?) ON CONFLICT (email) DO NOTHING"
+    /// );
+    /// ```
+    pub fn on_conflict<C>(self, columns: C) -> OnConflictBuilder
+    where
+        C: IntoColumns,
+    {
+        OnConflictBuilder {
+            insert: self,
+            target: columns.into_columns(),
+            target_where: Vec::new(),
+        }
+    }
+
+    /// Return columns of the inserted rows, turning this into a query that can be fetched
+    ///
+    /// Supported on PostgreSQL and SQLite 3.35+; rendering for a dialect without
+    /// RETURNING (such as MySQL) fails with [`Error::InvalidQuery`](crate::Error::InvalidQuery).
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{insert, QueryBuilder};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut data = HashMap::new();
+    /// data.insert("name".to_string(), "John".into());
+    ///
+    /// let query = insert("users").values(data).returning("id");
+    /// assert_eq!(query.to_sql().unwrap(), "INSERT INTO users (name) VALUES (?) RETURNING id");
+    /// ```
+    pub fn returning<C>(self, columns: C) -> ReturningBuilder<Self>
+    where
+        C: IntoColumns,
+    {
+        ReturningBuilder::new(self, columns)
+    }
+
+    /// Insert at most this many rows per statement
+    ///
+    /// Executing the query runs every statement through
+    /// [`ConnectionPool::execute_all`](crate::ConnectionPool::execute_all),
+    /// which the built-in pools wrap in one transaction, and returns the total
+    /// number of affected rows. Without this, rows are only split when a
+    /// single statement would bind more parameters than the dialect allows
+    /// ([`Dialect::max_parameters`]).
+    ///
+    /// Once the rows are split, `to_sql`, `to_sql_with_params`, `parameters`
+    /// and `.returning(..)` fail with [`Error::InvalidQuery`], since they can
+    /// only describe one statement. Render the statements with
+    /// [`to_sql_chunks`](Self::to_sql_chunks) instead, and insert batches
+    /// whose rows are needed back in slices that fit in one statement.
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::insert;
+    /// use archibald::dialect::PostgresDialect;
+    /// use std::collections::HashMap;
+    ///
+    /// let rows: Vec<HashMap<String, archibald::Value>> = (1..=5)
+    ///     .map(|id| HashMap::from([("id".to_string(), id.into())]))
+    ///     .collect();
+    ///
+    /// let statements = insert("items").values_many(rows).chunked(2);
+    /// let statements = statements.to_sql_chunks(&PostgresDialect).unwrap();
+    /// assert_eq!(statements.len(), 3);
+    /// assert_eq!(statements[2].0, r#"INSERT INTO "items" ("id") VALUES ($1)"#);
+    /// ```
+    pub fn chunked(mut self, rows_per_statement: usize) -> Self {
+        self.chunk_size = Some(rows_per_statement);
+        self
+    }
+
+    /// Render the statements the rows are split into, each with its parameters
+    ///
+    /// Returns a single statement unless the rows are [`chunked`](Self::chunked)
+    /// or exceed the dialect's parameter limit. Each statement repeats the WITH
+    /// and conflict handling clauses.
+    pub fn to_sql_chunks(&self, dialect: &dyn Dialect) -> Result<Vec<(String, Vec<Value>)>> {
+        let rows = self.aligned_rows()?;
+        if rows.is_empty() {
+            return Ok(vec![self.render_statement(&rows, dialect)?]);
+        }
+
+        let mut rows_per_statement = match self.chunk_size {
+            Some(0) => {
+                return Err(Error::invalid_query(
+                    "INSERT chunk size must be at least one row",
+                ));
+            }
+            Some(size) => size,
+            None => rows.len(),
+        };
+
+        if let Some(max_parameters) = dialect.max_parameters() {
+            // Parameters bound once per statement (CTEs, conflict handling)
+            // plus one per column for every row
+            let (_, params) = self.render_statement(&rows[..1], dialect)?;
+            let per_statement = params.len() - self.columns.len();
+            let fitting = max_parameters.saturating_sub(per_statement) / self.columns.len();
+            if fitting == 0 {
+                return Err(Error::invalid_query(format!(
+                    "a row of {} column(s) does not fit in the {} parameters {} allows",
+                    self.columns.len(),
+                    max_parameters,
+                    dialect.name()
+                )));
+            }
+            rows_per_statement = rows_per_statement.min(fitting);
+        }
+
+        rows.chunks(rows_per_statement)
+            .map(|chunk| self.render_statement(chunk, dialect))
+            .collect()
+    }
+
+    /// The literal rows with their values in the order of `self.columns`
+    ///
+    /// Empty for `INSERT ... SELECT`.
+    fn aligned_rows(&self) -> Result<Vec<Vec<Value>>> {
+        let InsertSource::Values(rows) = &self.source else {
+            return Ok(Vec::new());
+        };
+
+        rows.iter()
+            .enumerate()
+            .map(|(index, (columns, values))| {
+                align_row(&self.columns, columns, values.clone()).ok_or_else(|| {
+                    Error::invalid_query(format!(
+                        "INSERT row {} has columns ({}), expected ({})",
+                        index + 1,
+                        columns.join(", "),
+                        self.columns.join(", ")
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+impl OnConflictBuilder {
+    /// Restrict the conflict target to a partial unique index
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{insert, op, QueryBuilder, Value};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut data = HashMap::new();
+    /// data.insert("email".to_string(), "john@example.com".into());
+    ///
+    /// let query = insert("users")
+    ///     .values(data)
+    ///     .on_conflict("email")
+    ///     .where_(("deleted_at", op::IS_NULL, Value::Null))
+    ///     .do_nothing();
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "INSERT INTO users (email) VALUES (?) ON CONFLICT (email) WHERE deleted_at IS NULL DO NOTHING"
+    /// );
+    /// ```
+    pub fn where_<C>(mut self, condition: C) -> Self
+    where
+        C: IntoCondition,
+    {
+        self.push_condition(condition, WhereConnector::And);
+        self
+    }
+
+    /// Add an AND condition to the conflict target (same as where_)
+    pub fn and_where<C>(self, condition: C) -> Self
+    where
+        C: IntoCondition,
+    {
+        self.where_(condition)
+    }
+
+    /// Add an OR condition to the conflict target
+    pub fn or_where<C>(mut self, condition: C) -> Self
+    where
+        C: IntoCondition,
+    {
+        self.push_condition(condition, WhereConnector::Or);
+        self
+    }
+
+    fn push_condition<C>(&mut self, condition: C, connector: WhereConnector)
+    where
+        C: IntoCondition,
+    {
+        let (column, operator, value) = condition.into_condition();
+        self.target_where.push(WhereNode::Condition(WhereCondition {
+            column,
+            operator,
+            value,
+            connector,
+        }));
+    }
+
+    /// Keep the existing row and skip the conflicting one
+    pub fn do_nothing(self) -> InsertBuilderComplete {
+        self.resolve(ConflictAction::DoNothing)
+    }
+
+    /// Overwrite the given columns with the values that were being inserted
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{insert, QueryBuilder};
+    /// use archibald::dialect::PostgresDialect;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut data = HashMap::new();
+    /// data.insert("email".to_string(), "john@example.com".into());
+    ///
+    /// let query = insert("users").values(data).on_conflict("email").merge("email");
+    /// assert_eq!(
+    ///     query.to_sql_with(&PostgresDialect).unwrap(),
+    ///     r#"INSERT INTO "users" ("email") VALUES ($1) ON CONFLICT ("email") DO UPDATE SET "email" = EXCLUDED."email""#
+    /// );
+    /// ```
+    pub fn merge<C>(self, columns: C) -> InsertBuilderComplete
+    where
+        C: IntoColumns,
+    {
+        self.resolve(ConflictAction::Merge(columns.into_columns()))
+    }
+
+    /// Update the existing row with explicit values
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::insert;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut data = HashMap::new();
+    /// data.insert("email".to_string(), "john@example.com".into());
+    ///
+    /// let mut updates = HashMap::new();
+    /// updates.insert("status".to_string(), "returning".into());
+    ///
+    /// let query = insert("users").values(data).on_conflict("email").merge_with(updates);
+    /// ```
+    pub fn merge_with<T>(self, data: T) -> InsertBuilderComplete
+    where
+        T: IntoUpdateData,
+    {
+        self.resolve(ConflictAction::MergeWith(data.into_update_data()))
+    }
+
+    fn resolve(self, action: ConflictAction) -> InsertBuilderComplete {
+        InsertBuilderComplete {
+            on_conflict: Some(OnConflict {
+                target: self.target,
+                target_where: self.target_where,
+                action,
+            }),
+            ..self.insert
+        }
+    }
+}
+
+impl OnConflict {
+    /// Render the conflict clause (with its leading space) for the dialect
+    fn render(&self, insert_columns: &[String], ctx: &mut RenderContext) -> Result<String> {
+        match ctx.dialect.upsert_syntax() {
+            UpsertSyntax::OnConflict => self.render_on_conflict(ctx),
+            UpsertSyntax::OnDuplicateKeyUpdate => {
+                self.render_on_duplicate_key(insert_columns, ctx)
+            }
+        }
+    }
+
+    fn render_on_conflict(&self, ctx: &mut RenderContext) -> Result<String> {
+        let mut sql = String::from(" ON CONFLICT");
+
+        if !self.target.is_empty() {
+            let target: Vec<String> = self.target.iter().map(|c| ctx.ident(c)).collect();
+            sql.push_str(&format!(" ({})", target.join(", ")));
+        } else if !matches!(self.action, ConflictAction::DoNothing) {
+            return Err(Error::invalid_query(
+                "ON CONFLICT DO UPDATE requires conflict target columns",
+            ));
+        }
+
+        let where_sql = render_where_nodes(&self.target_where, ctx)?;
+        if !where_sql.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&where_sql);
+        }
+
+        let assignments = match &self.action {
+            ConflictAction::DoNothing => {
+                sql.push_str(" DO NOTHING");
+                return Ok(sql);
+            }
+            ConflictAction::Merge(columns) => columns
+                .iter()
+                .map(|c| {
+                    let column = ctx.ident(c);
+                    format!("{} = EXCLUDED.{}", column, column)
+                })
+                .collect::<Vec<_>>(),
+            ConflictAction::MergeWith(data) => data
+                .iter()
+                .map(|(column, value)| format!("{} = {}", ctx.ident(column), ctx.bind(value)))
+                .collect(),
+        };
+
+        if assignments.is_empty() {
+            return Err(Error::invalid_query(
+                "ON CONFLICT DO UPDATE requires at least one column to update",
+            ));
+        }
+
+        sql.push_str(" DO UPDATE SET ");
+        sql.push_str(&assignments.join(", "));
+        Ok(sql)
+    }
+
+    fn render_on_duplicate_key(
+        &self,
+        insert_columns: &[String],
+        ctx: &mut RenderContext,
+    ) -> Result<String> {
+        if !self.target_where.is_empty() {
+            return Err(Error::invalid_query(format!(
+                "The {} dialect does not support WHERE on a conflict target",
+                ctx.dialect.name()
+            )));
+        }
+
+        let assignments: Vec<String> = match &self.action {
+            // A no-op assignment keeps the existing row without ignoring other errors
+            // the way INSERT IGNORE would
+            ConflictAction::DoNothing => self
+                .target
+                .first()
+                .or(insert_columns.first())
+                .map(|c| {
+                    let column = ctx.ident(c);
+                    format!("{} = {}", column, column)
+                })
+                .into_iter()
+                .collect(),
+            ConflictAction::Merge(columns) => columns
+                .iter()
+                .map(|c| {
+                    let column = ctx.ident(c);
+                    format!("{} = VALUES({})", column, column)
+                })
+                .collect(),
+            ConflictAction::MergeWith(data) => data
+                .iter()
+                .map(|(column, value)| format!("{} = {}", ctx.ident(column), ctx.bind(value)))
+                .collect(),
+        };
+
+        if assignments.is_empty() {
+            return Err(Error::invalid_query(
+                "ON DUPLICATE KEY UPDATE requires at least one column to update",
+            ));
+        }
+
+        Ok(format!(" ON DUPLICATE KEY UPDATE {}", assignments.join(", ")))
+    }
+}
+
+impl InsertSource {
+    fn is_empty(&self) -> bool {
+        match self {
+            InsertSource::Values(rows) => rows.is_empty(),
+            InsertSource::Select(_) => false,
+        }
+    }
+}
+
+impl InsertBuilderComplete {
+    /// Render one statement inserting the given (aligned) rows, or the rows
+    /// of the SELECT for `INSERT ... SELECT`
+    fn render_statement(
+        &self,
+        rows: &[Vec<Value>],
+        dialect: &dyn Dialect,
+    ) -> Result<(String, Vec<Value>)> {
+        let mut ctx = RenderContext::new(dialect);
+
+        if self.columns.is_empty() || self.source.is_empty() {
             return Err(crate::Error::invalid_query(
                 "INSERT requires columns and values",
             ));
         }
 
-        let mut sql = String::new();
+        // WITH clause. Dialects that don't accept it before INSERT (MySQL)
+        // accept it in front of the SELECT of an INSERT ... SELECT instead
+        let with_before_insert = dialect.supports_insert_with();
+        if !self.ctes.is_empty()
+            && !with_before_insert
+            && !matches!(self.source, InsertSource::Select(_))
+        {
+            return Err(Error::invalid_query(format!(
+                "the {} dialect does not support WITH before INSERT",
+                dialect.name()
+            )));
+        }
+        let mut sql = if with_before_insert {
+            render_with(&self.ctes, &mut ctx)?
+        } else {
+            String::new()
+        };
 
         // INSERT INTO clause
         sql.push_str("INSERT INTO ");
-        sql.push_str(&self.table_name);
+        sql.push_str(&ctx.ident(&self.table_name));
 
         // Columns
+        let column_parts: Vec<String> = self.columns.iter().map(|c| ctx.ident(c)).collect();
         sql.push_str(" (");
-        sql.push_str(&self.columns.join(", "));
-        sql.push_str(")");
-
-        // VALUES clause
-        sql.push_str(" VALUES ");
-        let value_groups: Vec<String> = self
-            .values
-            .iter()
-            .map(|row| {
-                let placeholders: Vec<String> = row.iter().map(|_| "?".to_string()).collect();
-                format!("({})", placeholders.join(", "))
-            })
-            .collect();
-        sql.push_str(&value_groups.join(", "));
+        sql.push_str(&column_parts.join(", "));
+        sql.push(')');
 
-        Ok(sql)
+        match &self.source {
+            // VALUES clause
+            InsertSource::Values(_) => {
+                sql.push_str(" VALUES ");
+                let value_groups: Vec<String> = rows
+                    .iter()
+                    .map(|row| {
+                        let placeholders: Vec<String> =
+                            row.iter().map(|value| ctx.bind(value)).collect();
+                        format!("({})", placeholders.join(", "))
+                    })
+                    .collect();
+                sql.push_str(&value_groups.join(", "));
+            }
+            // SELECT producing the rows
+            InsertSource::Select(query) => {
+                if let SelectQuery::Select(select) = query {
+                    check_selected_column_count(&self.columns, select)?;
+                }
+
+                sql.push(' ');
+                if !with_before_insert {
+                    sql.push_str(&render_with(&self.ctes, &mut ctx)?);
+                }
+                let select_sql = query.render(&mut ctx)?;
+                if self.on_conflict.is_some() && dialect.upsert_select_requires_where() {
+                    sql.push_str(&format!(
+                        "SELECT * FROM ({}) WHERE {}",
+                        select_sql,
+                        dialect.boolean_literal(true)
+                    ));
+                } else {
+                    sql.push_str(&select_sql);
+                }
+            }
+        }
+
+        // Conflict handling
+        if let Some(on_conflict) = &self.on_conflict {
+            sql.push_str(&on_conflict.render(&self.columns, &mut ctx)?);
+        }
+
+        Ok((sql, ctx.into_params()))
     }
+}
 
-    fn parameters(&self) -> &[Value] {
-        &self.parameters
+impl QueryBuilder for InsertBuilderComplete {
+    /// Render the INSERT as one statement
+    ///
+    /// Fails when the rows are split into several statements; use
+    /// [`to_sql_chunks`](InsertBuilderComplete::to_sql_chunks) for those.
+    fn to_sql_with_params(&self, dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
+        let mut statements = self.to_sql_chunks(dialect)?;
+        if statements.len() > 1 {
+            return Err(Error::invalid_query(format!(
+                "INSERT is split into {} statements; render it with to_sql_chunks()",
+                statements.len()
+            )));
+        }
+        Ok(statements.remove(0))
     }
 
     fn clone_builder(&self) -> Self {
@@ -140,6 +789,51 @@ impl QueryBuilder for InsertBuilderComplete {
     }
 }
 
+/// Reject an INSERT ... SELECT whose query selects a different number of
+/// columns than are inserted into
+///
+/// Only checked when every selected column is known; `*` and `table.*` are
+/// skipped.
+fn check_selected_column_count(columns: &[String], select: &SelectBuilderComplete) -> Result<()> {
+    // A wildcard or raw fragment can expand to any number of columns
+    let open_ended = select.selected_columns.iter().any(|selector| match selector {
+        ColumnSelector::Column { name, .. } => name.ends_with('*'),
+        ColumnSelector::Raw { .. } => true,
+        _ => false,
+    });
+    if open_ended || select.selected_columns.len() == columns.len() {
+        return Ok(());
+    }
+    Err(Error::invalid_query(format!(
+        "INSERT into {} column(s) from a SELECT of {} column(s)",
+        columns.len(),
+        select.selected_columns.len()
+    )))
+}
+
+/// Reorder a row's values to follow `columns`, matching them up by name
+///
+/// Returns `None` if the row doesn't have exactly those columns.
+pub(crate) fn align_row(
+    columns: &[String],
+    row_columns: &[String],
+    mut values: Vec<Value>,
+) -> Option<Vec<Value>> {
+    if row_columns == columns {
+        return Some(values);
+    }
+    if row_columns.len() != columns.len() {
+        return None;
+    }
+    columns
+        .iter()
+        .map(|column| {
+            let position = row_columns.iter().position(|c| c == column)?;
+            Some(std::mem::replace(&mut values[position], Value::Null))
+        })
+        .collect()
+}
+
 /// Trait for types that can be converted to INSERT data
 pub trait IntoInsertData {
     fn into_insert_data(self) -> (Vec<String>, Vec<Value>);
@@ -191,6 +885,18 @@ mod tests {
         assert!(sql.starts_with("INSERT INTO users"));
     }
 
+    #[test]
+    fn test_insert_parameters_follow_values() {
+        let mut data = HashMap::new();
+        data.insert("name".to_string(), "John".into());
+
+        let query = insert("users").values_many(vec![data.clone(), data]);
+        assert_eq!(
+            query.parameters().unwrap(),
+            vec![Value::from("John"), Value::from("John")]
+        );
+    }
+
     #[test]
     fn test_insert_empty_data_fails() {
         let query = insert("users");
@@ -201,4 +907,326 @@ mod tests {
             .to_string()
             .contains("INSERT requires values"));
     }
+
+    #[test]
+    fn test_insert_postgres_dialect() {
+        use crate::dialect::PostgresDialect;
+
+        let mut data1 = HashMap::new();
+        data1.insert("name".to_string(), "John".into());
+
+        let mut data2 = HashMap::new();
+        data2.insert("name".to_string(), "Jane".into());
+
+        let query = insert("users").values_many(vec![data1, data2]);
+        let sql = query.to_sql_with(&PostgresDialect).unwrap();
+        assert_eq!(sql, r#"INSERT INTO "users" ("name") VALUES ($1), ($2)"#);
+    }
+
+    fn email_data() -> HashMap<String, Value> {
+        let mut data = HashMap::new();
+        data.insert("email".to_string(), "john@example.com".into());
+        data
+    }
+
+    #[test]
+    fn test_on_conflict_merge_postgres() {
+        use crate::dialect::PostgresDialect;
+
+        let query = insert("users")
+            .values(email_data())
+            .on_conflict(("tenant_id", "email"))
+            .merge(vec!["email", "updated_at"]);
+
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"INSERT INTO "users" ("email") VALUES ($1) ON CONFLICT ("tenant_id", "email") DO UPDATE SET "email" = EXCLUDED."email", "updated_at" = EXCLUDED."updated_at""#
+        );
+    }
+
+    #[test]
+    fn test_on_conflict_merge_with_parameter_order() {
+        use crate::dialect::PostgresDialect;
+        use crate::op;
+
+        let mut updates = HashMap::new();
+        updates.insert("visits".to_string(), Value::I32(1));
+
+        let query = insert("users")
+            .values(email_data())
+            .on_conflict("email")
+            .where_(("active", true))
+            .and_where(("deleted_at", op::IS_NULL, Value::Null))
+            .merge_with(updates);
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"INSERT INTO "users" ("email") VALUES ($1) ON CONFLICT ("email") WHERE "active" = $2 AND "deleted_at" IS NULL DO UPDATE SET "visits" = $3"#
+        );
+        assert_eq!(
+            params,
+            vec![
+                Value::from("john@example.com"),
+                Value::Bool(true),
+                Value::I32(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_on_conflict_mysql() {
+        use crate::dialect::MySqlDialect;
+
+        let merge = insert("users")
+            .values(email_data())
+            .on_conflict("email")
+            .merge("email");
+        assert_eq!(
+            merge.to_sql_with(&MySqlDialect).unwrap(),
+            "INSERT INTO `users` (`email`) VALUES (?) ON DUPLICATE KEY UPDATE `email` = VALUES(`email`)"
+        );
+
+        let nothing = insert("users")
+            .values(email_data())
+            .on_conflict("email")
+            .do_nothing();
+        assert_eq!(
+            nothing.to_sql_with(&MySqlDialect).unwrap(),
+            "INSERT INTO `users` (`email`) VALUES (?) ON DUPLICATE KEY UPDATE `email` = `email`"
+        );
+
+        let filtered = insert("users")
+            .values(email_data())
+            .on_conflict("email")
+            .where_(("active", true))
+            .do_nothing();
+        assert!(filtered.to_sql_with(&MySqlDialect).is_err());
+    }
+
+    #[test]
+    fn test_on_conflict_update_requires_columns() {
+        let without_target = insert("users")
+            .values(email_data())
+            .on_conflict(Vec::<String>::new())
+            .merge("email");
+        assert!(without_target.to_sql().is_err());
+
+        let without_columns = insert("users")
+            .values(email_data())
+            .on_conflict("email")
+            .merge(Vec::<String>::new());
+        assert!(without_columns.to_sql().is_err());
+
+        let do_nothing = insert("users")
+            .values(email_data())
+            .on_conflict(Vec::<String>::new())
+            .do_nothing();
+        assert_eq!(
+            do_nothing.to_sql().unwrap(),
+            "INSERT INTO users (email) VALUES (?) ON CONFLICT DO NOTHING"
+        );
+    }
+
+    #[test]
+    fn test_insert_select_binds_query_parameters() {
+        use crate::dialect::PostgresDialect;
+        use crate::{from, op};
+
+        let mut updates = HashMap::new();
+        updates.insert("archived_at".to_string(), Value::from("2024-06-01"));
+        let query = insert("archive")
+            .with("stale", from("sessions").select("user_id").where_(("expired", true)))
+            .columns(("id", "name"))
+            .from_select(
+                from("users")
+                    .select(("id", "name"))
+                    .where_(("age", op::GT, 90))
+                    .where_raw("id IN (SELECT user_id FROM stale)"),
+            )
+            .on_conflict("id")
+            .merge_with(updates);
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"WITH "stale" AS (SELECT "user_id" FROM "sessions" WHERE "expired" = $1) INSERT INTO "archive" ("id", "name") SELECT "id", "name" FROM "users" WHERE "age" > $2 AND id IN (SELECT user_id FROM stale) ON CONFLICT ("id") DO UPDATE SET "archived_at" = $3"#
+        );
+        assert_eq!(
+            params,
+            vec![Value::Bool(true), Value::I32(90), Value::from("2024-06-01")]
+        );
+    }
+
+    #[test]
+    fn test_insert_select_per_dialect() {
+        use crate::dialect::{MySqlDialect, SqliteDialect};
+        use crate::from;
+
+        let query = insert("archive")
+            .with("old", from("users").select(("id", "name")).where_(("active", false)))
+            .columns(("id", "name"))
+            .from_select(from("old").select(("id", "name")))
+            .on_conflict("id")
+            .merge("name");
+
+        assert_eq!(
+            query.to_sql_with(&MySqlDialect).unwrap(),
+            "INSERT INTO `archive` (`id`, `name`) WITH `old` AS (SELECT `id`, `name` FROM `users` WHERE `active` = ?) SELECT `id`, `name` FROM `old` ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)"
+        );
+        assert_eq!(
+            query.to_sql_with(&SqliteDialect).unwrap(),
+            r#"WITH "old" AS (SELECT "id", "name" FROM "users" WHERE "active" = ?) INSERT INTO "archive" ("id", "name") SELECT * FROM (SELECT "id", "name" FROM "old") WHERE 1 ON CONFLICT ("id") DO UPDATE SET "name" = EXCLUDED."name""#
+        );
+    }
+
+    #[test]
+    fn test_insert_select_column_count_must_match() {
+        use crate::from;
+
+        let mismatch = insert("archive")
+            .columns(("id", "name"))
+            .from_select(from("users").select("id"));
+        assert!(matches!(mismatch.to_sql(), Err(Error::InvalidQuery { .. })));
+
+        let wildcard = insert("archive")
+            .columns(("id", "name"))
+            .from_select(from("users").select("*"));
+        assert_eq!(
+            wildcard.to_sql().unwrap(),
+            "INSERT INTO archive (id, name) SELECT * FROM users"
+        );
+
+        let raw = insert("archive")
+            .columns(("id", "name"))
+            .from_select(from("users").select_raw("id, name"));
+        assert_eq!(
+            raw.to_sql().unwrap(),
+            "INSERT INTO archive (id, name) SELECT id, name FROM users"
+        );
+
+        let without_query = insert("archive").columns("id");
+        assert!(without_query.to_sql().is_err());
+    }
+
+    use crate::dialect::GenericDialect;
+
+    fn id_rows(count: i32) -> Vec<HashMap<String, Value>> {
+        (1..=count)
+            .map(|id| {
+                let mut row = HashMap::new();
+                row.insert("id".to_string(), Value::I32(id));
+                row.insert("name".to_string(), Value::from(format!("user{}", id)));
+                row
+            })
+            .collect()
+    }
+
+    /// A row with its columns in a fixed order
+    struct OrderedRow(Vec<(&'static str, Value)>);
+
+    impl IntoInsertData for OrderedRow {
+        fn into_insert_data(self) -> (Vec<String>, Vec<Value>) {
+            self.0.into_iter().map(|(column, value)| (column.to_string(), value)).unzip()
+        }
+    }
+
+    #[test]
+    fn test_values_many_matches_values_to_columns_by_name() {
+        let rows = vec![
+            OrderedRow(vec![("id", Value::I32(1)), ("name", "a".into())]),
+            OrderedRow(vec![("name", "b".into()), ("id", Value::I32(2))]),
+        ];
+
+        let query = insert("users").values_many(rows);
+        let (sql, params) = query.to_sql_with_params(&GenericDialect).unwrap();
+        assert_eq!(sql, "INSERT INTO users (id, name) VALUES (?, ?), (?, ?)");
+        assert_eq!(params, vec![Value::I32(1), "a".into(), Value::I32(2), "b".into()]);
+    }
+
+    #[test]
+    fn test_values_many_rejects_mismatched_columns() {
+        let mut rows = id_rows(2);
+        rows[1].remove("name");
+        rows[1].insert("email".to_string(), "b@example.com".into());
+
+        let result = insert("users").values_many(rows).to_sql();
+        let Err(Error::InvalidQuery { message }) = result else {
+            panic!("expected an invalid query error, got {:?}", result);
+        };
+        assert!(message.contains("row 2"), "{}", message);
+
+        let mut rows = id_rows(2);
+        rows[1].remove("name");
+        assert!(insert("users").values_many(rows).to_sql().is_err());
+    }
+
+    #[test]
+    fn test_chunked_splits_rows_into_statements() {
+        use crate::dialect::PostgresDialect;
+
+        let query = insert("users").values_many(id_rows(5)).chunked(2);
+        let statements = query.to_sql_chunks(&PostgresDialect).unwrap();
+
+        let row_counts: Vec<usize> =
+            statements.iter().map(|(_, params)| params.len() / 2).collect();
+        assert_eq!(row_counts, vec![2, 2, 1]);
+        for (sql, params) in &statements {
+            // Placeholders restart at $1 in every statement
+            assert!(sql.contains("VALUES ($1, $2)"));
+            assert_eq!(sql.matches('$').count(), params.len());
+        }
+        assert!(matches!(query.to_sql(), Err(Error::InvalidQuery { .. })));
+        assert!(matches!(query.parameters(), Err(Error::InvalidQuery { .. })));
+
+        let single = insert("users").values_many(id_rows(2)).chunked(2);
+        assert_eq!(single.to_sql_chunks(&PostgresDialect).unwrap().len(), 1);
+        assert!(single.to_sql().is_ok());
+
+        let zero = insert("users").values_many(id_rows(2)).chunked(0);
+        assert!(zero.to_sql_chunks(&PostgresDialect).is_err());
+    }
+
+    #[test]
+    fn test_rows_are_split_at_the_dialect_parameter_limit() {
+        use crate::dialect::SqliteDialect;
+
+        // 2 parameters per row plus 1 for the conflict update: 16382 rows fit
+        // in SQLite's 32766 parameters
+        let mut updates = HashMap::new();
+        updates.insert("visits".to_string(), Value::I32(0));
+        let query = insert("users")
+            .values_many(id_rows(20000))
+            .on_conflict("id")
+            .merge_with(updates);
+
+        let statements = query.to_sql_chunks(&SqliteDialect).unwrap();
+        let param_counts: Vec<usize> = statements.iter().map(|(_, p)| p.len()).collect();
+        assert_eq!(param_counts, vec![16382 * 2 + 1, (20000 - 16382) * 2 + 1]);
+        assert!(statements.iter().all(|(sql, _)| sql.ends_with(r#""visits" = ?"#)));
+
+        // The generic dialect has no limit
+        assert_eq!(query.to_sql_chunks(&GenericDialect).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_single_statement_apis_fail_past_the_parameter_limit() {
+        use crate::dialect::SqliteDialect;
+
+        // 16383 rows of 2 parameters fill SQLite's 32766 exactly
+        let fits = insert("users").values_many(id_rows(16383));
+        assert!(fits.to_sql_with(&SqliteDialect).is_ok());
+        assert!(fits.returning("id").to_sql_with(&SqliteDialect).is_ok());
+
+        let split = insert("users").values_many(id_rows(16384));
+        assert_eq!(split.to_sql_chunks(&SqliteDialect).unwrap().len(), 2);
+        assert!(matches!(
+            split.to_sql_with(&SqliteDialect),
+            Err(Error::InvalidQuery { .. })
+        ));
+        assert!(matches!(
+            split.returning("id").to_sql_with(&SqliteDialect),
+            Err(Error::InvalidQuery { .. })
+        ));
+    }
 }
@@ -1,14 +1,31 @@
 //! Query builder module
 
+pub mod column;
 pub mod common;
+pub mod compound;
+pub mod cte;
 pub mod delete;
 pub mod insert;
+pub mod raw;
+pub mod returning;
 pub mod select;
+pub mod table_ref;
 pub mod update;
+pub mod window;
 
 // Re-export types from submodules
+pub use column::{Column, ColumnCondition, Table};
+pub use compound::{CompoundSelectBuilder, SetOperator};
+pub use cte::Cte;
 pub use delete::{DeleteBuilderComplete, DeleteBuilderInitial};
-pub use insert::{InsertBuilderComplete, InsertBuilderInitial, IntoInsertData};
+pub use insert::{
+    InsertBuilderComplete, InsertBuilderInitial, InsertBuilderWithColumns, IntoInsertData,
+    OnConflictBuilder,
+};
+pub use raw::Raw;
+pub use returning::ReturningBuilder;
+pub use table_ref::TableRef;
 pub use update::{
     IntoUpdateData, UpdateBuilderComplete, UpdateBuilderInitial, UpdateBuilderWithSet,
 };
+pub use window::{FrameBound, FrameUnits, Window, WindowFrame, WindowFunction};
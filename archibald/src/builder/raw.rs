@@ -0,0 +1,257 @@
+//! Raw SQL queries and fragments
+
+use super::common::{QueryBuilder, RenderContext};
+use crate::dialect::Dialect;
+use crate::{Error, Result, Value};
+
+/// A piece of raw SQL with bound values
+///
+/// Values are bound to `?` placeholders, which are rewritten to the
+/// dialect's own placeholders (`$1, $2, ...` on PostgreSQL) when the query is
+/// rendered, continuing the numbering of the query the fragment is part of.
+/// A `?` inside a quoted string or identifier is left alone, and `\?` renders
+/// a literal `?` (for PostgreSQL's JSONB operators).
+///
+/// The number of placeholders must match the number of bound values; a
+/// mismatch fails rendering with [`Error::InvalidQuery`].
+///
+/// A `Raw` is a complete query on its own (see [`raw`](crate::raw)) or a
+/// fragment inside a builder, through methods such as `where_raw`,
+/// `select_raw`, `order_by_raw`, `having_raw` and `join_raw`.
+///
+/// # Examples
+/// ```
+/// use archibald::{from, raw, QueryBuilder, Raw};
+/// use archibald::dialect::PostgresDialect;
+///
+/// let query = from("users")
+///     .select("id")
+///     .where_(("active", true))
+///     .where_raw(raw("LOWER(email) = LOWER(?)", ["Ada@Example.com"]))
+///     .order_by_raw(Raw::new("LENGTH(name) DESC"));
+///
+/// assert_eq!(
+///     query.to_sql_with(&PostgresDialect).unwrap(),
+///     r#"SELECT "id" FROM "users" WHERE "active" = $1 AND LOWER(email) = LOWER($2) ORDER BY LENGTH(name) DESC"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raw {
+    sql: String,
+    params: Vec<Value>,
+}
+
+impl Raw {
+    /// Create a raw fragment without bound values
+    pub fn new(sql: &str) -> Self {
+        Self {
+            sql: sql.to_string(),
+            params: Vec::new(),
+        }
+    }
+
+    /// Bind a value to the next `?` placeholder
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{QueryBuilder, Raw, Value};
+    ///
+    /// let query = Raw::new("SELECT * FROM users WHERE id = ? AND active = ?")
+    ///     .bind(42)
+    ///     .bind(true);
+    ///
+    /// assert_eq!(query.parameters().unwrap(), vec![Value::I32(42), Value::Bool(true)]);
+    /// ```
+    pub fn bind<V>(mut self, value: V) -> Self
+    where
+        V: Into<Value>,
+    {
+        self.params.push(value.into());
+        self
+    }
+
+    /// The SQL text, with `?` placeholders
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The bound values, in placeholder order
+    pub fn values(&self) -> &[Value] {
+        &self.params
+    }
+
+    /// Render the SQL, binding the values through the render context
+    pub(crate) fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        let placeholders = scan(&self.sql, |_| {});
+        if placeholders != self.params.len() {
+            return Err(Error::invalid_query(format!(
+                "raw SQL has {} placeholder(s) but {} value(s) were bound: {}",
+                placeholders,
+                self.params.len(),
+                self.sql
+            )));
+        }
+
+        let mut sql = String::with_capacity(self.sql.len());
+        let mut params = self.params.iter();
+        scan(&self.sql, |piece| match piece {
+            Piece::Text(text) => sql.push_str(text),
+            Piece::Placeholder => {
+                // The counts were checked above
+                let value = params.next().expect("placeholder count checked");
+                sql.push_str(&ctx.bind(value));
+            }
+        });
+        Ok(sql)
+    }
+}
+
+impl From<&str> for Raw {
+    fn from(sql: &str) -> Self {
+        Raw::new(sql)
+    }
+}
+
+impl From<String> for Raw {
+    fn from(sql: String) -> Self {
+        Self {
+            sql,
+            params: Vec::new(),
+        }
+    }
+}
+
+impl QueryBuilder for Raw {
+    fn to_sql_with_params(&self, dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
+        let mut ctx = RenderContext::new(dialect);
+        let sql = self.render(&mut ctx)?;
+        Ok((sql, ctx.into_params()))
+    }
+
+    fn clone_builder(&self) -> Self {
+        self.clone()
+    }
+}
+
+/// A run of literal SQL text or a `?` placeholder
+enum Piece<'a> {
+    Text(&'a str),
+    Placeholder,
+}
+
+/// Split raw SQL into text and placeholders, returning the placeholder count
+///
+/// Quoted strings and identifiers (`'...'`, `"..."` and `` `...` ``) are
+/// copied verbatim; a doubled quote inside them simply closes and reopens
+/// the quote, which needs no special handling. `\?` is emitted as a literal
+/// `?`.
+fn scan<'a, F>(sql: &'a str, mut emit: F) -> usize
+where
+    F: FnMut(Piece<'a>),
+{
+    let mut count = 0;
+    let mut quote = None;
+    let mut start = 0;
+    let mut chars = sql.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some(open), _) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '\\') if matches!(chars.peek(), Some((_, '?'))) => {
+                emit(Piece::Text(&sql[start..i]));
+                let (j, _) = chars.next().expect("peeked");
+                start = j;
+            }
+            (None, '?') => {
+                emit(Piece::Text(&sql[start..i]));
+                emit(Piece::Placeholder);
+                count += 1;
+                start = i + 1;
+            }
+            (None, _) => {}
+        }
+    }
+    emit(Piece::Text(&sql[start..]));
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MySqlDialect, PostgresDialect, SqliteDialect};
+    use crate::raw;
+
+    #[test]
+    fn test_raw_query_binds_values() {
+        let query = raw("SELECT * FROM users WHERE id = ? AND name = ?", [
+            Value::from(1),
+            Value::from("Ada"),
+        ]);
+
+        assert_eq!(query.to_sql().unwrap(), "SELECT * FROM users WHERE id = ? AND name = ?");
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            "SELECT * FROM users WHERE id = $1 AND name = $2"
+        );
+        assert_eq!(
+            query.to_sql_with(&SqliteDialect).unwrap(),
+            "SELECT * FROM users WHERE id = ? AND name = ?"
+        );
+        assert_eq!(
+            query.to_sql_with(&MySqlDialect).unwrap(),
+            "SELECT * FROM users WHERE id = ? AND name = ?"
+        );
+        assert_eq!(query.parameters().unwrap(), vec![Value::I32(1), Value::from("Ada")]);
+    }
+
+    #[test]
+    fn test_raw_placeholder_count_mismatch() {
+        let too_few = Raw::new("SELECT * FROM users WHERE id = ? AND name = ?").bind(1);
+        let error = too_few.to_sql().unwrap_err();
+        assert!(matches!(error, Error::InvalidQuery { .. }));
+        assert!(error.to_string().contains("2 placeholder(s) but 1 value(s)"));
+
+        let too_many = Raw::new("SELECT 1").bind(1);
+        assert!(too_many.to_sql().is_err());
+        assert!(too_many.parameters().is_err());
+    }
+
+    #[test]
+    fn test_raw_skips_quoted_question_marks() {
+        let query = Raw::new(r#"SELECT '?', "what?", `why?` FROM faq WHERE id = ?"#).bind(7);
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"SELECT '?', "what?", `why?` FROM faq WHERE id = $1"#
+        );
+
+        let escaped = Raw::new("SELECT 'it''s' FROM t WHERE id = ?").bind(1);
+        assert_eq!(
+            escaped.to_sql_with(&PostgresDialect).unwrap(),
+            "SELECT 'it''s' FROM t WHERE id = $1"
+        );
+    }
+
+    #[test]
+    fn test_raw_escaped_question_mark() {
+        let query = Raw::new(r"SELECT * FROM docs WHERE tags \? ? AND meta \?| ?")
+            .bind("rust")
+            .bind(Value::Array(vec![Value::from("a"), Value::from("b")]));
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            "SELECT * FROM docs WHERE tags ? $1 AND meta ?| $2"
+        );
+        assert_eq!(query.parameters().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_raw_from_string() {
+        let query: Raw = "SELECT 1".into();
+        assert_eq!(query.to_sql().unwrap(), "SELECT 1");
+        assert_eq!(query.sql(), "SELECT 1");
+
+        let query = Raw::from(String::from("SELECT ?")).bind(true);
+        assert_eq!(query.to_sql_with(&PostgresDialect).unwrap(), "SELECT $1");
+    }
+}
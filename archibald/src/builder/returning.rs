@@ -0,0 +1,136 @@
+//! RETURNING clause support for INSERT, UPDATE and DELETE
+
+use super::common::{IntoColumns, QueryBuilder};
+use crate::dialect::Dialect;
+use crate::{Error, Result, Value};
+
+/// An INSERT, UPDATE or DELETE query with a RETURNING clause
+///
+/// Created with `.returning(...)` on a complete modification builder. The
+/// affected rows are fetched like a SELECT, through
+/// [`ExecutableQuery`](crate::ExecutableQuery).
+#[derive(Debug, Clone)]
+pub struct ReturningBuilder<Q> {
+    query: Q,
+    columns: Vec<String>,
+}
+
+impl<Q> ReturningBuilder<Q> {
+    pub(crate) fn new<C>(query: Q, columns: C) -> Self
+    where
+        C: IntoColumns,
+    {
+        Self {
+            query,
+            columns: columns.into_columns(),
+        }
+    }
+}
+
+impl<Q> QueryBuilder for ReturningBuilder<Q>
+where
+    Q: QueryBuilder + Clone,
+{
+    fn to_sql_with_params(&self, dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
+        if !dialect.supports_returning() {
+            return Err(Error::invalid_query(format!(
+                "RETURNING is not supported by the {} dialect",
+                dialect.name()
+            )));
+        }
+
+        if self.columns.is_empty() {
+            return Err(Error::invalid_query(
+                "RETURNING requires at least one column",
+            ));
+        }
+
+        let (mut sql, params) = self.query.to_sql_with_params(dialect)?;
+        let columns: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| dialect.quote_reference(column))
+            .collect();
+        sql.push_str(" RETURNING ");
+        sql.push_str(&columns.join(", "));
+
+        Ok((sql, params))
+    }
+
+    fn clone_builder(&self) -> Self {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MySqlDialect, PostgresDialect, SqliteDialect};
+    use crate::{delete, insert, update};
+    use std::collections::HashMap;
+
+    fn user_data() -> HashMap<String, Value> {
+        let mut data = HashMap::new();
+        data.insert("name".to_string(), "John".into());
+        data
+    }
+
+    #[test]
+    fn test_insert_returning() {
+        let query = insert("users").values(user_data()).returning(("id", "name"));
+
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "INSERT INTO users (name) VALUES (?) RETURNING id, name"
+        );
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"INSERT INTO "users" ("name") VALUES ($1) RETURNING "id", "name""#
+        );
+        assert_eq!(query.parameters().unwrap(), vec![Value::from("John")]);
+    }
+
+    #[test]
+    fn test_update_returning_all_columns() {
+        let query = update("users")
+            .set(user_data())
+            .where_(("id", 1))
+            .returning("*");
+
+        assert_eq!(
+            query.to_sql_with(&SqliteDialect).unwrap(),
+            r#"UPDATE "users" SET "name" = ? WHERE "id" = ? RETURNING *"#
+        );
+        assert_eq!(query.parameters().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_delete_returning() {
+        let query = delete("sessions")
+            .where_(("expired", true))
+            .returning("id");
+
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"DELETE FROM "sessions" WHERE "expired" = $1 RETURNING "id""#
+        );
+    }
+
+    #[test]
+    fn test_returning_unsupported_dialect() {
+        let query = delete("sessions").where_(("id", 1)).returning("id");
+
+        let err = query.to_sql_with(&MySqlDialect).unwrap_err();
+        assert!(matches!(err, Error::InvalidQuery { .. }));
+        assert!(err.to_string().contains("mysql"));
+    }
+
+    #[test]
+    fn test_returning_requires_columns() {
+        let query = delete("sessions")
+            .where_(("id", 1))
+            .returning(Vec::<String>::new());
+
+        assert!(query.to_sql().is_err());
+    }
+}
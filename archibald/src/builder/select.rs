@@ -1,10 +1,18 @@
 //! SELECT query builder implementation
 
 use super::common::{
-    AggregateFunction, GroupByClause, HavingCondition, IntoColumnSelectors, IntoColumns,
-    IntoCondition, JoinClause, JoinConnector, JoinType, OrderByClause, QueryBuilder, SortDirection,
-    WhereCondition, WhereConnector,
+    AggregateFunction, GroupByClause, HavingCondition, HavingNode, IntoColumnRef,
+    IntoColumnSelectors, IntoColumns, IntoCondition, JoinBuilder, JoinClause, JoinConnector,
+    JoinNode, JoinOperand, JoinType, OrderByClause, OrderByNode, QueryBuilder, RenderContext,
+    SortDirection, WhereCondition, WhereConnector, WhereGroupBuilder, WhereNode, render_order_by,
+    render_value_comparison, render_where_nodes,
 };
+use super::compound::{CompoundSelectBuilder, SetOperator};
+use super::cte::{Cte, render_with};
+use super::raw::Raw;
+use super::table_ref::TableRef;
+use super::window::{Window, WindowFunction, render_windows};
+use crate::dialect::{Dialect, GenericDialect};
 use crate::{Error, IntoOperator, Result, Value};
 
 /// Column selector that can be a regular column or an aggregation
@@ -26,6 +34,19 @@ pub enum ColumnSelector {
         subquery: Subquery,
         alias: Option<String>,
     },
+    Raw {
+        raw: Raw,
+        alias: Option<String>,
+    },
+    Window {
+        function: Box<ColumnSelector>,
+        window: Window,
+        alias: Option<String>,
+    },
+    WindowFunction {
+        function: WindowFunction,
+        alias: Option<String>,
+    },
 }
 
 impl ColumnSelector {
@@ -50,55 +71,73 @@ impl ColumnSelector {
     }
 
     /// Create a COUNT(column) selector
-    pub fn count_column(column: &str) -> Self {
+    pub fn count_column<C>(column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
         Self::Aggregate {
             function: AggregateFunction::Count,
-            column: column.to_string(),
+            column: column.into_column_ref(),
             alias: None,
         }
     }
 
     /// Create a COUNT(DISTINCT column) selector
-    pub fn count_distinct(column: &str) -> Self {
+    pub fn count_distinct<C>(column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
         Self::Aggregate {
             function: AggregateFunction::CountDistinct,
-            column: column.to_string(),
+            column: column.into_column_ref(),
             alias: None,
         }
     }
 
     /// Create a SUM(column) selector
-    pub fn sum(column: &str) -> Self {
+    pub fn sum<C>(column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
         Self::Aggregate {
             function: AggregateFunction::Sum,
-            column: column.to_string(),
+            column: column.into_column_ref(),
             alias: None,
         }
     }
 
     /// Create an AVG(column) selector
-    pub fn avg(column: &str) -> Self {
+    pub fn avg<C>(column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
         Self::Aggregate {
             function: AggregateFunction::Avg,
-            column: column.to_string(),
+            column: column.into_column_ref(),
             alias: None,
         }
     }
 
     /// Create a MIN(column) selector
-    pub fn min(column: &str) -> Self {
+    pub fn min<C>(column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
         Self::Aggregate {
             function: AggregateFunction::Min,
-            column: column.to_string(),
+            column: column.into_column_ref(),
             alias: None,
         }
     }
 
     /// Create a MAX(column) selector
-    pub fn max(column: &str) -> Self {
+    pub fn max<C>(column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
         Self::Aggregate {
             function: AggregateFunction::Max,
-            column: column.to_string(),
+            column: column.into_column_ref(),
             alias: None,
         }
     }
@@ -133,41 +172,362 @@ impl ColumnSelector {
                 *alias_field = Some(alias.to_string());
                 self
             }
+            Self::Raw {
+                alias: ref mut alias_field,
+                ..
+            } => {
+                *alias_field = Some(alias.to_string());
+                self
+            }
+            Self::Window {
+                alias: ref mut alias_field,
+                ..
+            } => {
+                *alias_field = Some(alias.to_string());
+                self
+            }
+            Self::WindowFunction {
+                alias: ref mut alias_field,
+                ..
+            } => {
+                *alias_field = Some(alias.to_string());
+                self
+            }
         }
     }
 
+    /// Split off the alias, leaving the bare expression
+    fn take_alias(mut self) -> (Self, Option<String>) {
+        let alias = match &mut self {
+            Self::Column { alias, .. }
+            | Self::Aggregate { alias, .. }
+            | Self::CountAll { alias }
+            | Self::SubqueryColumn { alias, .. }
+            | Self::Raw { alias, .. }
+            | Self::Window { alias, .. }
+            | Self::WindowFunction { alias, .. } => alias.take(),
+        };
+        (self, alias)
+    }
+
     /// Create a subquery column selector with alias
-    pub fn subquery_as(query: SelectBuilderComplete, alias: &str) -> Self {
+    pub fn subquery_as<Q>(query: Q, alias: &str) -> Self
+    where
+        Q: Into<Subquery>,
+    {
         Self::SubqueryColumn {
-            subquery: Subquery::new(query),
+            subquery: query.into(),
             alias: Some(alias.to_string()),
         }
     }
+
+    /// Create a selector for a raw SQL expression
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, raw, ColumnSelector, QueryBuilder};
+    ///
+    /// let query = from("users").select((
+    ///     "id",
+    ///     ColumnSelector::raw(raw("COALESCE(nickname, ?)", ["anonymous"])).as_alias("handle"),
+    /// ));
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT id, COALESCE(nickname, ?) AS handle FROM users"
+    /// );
+    /// ```
+    pub fn raw<R>(raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        Self::Raw {
+            raw: raw.into(),
+            alias: None,
+        }
+    }
+
+    fn window_function(function: WindowFunction) -> Self {
+        Self::Window {
+            function: Box::new(Self::WindowFunction { function, alias: None }),
+            window: Window::new(),
+            alias: None,
+        }
+    }
+
+    /// Create a ROW_NUMBER() window function, numbering rows from 1
+    ///
+    /// Window functions render `OVER ()` until a window is given with
+    /// [`over`](Self::over) or [`over_named`](Self::over_named).
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, ColumnSelector, QueryBuilder};
+    ///
+    /// let query = from("scores").select((
+    ///     "player",
+    ///     ColumnSelector::row_number()
+    ///         .over(|w| w.partition_by("game").order_by_desc("points"))
+    ///         .as_alias("position"),
+    /// ));
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT player, ROW_NUMBER() OVER (PARTITION BY game ORDER BY points DESC) AS position FROM scores"
+    /// );
+    /// ```
+    pub fn row_number() -> Self {
+        Self::window_function(WindowFunction::RowNumber)
+    }
+
+    /// Create a RANK() window function, leaving gaps after ties
+    pub fn rank() -> Self {
+        Self::window_function(WindowFunction::Rank)
+    }
+
+    /// Create a DENSE_RANK() window function, without gaps after ties
+    pub fn dense_rank() -> Self {
+        Self::window_function(WindowFunction::DenseRank)
+    }
+
+    /// Create a LAG(column, offset) window function, reading a previous row
+    pub fn lag<C>(column: C, offset: u64) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        Self::window_function(WindowFunction::Lag {
+            column: column.into_column_ref(),
+            offset,
+            default: None,
+        })
+    }
+
+    /// Create a LAG(column, offset, default) window function
+    ///
+    /// The default is bound as a parameter and returned where there is no
+    /// previous row.
+    pub fn lag_or<C, V>(column: C, offset: u64, default: V) -> Self
+    where
+        C: IntoColumnRef,
+        V: Into<Value>,
+    {
+        Self::window_function(WindowFunction::Lag {
+            column: column.into_column_ref(),
+            offset,
+            default: Some(default.into()),
+        })
+    }
+
+    /// Create a LEAD(column, offset) window function, reading a following row
+    pub fn lead<C>(column: C, offset: u64) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        Self::window_function(WindowFunction::Lead {
+            column: column.into_column_ref(),
+            offset,
+            default: None,
+        })
+    }
+
+    /// Create a LEAD(column, offset, default) window function
+    pub fn lead_or<C, V>(column: C, offset: u64, default: V) -> Self
+    where
+        C: IntoColumnRef,
+        V: Into<Value>,
+    {
+        Self::window_function(WindowFunction::Lead {
+            column: column.into_column_ref(),
+            offset,
+            default: Some(default.into()),
+        })
+    }
+
+    /// Evaluate this selector over a window
+    ///
+    /// Works for window functions, aggregates and raw expressions; other
+    /// selectors fail to render. Calling `over` again refines the same window.
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, ColumnSelector, QueryBuilder};
+    ///
+    /// let query = from("employees").select((
+    ///     "name",
+    ///     ColumnSelector::avg("salary").over(|w| w.partition_by("department")).as_alias("avg"),
+    /// ));
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT name, AVG(salary) OVER (PARTITION BY department) AS avg FROM employees"
+    /// );
+    /// ```
+    pub fn over<F>(self, build: F) -> Self
+    where
+        F: FnOnce(Window) -> Window,
+    {
+        match self {
+            Self::Window { function, window, alias } => Self::Window {
+                function,
+                window: build(window),
+                alias,
+            },
+            selector => {
+                let (function, alias) = selector.take_alias();
+                Self::Window {
+                    function: Box::new(function),
+                    window: build(Window::new()),
+                    alias,
+                }
+            }
+        }
+    }
+
+    /// Evaluate this selector over a named window, rendering `OVER name`
+    ///
+    /// See [`SelectBuilderComplete::window`].
+    pub fn over_named(self, name: &str) -> Self {
+        self.over(|w| w.based_on(name))
+    }
+
+    /// Render the selector, including its alias
+    pub(crate) fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        let (expression, alias) = match self {
+            ColumnSelector::Column { name, alias } => (ctx.ident(name), alias),
+            ColumnSelector::Aggregate {
+                function,
+                column,
+                alias,
+            } => {
+                let func_sql = match function {
+                    AggregateFunction::CountDistinct => {
+                        format!("{}({}))", function, ctx.ident(column))
+                    }
+                    _ => format!("{}({})", function, ctx.ident(column)),
+                };
+                (func_sql, alias)
+            }
+            ColumnSelector::CountAll { alias } => ("COUNT(*)".to_string(), alias),
+            ColumnSelector::SubqueryColumn { subquery, alias } => (subquery.render(ctx)?, alias),
+            ColumnSelector::Raw { raw, alias } => (raw.render(ctx)?, alias),
+            ColumnSelector::Window {
+                function,
+                window,
+                alias,
+            } => {
+                let function_sql = match function.as_ref() {
+                    ColumnSelector::Aggregate { .. }
+                    | ColumnSelector::CountAll { .. }
+                    | ColumnSelector::Raw { .. } => function.render(ctx)?,
+                    ColumnSelector::WindowFunction { function, .. } => function.render(ctx),
+                    _ => {
+                        return Err(Error::invalid_query(
+                            "only window functions, aggregates and raw expressions can be used \
+                             with OVER",
+                        ));
+                    }
+                };
+                (function_sql + &window.render_over(ctx)?, alias)
+            }
+            ColumnSelector::WindowFunction { function, .. } => {
+                return Err(Error::invalid_query(format!(
+                    "{} requires an OVER clause",
+                    function.render(ctx)
+                )));
+            }
+        };
+        Ok(ctx.aliased(expression, alias.as_ref()))
+    }
+}
+
+/// A query producing rows: a SELECT, a compound SELECT or raw SQL
+///
+/// Used wherever a query is nested in another one, such as subqueries and
+/// common table expressions.
+#[derive(Debug, Clone)]
+pub enum SelectQuery {
+    Select(Box<SelectBuilderComplete>),
+    Compound(Box<CompoundSelectBuilder>),
+    Raw(Raw),
+}
+
+impl SelectQuery {
+    /// Render the query, continuing the outer placeholder numbering
+    pub(crate) fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        match self {
+            SelectQuery::Select(query) => query.render(ctx),
+            SelectQuery::Compound(query) => query.render(ctx),
+            SelectQuery::Raw(raw) => raw.render(ctx),
+        }
+    }
+}
+
+impl From<SelectBuilderComplete> for SelectQuery {
+    fn from(query: SelectBuilderComplete) -> Self {
+        SelectQuery::Select(Box::new(query))
+    }
+}
+
+impl From<CompoundSelectBuilder> for SelectQuery {
+    fn from(query: CompoundSelectBuilder) -> Self {
+        SelectQuery::Compound(Box::new(query))
+    }
+}
+
+impl From<Raw> for SelectQuery {
+    fn from(raw: Raw) -> Self {
+        SelectQuery::Raw(raw)
+    }
 }
 
 /// Subquery wrapper for use in various SQL contexts
 #[derive(Debug, Clone)]
 pub struct Subquery {
-    pub query: Box<SelectBuilderComplete>,
+    pub query: SelectQuery,
 }
 
 impl Subquery {
-    /// Create a new subquery from a SelectBuilder
-    pub fn new(query: SelectBuilderComplete) -> Self {
+    /// Create a new subquery from a SELECT, a compound SELECT or raw SQL
+    pub fn new<Q>(query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
         Self {
-            query: Box::new(query),
+            query: query.into(),
         }
     }
 
     /// Convert to SQL string
     pub fn to_sql(&self) -> Result<String> {
-        let inner_sql = self.query.to_sql()?;
+        self.render(&mut RenderContext::new(&GenericDialect))
+    }
+
+    /// Render the parenthesized subquery, continuing the outer placeholder numbering
+    pub(crate) fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        let inner_sql = self.query.render(ctx)?;
         Ok(format!("({})", inner_sql))
     }
 
     /// Get parameters from the subquery
-    pub fn parameters(&self) -> &[Value] {
-        self.query.parameters()
+    pub fn parameters(&self) -> Result<Vec<Value>> {
+        let mut ctx = RenderContext::new(&GenericDialect);
+        self.render(&mut ctx)?;
+        Ok(ctx.into_params())
+    }
+}
+
+impl From<SelectBuilderComplete> for Subquery {
+    fn from(query: SelectBuilderComplete) -> Self {
+        Subquery::new(query)
+    }
+}
+
+impl From<CompoundSelectBuilder> for Subquery {
+    fn from(query: CompoundSelectBuilder) -> Self {
+        Subquery::new(query)
+    }
+}
+
+impl From<Raw> for Subquery {
+    fn from(raw: Raw) -> Self {
+        Subquery::new(raw)
     }
 }
 
@@ -180,48 +540,63 @@ pub struct SubqueryCondition {
     pub connector: WhereConnector,
 }
 
+impl SubqueryCondition {
+    /// Render `column IN (subquery)`, or `EXISTS (subquery)` without a column
+    pub(crate) fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        self.operator.validate()?;
+        let subquery = self.subquery.render(ctx)?;
+        if self.column.is_empty() {
+            Ok(format!("{} {}", self.operator.as_str(), subquery))
+        } else {
+            Ok(format!("{} {} {}", ctx.ident(&self.column), self.operator.as_str(), subquery))
+        }
+    }
+}
+
 /// SELECT query builder in initial state (before select() is called)
 /// Can build conditions but cannot execute queries  
 #[derive(Debug, Clone)]
 pub struct SelectBuilderInitial {
-    table_name: String,
-    where_conditions: Vec<WhereCondition>,
-    subquery_conditions: Vec<SubqueryCondition>,
-    join_clauses: Vec<JoinClause>,
-    order_by_clauses: Vec<OrderByClause>,
+    ctes: Vec<Cte>,
+    table: TableRef,
+    where_conditions: Vec<WhereNode>,
+    join_clauses: Vec<JoinNode>,
+    order_by_clauses: Vec<OrderByNode>,
     group_by_clause: Option<GroupByClause>,
-    having_conditions: Vec<HavingCondition>,
+    having_conditions: Vec<HavingNode>,
     distinct: bool,
     limit_value: Option<u64>,
     offset_value: Option<u64>,
-    parameters: Vec<Value>,
 }
 
 /// SELECT query builder in complete state (after select() is called)
 /// Can execute queries and add more conditions
 #[derive(Debug, Clone)]
 pub struct SelectBuilderComplete {
-    pub table_name: String,
+    pub ctes: Vec<Cte>,
+    pub table: TableRef,
     pub selected_columns: Vec<ColumnSelector>,
-    pub where_conditions: Vec<WhereCondition>,
-    pub subquery_conditions: Vec<SubqueryCondition>,
-    pub join_clauses: Vec<JoinClause>,
-    pub order_by_clauses: Vec<OrderByClause>,
+    pub where_conditions: Vec<WhereNode>,
+    pub join_clauses: Vec<JoinNode>,
+    pub order_by_clauses: Vec<OrderByNode>,
     pub group_by_clause: Option<GroupByClause>,
-    pub having_conditions: Vec<HavingCondition>,
+    pub having_conditions: Vec<HavingNode>,
+    pub windows: Vec<(String, Window)>,
     pub distinct: bool,
     pub limit_value: Option<u64>,
     pub offset_value: Option<u64>,
-    pub parameters: Vec<Value>,
 }
 
 impl SelectBuilderInitial {
     /// Create a new SELECT query builder in initial state
-    pub fn new(table: &str) -> Self {
+    pub fn new<T>(table: T) -> Self
+    where
+        T: Into<TableRef>,
+    {
         Self {
-            table_name: table.to_string(),
+            ctes: Vec::new(),
+            table: table.into(),
             where_conditions: Vec::new(),
-            subquery_conditions: Vec::new(),
             join_clauses: Vec::new(),
             order_by_clauses: Vec::new(),
             group_by_clause: None,
@@ -229,10 +604,62 @@ impl SelectBuilderInitial {
             distinct: false,
             limit_value: None,
             offset_value: None,
-            parameters: Vec::new(),
         }
     }
 
+    /// Add a common table expression to a WITH clause before the query
+    ///
+    /// CTEs render in the order they are added, and their parameters are
+    /// bound ahead of the main query's.
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, op, QueryBuilder};
+    ///
+    /// let query = from("recent")
+    ///     .with("recent", from("orders").select(("id", "total")).where_(("total", op::GT, 100)))
+    ///     .select("*");
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "WITH recent AS (SELECT id, total FROM orders WHERE total > ?) SELECT * FROM recent"
+    /// );
+    /// ```
+    pub fn with<Q>(mut self, name: &str, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::new(name, query));
+        self
+    }
+
+    /// Add a recursive common table expression, rendering `WITH RECURSIVE`
+    ///
+    /// The query is usually an anchor query combined with a query over the
+    /// CTE itself through [`union_all`](SelectBuilderComplete::union_all).
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder};
+    ///
+    /// let anchor = from("employees").select(("id", "manager_id")).where_(("id", 1));
+    /// let reports = from("employees")
+    ///     .inner_join("chain", "employees.manager_id", "chain.id")
+    ///     .select(("employees.id", "employees.manager_id"));
+    ///
+    /// let query = from("chain")
+    ///     .with_recursive("chain", ("id", "manager_id"), anchor.union_all(reports))
+    ///     .select("id");
+    /// assert!(query.to_sql().unwrap().starts_with("WITH RECURSIVE chain (id, manager_id) AS ("));
+    /// ```
+    pub fn with_recursive<C, Q>(mut self, name: &str, columns: C, query: Q) -> Self
+    where
+        C: IntoColumns,
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::recursive(name, columns, query));
+        self
+    }
+
     /// Select specific columns, transitioning to SelectBuilderComplete
     ///
     /// # Examples
@@ -248,39 +675,58 @@ impl SelectBuilderInitial {
         let selected_columns = columns.into_column_selectors();
 
         SelectBuilderComplete {
-            table_name: self.table_name,
+            ctes: self.ctes,
+            table: self.table,
             selected_columns,
             where_conditions: self.where_conditions,
-            subquery_conditions: self.subquery_conditions,
             join_clauses: self.join_clauses,
             order_by_clauses: self.order_by_clauses,
             group_by_clause: self.group_by_clause,
             having_conditions: self.having_conditions,
+            windows: Vec::new(),
             distinct: self.distinct,
             limit_value: self.limit_value,
             offset_value: self.offset_value,
-            parameters: self.parameters,
         }
     }
 
     /// Select all columns, transitioning to SelectBuilderComplete
     pub fn select_all(self) -> SelectBuilderComplete {
         SelectBuilderComplete {
-            table_name: self.table_name,
+            ctes: self.ctes,
+            table: self.table,
             selected_columns: vec![ColumnSelector::Column { name: "*".to_string(), alias: None }],
             where_conditions: self.where_conditions,
-            subquery_conditions: self.subquery_conditions,
             join_clauses: self.join_clauses,
             order_by_clauses: self.order_by_clauses,
             group_by_clause: self.group_by_clause,
             having_conditions: self.having_conditions,
+            windows: Vec::new(),
             distinct: self.distinct,
             limit_value: self.limit_value,
             offset_value: self.offset_value,
-            parameters: self.parameters,
         }
     }
 
+    /// Select a raw SQL expression, transitioning to SelectBuilderComplete
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder};
+    ///
+    /// let query = from("orders").select_raw("COUNT(*) FILTER (WHERE paid) AS paid_orders");
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT COUNT(*) FILTER (WHERE paid) AS paid_orders FROM orders"
+    /// );
+    /// ```
+    pub fn select_raw<R>(self, raw: R) -> SelectBuilderComplete
+    where
+        R: Into<Raw>,
+    {
+        self.select(ColumnSelector::raw(raw))
+    }
+
     /// Add a WHERE condition
     ///
     /// # Examples
@@ -297,14 +743,12 @@ impl SelectBuilderInitial {
     {
         let (column, operator, value) = condition.into_condition();
 
-        self.where_conditions.push(WhereCondition {
+        self.where_conditions.push(WhereNode::Condition(WhereCondition {
             column,
             operator,
             value,
             connector: WhereConnector::And,
-        });
-        self.parameters
-            .push(self.where_conditions.last().unwrap().value.clone());
+        }));
 
         self
     }
@@ -316,14 +760,12 @@ impl SelectBuilderInitial {
     {
         let (column, operator, value) = condition.into_condition();
 
-        self.where_conditions.push(WhereCondition {
+        self.where_conditions.push(WhereNode::Condition(WhereCondition {
             column,
             operator,
             value,
             connector: WhereConnector::Or,
-        });
-        self.parameters
-            .push(self.where_conditions.last().unwrap().value.clone());
+        }));
 
         self
     }
@@ -336,135 +778,294 @@ impl SelectBuilderInitial {
         self.where_(condition)
     }
 
-    /// Add a WHERE IN condition with a subquery
+    /// Add a parenthesized group of WHERE conditions joined with AND
     ///
     /// # Examples
     /// ```
     /// use archibald::from;
     ///
-    /// let subquery = from("orders").select("customer_id").where_(("status", "active"));
-    /// let query = from("customers").where_in("id", subquery);
+    /// // WHERE active = ? AND (role = ? OR role = ?)
+    /// let query = from("users")
+    ///     .where_(("active", true))
+    ///     .where_group(|q| q.where_(("role", "admin")).or_where(("role", "owner")));
     /// ```
-    pub fn where_in(mut self, column: &str, subquery: SelectBuilderComplete) -> Self {
-        self.subquery_conditions.push(SubqueryCondition {
-            column: column.to_string(),
-            operator: crate::Operator::IN,
-            subquery: Subquery::new(subquery),
-            connector: WhereConnector::And,
-        });
-        self
-    }
-
-    /// Add a WHERE EXISTS condition with a subquery
-    pub fn where_exists(mut self, subquery: SelectBuilderComplete) -> Self {
-        self.subquery_conditions.push(SubqueryCondition {
-            column: "".to_string(), // EXISTS doesn't need a column
-            operator: crate::Operator::EXISTS,
-            subquery: Subquery::new(subquery),
-            connector: WhereConnector::And,
-        });
-        self
+    pub fn where_group<F>(self, build: F) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        self.push_where_group(build, WhereConnector::And)
     }
 
-    /// Add a WHERE NOT IN condition with a subquery
-    pub fn where_not_in(mut self, column: &str, subquery: SelectBuilderComplete) -> Self {
-        self.subquery_conditions.push(SubqueryCondition {
-            column: column.to_string(),
-            operator: crate::Operator::NOT_IN,
-            subquery: Subquery::new(subquery),
-            connector: WhereConnector::And,
-        });
-        self
+    /// Add a parenthesized group of WHERE conditions joined with OR
+    pub fn or_where_group<F>(self, build: F) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        self.push_where_group(build, WhereConnector::Or)
     }
 
-    /// Add a WHERE NOT EXISTS condition with a subquery
-    pub fn where_not_exists(mut self, subquery: SelectBuilderComplete) -> Self {
-        self.subquery_conditions.push(SubqueryCondition {
-            column: "".to_string(), // NOT EXISTS doesn't need a column
-            operator: crate::Operator::NOT_EXISTS,
-            subquery: Subquery::new(subquery),
-            connector: WhereConnector::And,
-        });
+    fn push_where_group<F>(mut self, build: F, connector: WhereConnector) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        let node = build(WhereGroupBuilder::new()).into_node(connector);
+        self.where_conditions.push(node);
         self
     }
 
-    /// Add an INNER JOIN clause
+    /// Add a raw SQL WHERE condition
+    ///
+    /// The fragment is inserted as written, so an `OR` inside it should be
+    /// parenthesized.
     ///
     /// # Examples
     /// ```
-    /// use archibald::from;
+    /// use archibald::{from, raw, QueryBuilder};
     ///
     /// let query = from("users")
-    ///     .inner_join("posts", "users.id", "posts.user_id");
+    ///     .where_(("active", true))
+    ///     .where_raw(raw("created_at > NOW() - INTERVAL ? DAY", [30]))
+    ///     .select("id");
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT id FROM users WHERE active = ? AND created_at > NOW() - INTERVAL ? DAY"
+    /// );
     /// ```
-    pub fn inner_join(mut self, table: &str, left_column: &str, right_column: &str) -> Self {
-        self.join_clauses.push(JoinClause {
-            join_type: JoinType::Inner,
-            table: table.to_string(),
-            on_conditions: vec![super::common::JoinCondition {
-                left_column: left_column.to_string(),
-                operator: crate::Operator::EQ,
-                right_column: right_column.to_string(),
-                connector: JoinConnector::And,
-            }],
-        });
-        self
-    }
-
-    /// Add a LEFT JOIN clause
-    pub fn left_join(mut self, table: &str, left_column: &str, right_column: &str) -> Self {
-        self.join_clauses.push(JoinClause {
-            join_type: JoinType::Left,
-            table: table.to_string(),
-            on_conditions: vec![super::common::JoinCondition {
-                left_column: left_column.to_string(),
-                operator: crate::Operator::EQ,
-                right_column: right_column.to_string(),
-                connector: JoinConnector::And,
-            }],
+    pub fn where_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.where_conditions.push(WhereNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::And,
         });
         self
     }
 
-    /// Add a RIGHT JOIN clause
-    pub fn right_join(mut self, table: &str, left_column: &str, right_column: &str) -> Self {
-        self.join_clauses.push(JoinClause {
-            join_type: JoinType::Right,
-            table: table.to_string(),
-            on_conditions: vec![super::common::JoinCondition {
-                left_column: left_column.to_string(),
-                operator: crate::Operator::EQ,
-                right_column: right_column.to_string(),
-                connector: JoinConnector::And,
-            }],
+    /// Add a raw SQL WHERE condition joined with OR
+    pub fn or_where_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.where_conditions.push(WhereNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::Or,
         });
         self
     }
 
-    /// Add a FULL OUTER JOIN clause
-    pub fn full_outer_join(mut self, table: &str, left_column: &str, right_column: &str) -> Self {
-        self.join_clauses.push(JoinClause {
-            join_type: JoinType::Full,
-            table: table.to_string(),
-            on_conditions: vec![super::common::JoinCondition {
-                left_column: left_column.to_string(),
-                operator: crate::Operator::EQ,
-                right_column: right_column.to_string(),
-                connector: JoinConnector::And,
-            }],
-        });
+    fn push_subquery<Q>(
+        mut self,
+        column: String,
+        operator: crate::Operator,
+        subquery: Q,
+        connector: WhereConnector,
+    ) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        self.where_conditions
+            .push(WhereNode::subquery(column, operator, subquery.into(), connector));
         self
     }
 
-    /// Add a CROSS JOIN clause
-    pub fn cross_join(mut self, table: &str) -> Self {
-        self.join_clauses.push(JoinClause {
-            join_type: JoinType::Cross,
-            table: table.to_string(),
-            on_conditions: Vec::new(), // CROSS JOIN has no ON conditions
-        });
-        self
-    }
+    /// Add a WHERE IN condition with a subquery
+    ///
+    /// Subquery conditions sit in the WHERE tree like any other condition, so
+    /// they keep their position and connector.
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder};
+    ///
+    /// let subquery = from("orders").select("customer_id").where_(("status", "active"));
+    /// let query = from("customers")
+    ///     .where_in("id", subquery)
+    ///     .or_where(("vip", true))
+    ///     .select("*");
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT * FROM customers WHERE id IN (SELECT customer_id FROM orders WHERE status = ?) OR vip = ?"
+    /// );
+    /// ```
+    pub fn where_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        let column = column.into_column_ref();
+        self.push_subquery(column, crate::Operator::IN, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE IN condition with a subquery joined with OR
+    pub fn or_where_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        let column = column.into_column_ref();
+        self.push_subquery(column, crate::Operator::IN, subquery, WhereConnector::Or)
+    }
+
+    /// Add a WHERE EXISTS condition with a subquery
+    pub fn where_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        self.push_subquery(String::new(), crate::Operator::EXISTS, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE EXISTS condition with a subquery joined with OR
+    pub fn or_where_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        self.push_subquery(String::new(), crate::Operator::EXISTS, subquery, WhereConnector::Or)
+    }
+
+    /// Add a WHERE NOT IN condition with a subquery
+    pub fn where_not_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        let column = column.into_column_ref();
+        self.push_subquery(column, crate::Operator::NOT_IN, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE NOT IN condition with a subquery joined with OR
+    pub fn or_where_not_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        let column = column.into_column_ref();
+        self.push_subquery(column, crate::Operator::NOT_IN, subquery, WhereConnector::Or)
+    }
+
+    /// Add a WHERE NOT EXISTS condition with a subquery
+    pub fn where_not_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        let operator = crate::Operator::NOT_EXISTS;
+        self.push_subquery(String::new(), operator, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE NOT EXISTS condition with a subquery joined with OR
+    pub fn or_where_not_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        let operator = crate::Operator::NOT_EXISTS;
+        self.push_subquery(String::new(), operator, subquery, WhereConnector::Or)
+    }
+
+    /// Add an INNER JOIN clause
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::from;
+    ///
+    /// let query = from("users")
+    ///     .inner_join("posts", "users.id", "posts.user_id");
+    /// ```
+    pub fn inner_join<T, L, R>(mut self, table: T, left_column: L, right_column: R) -> Self
+    where
+        T: Into<TableRef>,
+        L: IntoColumnRef,
+        R: IntoColumnRef,
+    {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
+            join_type: JoinType::Inner,
+            table: table.into(),
+            on_conditions: vec![super::common::JoinCondition {
+                left_column: left_column.into_column_ref(),
+                operator: crate::Operator::EQ,
+                right: JoinOperand::Column(right_column.into_column_ref()),
+                connector: JoinConnector::And,
+            }],
+            using_columns: Vec::new(),
+        }));
+        self
+    }
+
+    /// Add a LEFT JOIN clause
+    pub fn left_join<T, L, R>(mut self, table: T, left_column: L, right_column: R) -> Self
+    where
+        T: Into<TableRef>,
+        L: IntoColumnRef,
+        R: IntoColumnRef,
+    {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
+            join_type: JoinType::Left,
+            table: table.into(),
+            on_conditions: vec![super::common::JoinCondition {
+                left_column: left_column.into_column_ref(),
+                operator: crate::Operator::EQ,
+                right: JoinOperand::Column(right_column.into_column_ref()),
+                connector: JoinConnector::And,
+            }],
+            using_columns: Vec::new(),
+        }));
+        self
+    }
+
+    /// Add a RIGHT JOIN clause
+    pub fn right_join<T, L, R>(mut self, table: T, left_column: L, right_column: R) -> Self
+    where
+        T: Into<TableRef>,
+        L: IntoColumnRef,
+        R: IntoColumnRef,
+    {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
+            join_type: JoinType::Right,
+            table: table.into(),
+            on_conditions: vec![super::common::JoinCondition {
+                left_column: left_column.into_column_ref(),
+                operator: crate::Operator::EQ,
+                right: JoinOperand::Column(right_column.into_column_ref()),
+                connector: JoinConnector::And,
+            }],
+            using_columns: Vec::new(),
+        }));
+        self
+    }
+
+    /// Add a FULL OUTER JOIN clause
+    pub fn full_outer_join<T, L, R>(mut self, table: T, left_column: L, right_column: R) -> Self
+    where
+        T: Into<TableRef>,
+        L: IntoColumnRef,
+        R: IntoColumnRef,
+    {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
+            join_type: JoinType::Full,
+            table: table.into(),
+            on_conditions: vec![super::common::JoinCondition {
+                left_column: left_column.into_column_ref(),
+                operator: crate::Operator::EQ,
+                right: JoinOperand::Column(right_column.into_column_ref()),
+                connector: JoinConnector::And,
+            }],
+            using_columns: Vec::new(),
+        }));
+        self
+    }
+
+    /// Add a CROSS JOIN clause
+    pub fn cross_join<T>(mut self, table: T) -> Self
+    where
+        T: Into<TableRef>,
+    {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
+            join_type: JoinType::Cross,
+            table: table.into(),
+            on_conditions: Vec::new(), // CROSS JOIN has no ON conditions
+            using_columns: Vec::new(),
+        }));
+        self
+    }
 
     /// Generic JOIN method with custom join type and operator
     ///
@@ -475,27 +1076,111 @@ impl SelectBuilderInitial {
     /// let query = from("users")
     ///     .join(JoinType::Left, "profiles", "users.id", op::EQ, "profiles.user_id");
     /// ```
-    pub fn join<O>(
+    pub fn join<T, L, O, R>(
         mut self,
         join_type: JoinType,
-        table: &str,
-        left_col: &str,
+        table: T,
+        left_col: L,
         operator: O,
-        right_col: &str,
+        right_col: R,
     ) -> Self
     where
+        T: Into<TableRef>,
+        L: IntoColumnRef,
         O: IntoOperator,
+        R: IntoColumnRef,
     {
-        self.join_clauses.push(JoinClause {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
             join_type,
-            table: table.to_string(),
+            table: table.into(),
             on_conditions: vec![super::common::JoinCondition {
-                left_column: left_col.to_string(),
+                left_column: left_col.into_column_ref(),
                 operator: operator.into_operator(),
-                right_column: right_col.to_string(),
+                right: JoinOperand::Column(right_col.into_column_ref()),
                 connector: JoinConnector::And,
             }],
-        });
+            using_columns: Vec::new(),
+        }));
+        self
+    }
+
+    /// Add a JOIN built from several conditions
+    ///
+    /// The closure receives a [`JoinBuilder`] for ON conditions between
+    /// columns, conditions on bound values and USING columns. Values are bound
+    /// in the order the conditions are added, before any WHERE values.
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, op, QueryBuilder, Value};
+    ///
+    /// let query = from("users")
+    ///     .join_with("orders", |j| {
+    ///         j.on("users.id", "=", "orders.user_id")
+    ///             .and_on_val("orders.status", "paid")
+    ///             .and_on_where(("orders.total", op::GT, 100))
+    ///     })
+    ///     .where_(("users.active", true))
+    ///     .select("users.id");
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT users.id FROM users INNER JOIN orders ON users.id = orders.user_id AND orders.status = ? AND orders.total > ? WHERE users.active = ?"
+    /// );
+    /// assert_eq!(
+    ///     query.parameters().unwrap(),
+    ///     vec![Value::from("paid"), Value::I32(100), Value::Bool(true)]
+    /// );
+    /// ```
+    pub fn join_with<T, F>(mut self, table: T, build: F) -> Self
+    where
+        T: Into<TableRef>,
+        F: FnOnce(JoinBuilder) -> JoinBuilder,
+    {
+        let join = build(JoinBuilder::new(JoinType::Inner, table.into())).build();
+        self.join_clauses.push(JoinNode::Clause(join));
+        self
+    }
+
+    /// Add a LEFT JOIN built from several conditions
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder};
+    ///
+    /// let query = from("users")
+    ///     .left_join_with("profiles", |j| j.using("user_id"))
+    ///     .select("*");
+    /// assert_eq!(query.to_sql().unwrap(), "SELECT * FROM users LEFT JOIN profiles USING (user_id)");
+    /// ```
+    pub fn left_join_with<T, F>(mut self, table: T, build: F) -> Self
+    where
+        T: Into<TableRef>,
+        F: FnOnce(JoinBuilder) -> JoinBuilder,
+    {
+        let join = build(JoinBuilder::new(JoinType::Left, table.into())).build();
+        self.join_clauses.push(JoinNode::Clause(join));
+        self
+    }
+
+    /// Add a raw SQL JOIN, written out in full
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, raw, QueryBuilder};
+    ///
+    /// let query = from("users")
+    ///     .join_raw(raw("LEFT JOIN orders o ON o.user_id = users.id AND o.total > ?", [100]))
+    ///     .select("users.id");
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT users.id FROM users LEFT JOIN orders o ON o.user_id = users.id AND o.total > ?"
+    /// );
+    /// ```
+    pub fn join_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.join_clauses.push(JoinNode::Raw(raw.into()));
         self
     }
 
@@ -536,13 +1221,12 @@ impl SelectBuilderInitial {
         C: IntoCondition,
     {
         let (column, operator, value) = condition.into_condition();
-        self.having_conditions.push(HavingCondition {
+        self.having_conditions.push(HavingNode::Condition(HavingCondition {
             column_or_function: column,
             operator,
-            value: value.clone(),
+            value,
             connector: WhereConnector::And,
-        });
-        self.parameters.push(value);
+        }));
         self
     }
 
@@ -552,13 +1236,12 @@ impl SelectBuilderInitial {
         C: IntoCondition,
     {
         let (column, operator, value) = condition.into_condition();
-        self.having_conditions.push(HavingCondition {
+        self.having_conditions.push(HavingNode::Condition(HavingCondition {
             column_or_function: column,
             operator,
-            value: value.clone(),
+            value,
             connector: WhereConnector::And,
-        });
-        self.parameters.push(value);
+        }));
         self
     }
 
@@ -568,13 +1251,50 @@ impl SelectBuilderInitial {
         C: IntoCondition,
     {
         let (column, operator, value) = condition.into_condition();
-        self.having_conditions.push(HavingCondition {
+        self.having_conditions.push(HavingNode::Condition(HavingCondition {
             column_or_function: column,
             operator,
-            value: value.clone(),
+            value,
+            connector: WhereConnector::Or,
+        }));
+        self
+    }
+
+    /// Add a raw SQL HAVING condition (requires GROUP BY)
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, raw, QueryBuilder};
+    ///
+    /// let query = from("orders")
+    ///     .group_by("customer_id")
+    ///     .having_raw(raw("SUM(total) > ?", [1000]))
+    ///     .select("customer_id");
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT customer_id FROM orders GROUP BY customer_id HAVING SUM(total) > ?"
+    /// );
+    /// ```
+    pub fn having_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.having_conditions.push(HavingNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::And,
+        });
+        self
+    }
+
+    /// Add a raw SQL HAVING condition joined with OR (requires GROUP BY)
+    pub fn or_having_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.having_conditions.push(HavingNode::Raw {
+            raw: raw.into(),
             connector: WhereConnector::Or,
         });
-        self.parameters.push(value);
         self
     }
 
@@ -586,11 +1306,14 @@ impl SelectBuilderInitial {
     ///
     /// let query = from("users").order_by("name", SortDirection::Asc);
     /// ```
-    pub fn order_by(mut self, column: &str, direction: SortDirection) -> Self {
-        self.order_by_clauses.push(OrderByClause {
-            column: column.to_string(),
+    pub fn order_by<C>(mut self, column: C, direction: SortDirection) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        self.order_by_clauses.push(OrderByNode::Clause(OrderByClause {
+            column: column.into_column_ref(),
             direction,
-        });
+        }));
         self
     }
 
@@ -602,11 +1325,14 @@ impl SelectBuilderInitial {
     ///
     /// let query = from("users").order_by_asc("created_at");
     /// ```
-    pub fn order_by_asc(mut self, column: &str) -> Self {
-        self.order_by_clauses.push(OrderByClause {
-            column: column.to_string(),
+    pub fn order_by_asc<C>(mut self, column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        self.order_by_clauses.push(OrderByNode::Clause(OrderByClause {
+            column: column.into_column_ref(),
             direction: SortDirection::Asc,
-        });
+        }));
         self
     }
 
@@ -618,11 +1344,34 @@ impl SelectBuilderInitial {
     ///
     /// let query = from("users").order_by_desc("created_at");
     /// ```
-    pub fn order_by_desc(mut self, column: &str) -> Self {
-        self.order_by_clauses.push(OrderByClause {
-            column: column.to_string(),
+    pub fn order_by_desc<C>(mut self, column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        self.order_by_clauses.push(OrderByNode::Clause(OrderByClause {
+            column: column.into_column_ref(),
             direction: SortDirection::Desc,
-        });
+        }));
+        self
+    }
+
+    /// Add a raw SQL ORDER BY expression, including its direction
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder};
+    ///
+    /// let query = from("users").order_by_raw("LENGTH(name) DESC, name").select("name");
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT name FROM users ORDER BY LENGTH(name) DESC, name"
+    /// );
+    /// ```
+    pub fn order_by_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.order_by_clauses.push(OrderByNode::Raw(raw.into()));
         self
     }
 
@@ -640,6 +1389,155 @@ impl SelectBuilderInitial {
 }
 
 impl SelectBuilderComplete {
+    /// Add a common table expression to a WITH clause before the query
+    pub fn with<Q>(mut self, name: &str, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::new(name, query));
+        self
+    }
+
+    /// Add a recursive common table expression, rendering `WITH RECURSIVE`
+    pub fn with_recursive<C, Q>(mut self, name: &str, columns: C, query: Q) -> Self
+    where
+        C: IntoColumns,
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::recursive(name, columns, query));
+        self
+    }
+
+    /// Combine this query with another one, removing duplicate rows (UNION)
+    ///
+    /// The other query can be a select builder, another compound query or raw
+    /// SQL. See [`CompoundSelectBuilder`] for ordering and limiting the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder};
+    ///
+    /// let query = from("customers").select("email").union(from("leads").select("email"));
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT email FROM customers UNION SELECT email FROM leads"
+    /// );
+    /// ```
+    pub fn union<Q>(self, query: Q) -> CompoundSelectBuilder
+    where
+        Q: Into<SelectQuery>,
+    {
+        CompoundSelectBuilder::new(self, SetOperator::Union, query)
+    }
+
+    /// Combine this query with another one, keeping duplicate rows (UNION ALL)
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder};
+    ///
+    /// let query = from("customers").select("email").union_all(from("leads").select("email"));
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT email FROM customers UNION ALL SELECT email FROM leads"
+    /// );
+    /// ```
+    pub fn union_all<Q>(self, query: Q) -> CompoundSelectBuilder
+    where
+        Q: Into<SelectQuery>,
+    {
+        CompoundSelectBuilder::new(self, SetOperator::UnionAll, query)
+    }
+
+    /// Keep only the rows also returned by another query (INTERSECT)
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder};
+    ///
+    /// let query = from("customers").select("email").intersect(from("leads").select("email"));
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT email FROM customers INTERSECT SELECT email FROM leads"
+    /// );
+    /// ```
+    pub fn intersect<Q>(self, query: Q) -> CompoundSelectBuilder
+    where
+        Q: Into<SelectQuery>,
+    {
+        CompoundSelectBuilder::new(self, SetOperator::Intersect, query)
+    }
+
+    /// Remove the rows returned by another query (EXCEPT)
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder};
+    ///
+    /// let query = from("customers").select("email").except(from("leads").select("email"));
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT email FROM customers EXCEPT SELECT email FROM leads"
+    /// );
+    /// ```
+    pub fn except<Q>(self, query: Q) -> CompoundSelectBuilder
+    where
+        Q: Into<SelectQuery>,
+    {
+        CompoundSelectBuilder::new(self, SetOperator::Except, query)
+    }
+
+    /// Add a raw SQL expression to the selected columns
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, raw, QueryBuilder};
+    ///
+    /// let query = from("products")
+    ///     .select(("id", "price"))
+    ///     .select_raw(raw("price * ? AS price_with_tax", [1.2]));
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT id, price, price * ? AS price_with_tax FROM products"
+    /// );
+    /// ```
+    pub fn select_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.selected_columns.push(ColumnSelector::raw(raw));
+        self
+    }
+
+    /// Define a named window in a WINDOW clause
+    ///
+    /// Selectors refer to it with [`ColumnSelector::over_named`], or extend it
+    /// with [`Window::based_on`].
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, ColumnSelector, QueryBuilder};
+    ///
+    /// let query = from("scores")
+    ///     .select((
+    ///         "player",
+    ///         ColumnSelector::rank().over_named("by_points").as_alias("rank"),
+    ///         ColumnSelector::lag("points", 1).over_named("by_points").as_alias("next_best"),
+    ///     ))
+    ///     .window("by_points", |w| w.partition_by("game").order_by_desc("points"));
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "SELECT player, RANK() OVER by_points AS rank, LAG(points, 1) OVER by_points AS next_best FROM scores WINDOW by_points AS (PARTITION BY game ORDER BY points DESC)"
+    /// );
+    /// ```
+    pub fn window<F>(mut self, name: &str, build: F) -> Self
+    where
+        F: FnOnce(Window) -> Window,
+    {
+        self.windows.push((name.to_string(), build(Window::new())));
+        self
+    }
+
     /// Add a WHERE condition
     pub fn where_<C>(mut self, condition: C) -> Self
     where
@@ -647,14 +1545,12 @@ impl SelectBuilderComplete {
     {
         let (column, operator, value) = condition.into_condition();
 
-        self.where_conditions.push(WhereCondition {
+        self.where_conditions.push(WhereNode::Condition(WhereCondition {
             column,
             operator,
             value,
             connector: WhereConnector::And,
-        });
-        self.parameters
-            .push(self.where_conditions.last().unwrap().value.clone());
+        }));
 
         self
     }
@@ -666,14 +1562,12 @@ impl SelectBuilderComplete {
     {
         let (column, operator, value) = condition.into_condition();
 
-        self.where_conditions.push(WhereCondition {
+        self.where_conditions.push(WhereNode::Condition(WhereCondition {
             column,
             operator,
             value,
             connector: WhereConnector::Or,
-        });
-        self.parameters
-            .push(self.where_conditions.last().unwrap().value.clone());
+        }));
 
         self
     }
@@ -686,25 +1580,92 @@ impl SelectBuilderComplete {
         self.where_(condition)
     }
 
+    /// Add a parenthesized group of WHERE conditions joined with AND
+    pub fn where_group<F>(self, build: F) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        self.push_where_group(build, WhereConnector::And)
+    }
+
+    /// Add a parenthesized group of WHERE conditions joined with OR
+    pub fn or_where_group<F>(self, build: F) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        self.push_where_group(build, WhereConnector::Or)
+    }
+
+    fn push_where_group<F>(mut self, build: F, connector: WhereConnector) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        let node = build(WhereGroupBuilder::new()).into_node(connector);
+        self.where_conditions.push(node);
+        self
+    }
+
+    /// Add a raw SQL WHERE condition
+    pub fn where_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.where_conditions.push(WhereNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::And,
+        });
+        self
+    }
+
+    /// Add a raw SQL WHERE condition joined with OR
+    pub fn or_where_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.where_conditions.push(WhereNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::Or,
+        });
+        self
+    }
+
     /// Add an ORDER BY clause
-    pub fn order_by(mut self, column: &str, direction: SortDirection) -> Self {
-        self.order_by_clauses.push(OrderByClause {
-            column: column.to_string(),
+    pub fn order_by<C>(mut self, column: C, direction: SortDirection) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        self.order_by_clauses.push(OrderByNode::Clause(OrderByClause {
+            column: column.into_column_ref(),
             direction,
-        });
+        }));
         self
     }
 
     /// Add an ORDER BY ASC clause (convenience method)
-    pub fn order_by_asc(self, column: &str) -> Self {
+    pub fn order_by_asc<C>(self, column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
         self.order_by(column, SortDirection::Asc)
     }
 
     /// Add an ORDER BY DESC clause (convenience method)
-    pub fn order_by_desc(self, column: &str) -> Self {
+    pub fn order_by_desc<C>(self, column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
         self.order_by(column, SortDirection::Desc)
     }
 
+    /// Add a raw SQL ORDER BY expression, including its direction
+    pub fn order_by_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.order_by_clauses.push(OrderByNode::Raw(raw.into()));
+        self
+    }
+
     /// Add a LIMIT clause
     pub fn limit(mut self, count: u64) -> Self {
         self.limit_value = Some(count);
@@ -730,146 +1691,252 @@ impl SelectBuilderComplete {
         self
     }
 
-    /// Add a WHERE IN condition with a subquery
-    pub fn where_in(mut self, column: &str, subquery: SelectBuilderComplete) -> Self {
-        self.subquery_conditions.push(SubqueryCondition {
-            column: column.to_string(),
-            operator: crate::Operator::IN,
-            subquery: Subquery::new(subquery),
-            connector: WhereConnector::And,
-        });
-        // Parameters from subqueries are handled inside the Subquery struct
+    fn push_subquery<Q>(
+        mut self,
+        column: String,
+        operator: crate::Operator,
+        subquery: Q,
+        connector: WhereConnector,
+    ) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        self.where_conditions
+            .push(WhereNode::subquery(column, operator, subquery.into(), connector));
         self
     }
 
+    /// Add a WHERE IN condition with a subquery
+    pub fn where_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        let column = column.into_column_ref();
+        self.push_subquery(column, crate::Operator::IN, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE IN condition with a subquery joined with OR
+    pub fn or_where_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        let column = column.into_column_ref();
+        self.push_subquery(column, crate::Operator::IN, subquery, WhereConnector::Or)
+    }
+
     /// Add a WHERE EXISTS condition with a subquery
-    pub fn where_exists(mut self, subquery: SelectBuilderComplete) -> Self {
-        self.subquery_conditions.push(SubqueryCondition {
-            column: "".to_string(), // EXISTS doesn't need a column
-            operator: crate::Operator::EXISTS,
-            subquery: Subquery::new(subquery),
-            connector: WhereConnector::And,
-        });
-        // Parameters from subqueries are handled inside the Subquery struct
-        self
+    pub fn where_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        self.push_subquery(String::new(), crate::Operator::EXISTS, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE EXISTS condition with a subquery joined with OR
+    pub fn or_where_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        self.push_subquery(String::new(), crate::Operator::EXISTS, subquery, WhereConnector::Or)
     }
 
     /// Add a WHERE NOT IN condition with a subquery
-    pub fn where_not_in(mut self, column: &str, subquery: SelectBuilderComplete) -> Self {
-        self.subquery_conditions.push(SubqueryCondition {
-            column: column.to_string(),
-            operator: crate::Operator::NOT_IN,
-            subquery: Subquery::new(subquery),
-            connector: WhereConnector::And,
-        });
-        // Parameters from subqueries are handled inside the Subquery struct
-        self
+    pub fn where_not_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        let column = column.into_column_ref();
+        self.push_subquery(column, crate::Operator::NOT_IN, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE NOT IN condition with a subquery joined with OR
+    pub fn or_where_not_in<C, Q>(self, column: C, subquery: Q) -> Self
+    where
+        C: IntoColumnRef,
+        Q: Into<Subquery>,
+    {
+        let column = column.into_column_ref();
+        self.push_subquery(column, crate::Operator::NOT_IN, subquery, WhereConnector::Or)
     }
 
     /// Add a WHERE NOT EXISTS condition with a subquery
-    pub fn where_not_exists(mut self, subquery: SelectBuilderComplete) -> Self {
-        self.subquery_conditions.push(SubqueryCondition {
-            column: "".to_string(), // NOT EXISTS doesn't need a column
-            operator: crate::Operator::NOT_EXISTS,
-            subquery: Subquery::new(subquery),
-            connector: WhereConnector::And,
-        });
-        // Parameters from subqueries are handled inside the Subquery struct
-        self
+    pub fn where_not_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        let operator = crate::Operator::NOT_EXISTS;
+        self.push_subquery(String::new(), operator, subquery, WhereConnector::And)
+    }
+
+    /// Add a WHERE NOT EXISTS condition with a subquery joined with OR
+    pub fn or_where_not_exists<Q>(self, subquery: Q) -> Self
+    where
+        Q: Into<Subquery>,
+    {
+        let operator = crate::Operator::NOT_EXISTS;
+        self.push_subquery(String::new(), operator, subquery, WhereConnector::Or)
     }
 
     /// Add an INNER JOIN clause
-    pub fn inner_join(mut self, table: &str, left_column: &str, right_column: &str) -> Self {
-        self.join_clauses.push(JoinClause {
+    pub fn inner_join<T, L, R>(mut self, table: T, left_column: L, right_column: R) -> Self
+    where
+        T: Into<TableRef>,
+        L: IntoColumnRef,
+        R: IntoColumnRef,
+    {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
             join_type: JoinType::Inner,
-            table: table.to_string(),
+            table: table.into(),
             on_conditions: vec![super::common::JoinCondition {
-                left_column: left_column.to_string(),
+                left_column: left_column.into_column_ref(),
                 operator: crate::Operator::EQ,
-                right_column: right_column.to_string(),
+                right: JoinOperand::Column(right_column.into_column_ref()),
                 connector: JoinConnector::And,
             }],
-        });
+            using_columns: Vec::new(),
+        }));
         self
     }
 
     /// Add a LEFT JOIN clause
-    pub fn left_join(mut self, table: &str, left_column: &str, right_column: &str) -> Self {
-        self.join_clauses.push(JoinClause {
+    pub fn left_join<T, L, R>(mut self, table: T, left_column: L, right_column: R) -> Self
+    where
+        T: Into<TableRef>,
+        L: IntoColumnRef,
+        R: IntoColumnRef,
+    {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
             join_type: JoinType::Left,
-            table: table.to_string(),
+            table: table.into(),
             on_conditions: vec![super::common::JoinCondition {
-                left_column: left_column.to_string(),
+                left_column: left_column.into_column_ref(),
                 operator: crate::Operator::EQ,
-                right_column: right_column.to_string(),
+                right: JoinOperand::Column(right_column.into_column_ref()),
                 connector: JoinConnector::And,
             }],
-        });
+            using_columns: Vec::new(),
+        }));
         self
     }
 
     /// Add a RIGHT JOIN clause
-    pub fn right_join(mut self, table: &str, left_column: &str, right_column: &str) -> Self {
-        self.join_clauses.push(JoinClause {
+    pub fn right_join<T, L, R>(mut self, table: T, left_column: L, right_column: R) -> Self
+    where
+        T: Into<TableRef>,
+        L: IntoColumnRef,
+        R: IntoColumnRef,
+    {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
             join_type: JoinType::Right,
-            table: table.to_string(),
+            table: table.into(),
             on_conditions: vec![super::common::JoinCondition {
-                left_column: left_column.to_string(),
+                left_column: left_column.into_column_ref(),
                 operator: crate::Operator::EQ,
-                right_column: right_column.to_string(),
+                right: JoinOperand::Column(right_column.into_column_ref()),
                 connector: JoinConnector::And,
             }],
-        });
+            using_columns: Vec::new(),
+        }));
         self
     }
 
     /// Add a FULL OUTER JOIN clause
-    pub fn full_outer_join(mut self, table: &str, left_column: &str, right_column: &str) -> Self {
-        self.join_clauses.push(JoinClause {
+    pub fn full_outer_join<T, L, R>(mut self, table: T, left_column: L, right_column: R) -> Self
+    where
+        T: Into<TableRef>,
+        L: IntoColumnRef,
+        R: IntoColumnRef,
+    {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
             join_type: JoinType::Full,
-            table: table.to_string(),
+            table: table.into(),
             on_conditions: vec![super::common::JoinCondition {
-                left_column: left_column.to_string(),
+                left_column: left_column.into_column_ref(),
                 operator: crate::Operator::EQ,
-                right_column: right_column.to_string(),
+                right: JoinOperand::Column(right_column.into_column_ref()),
                 connector: JoinConnector::And,
             }],
-        });
+            using_columns: Vec::new(),
+        }));
         self
     }
 
     /// Add a CROSS JOIN clause
-    pub fn cross_join(mut self, table: &str) -> Self {
-        self.join_clauses.push(JoinClause {
+    pub fn cross_join<T>(mut self, table: T) -> Self
+    where
+        T: Into<TableRef>,
+    {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
             join_type: JoinType::Cross,
-            table: table.to_string(),
+            table: table.into(),
             on_conditions: Vec::new(), // CROSS JOIN has no ON conditions
-        });
+            using_columns: Vec::new(),
+        }));
         self
     }
 
     /// Generic JOIN method with custom join type and operator
-    pub fn join<O>(
+    pub fn join<T, L, O, R>(
         mut self,
         join_type: JoinType,
-        table: &str,
-        left_col: &str,
+        table: T,
+        left_col: L,
         operator: O,
-        right_col: &str,
+        right_col: R,
     ) -> Self
     where
+        T: Into<TableRef>,
+        L: IntoColumnRef,
         O: IntoOperator,
+        R: IntoColumnRef,
     {
-        self.join_clauses.push(JoinClause {
+        self.join_clauses.push(JoinNode::Clause(JoinClause {
             join_type,
-            table: table.to_string(),
+            table: table.into(),
             on_conditions: vec![super::common::JoinCondition {
-                left_column: left_col.to_string(),
+                left_column: left_col.into_column_ref(),
                 operator: operator.into_operator(),
-                right_column: right_col.to_string(),
+                right: JoinOperand::Column(right_col.into_column_ref()),
                 connector: JoinConnector::And,
             }],
-        });
+            using_columns: Vec::new(),
+        }));
+        self
+    }
+
+    /// Add a JOIN built from several conditions
+    ///
+    /// See [`SelectBuilderInitial::join_with`].
+    pub fn join_with<T, F>(mut self, table: T, build: F) -> Self
+    where
+        T: Into<TableRef>,
+        F: FnOnce(JoinBuilder) -> JoinBuilder,
+    {
+        let join = build(JoinBuilder::new(JoinType::Inner, table.into())).build();
+        self.join_clauses.push(JoinNode::Clause(join));
+        self
+    }
+
+    /// Add a LEFT JOIN built from several conditions
+    pub fn left_join_with<T, F>(mut self, table: T, build: F) -> Self
+    where
+        T: Into<TableRef>,
+        F: FnOnce(JoinBuilder) -> JoinBuilder,
+    {
+        let join = build(JoinBuilder::new(JoinType::Left, table.into())).build();
+        self.join_clauses.push(JoinNode::Clause(join));
+        self
+    }
+
+    /// Add a raw SQL JOIN, written out in full
+    pub fn join_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.join_clauses.push(JoinNode::Raw(raw.into()));
         self
     }
 
@@ -890,13 +1957,12 @@ impl SelectBuilderComplete {
         C: IntoCondition,
     {
         let (column, operator, value) = condition.into_condition();
-        self.having_conditions.push(HavingCondition {
+        self.having_conditions.push(HavingNode::Condition(HavingCondition {
             column_or_function: column,
             operator,
-            value: value.clone(),
+            value,
             connector: WhereConnector::And,
-        });
-        self.parameters.push(value);
+        }));
         self
     }
 
@@ -906,13 +1972,12 @@ impl SelectBuilderComplete {
         C: IntoCondition,
     {
         let (column, operator, value) = condition.into_condition();
-        self.having_conditions.push(HavingCondition {
+        self.having_conditions.push(HavingNode::Condition(HavingCondition {
             column_or_function: column,
             operator,
-            value: value.clone(),
+            value,
             connector: WhereConnector::And,
-        });
-        self.parameters.push(value);
+        }));
         self
     }
 
@@ -922,45 +1987,57 @@ impl SelectBuilderComplete {
         C: IntoCondition,
     {
         let (column, operator, value) = condition.into_condition();
-        self.having_conditions.push(HavingCondition {
+        self.having_conditions.push(HavingNode::Condition(HavingCondition {
             column_or_function: column,
             operator,
-            value: value.clone(),
+            value,
+            connector: WhereConnector::Or,
+        }));
+        self
+    }
+
+    /// Add a raw SQL HAVING condition (requires GROUP BY)
+    pub fn having_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.having_conditions.push(HavingNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::And,
+        });
+        self
+    }
+
+    /// Add a raw SQL HAVING condition joined with OR (requires GROUP BY)
+    pub fn or_having_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.having_conditions.push(HavingNode::Raw {
+            raw: raw.into(),
             connector: WhereConnector::Or,
         });
-        self.parameters.push(value);
         self
     }
 }
 
 impl QueryBuilder for SelectBuilderInitial {
-    fn to_sql(&self) -> Result<String> {
+    fn to_sql_with_params(&self, _dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
         Err(Error::invalid_query(
             "SELECT requires columns to be specified with .select()",
         ))
     }
 
-    fn parameters(&self) -> &[Value] {
-        &[]
-    }
-
     fn clone_builder(&self) -> Self {
         self.clone()
     }
 }
 
-impl QueryBuilder for SelectBuilderComplete {
-    fn to_sql(&self) -> Result<String> {
-        // Validate all operators before generating SQL
-        for condition in &self.where_conditions {
-            condition.operator.validate()?;
-        }
-
-        for condition in &self.subquery_conditions {
-            condition.operator.validate()?;
-        }
-
-        let mut sql = String::new();
+impl SelectBuilderComplete {
+    /// Render this query into SQL using the given render context
+    pub(crate) fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        // WITH clause
+        let mut sql = render_with(&self.ctes, ctx)?;
 
         // SELECT clause
         sql.push_str("SELECT ");
@@ -971,181 +2048,95 @@ impl QueryBuilder for SelectBuilderComplete {
 
         // Columns
         if self.selected_columns.is_empty() {
-            sql.push_str("*");
+            sql.push('*');
         } else {
             let mut column_parts = Vec::new();
             for col in &self.selected_columns {
-                let part = match col {
-                    ColumnSelector::Column { name, alias } => {
-                        if let Some(alias) = alias {
-                            format!("{} AS {}", name, alias)
-                        } else {
-                            name.clone()
-                        }
-                    }
-                    ColumnSelector::Aggregate {
-                        function,
-                        column,
-                        alias,
-                    } => {
-                        let func_sql = match function {
-                            AggregateFunction::CountDistinct => {
-                                format!("{}({}))", function, column)
-                            }
-                            _ => format!("{}({})", function, column),
-                        };
-                        if let Some(alias) = alias {
-                            format!("{} AS {}", func_sql, alias)
-                        } else {
-                            func_sql
-                        }
-                    }
-                    ColumnSelector::CountAll { alias } => {
-                        let count_sql = "COUNT(*)".to_string();
-                        if let Some(alias) = alias {
-                            format!("{} AS {}", count_sql, alias)
-                        } else {
-                            count_sql
-                        }
-                    }
-                    ColumnSelector::SubqueryColumn { subquery, alias } => {
-                        let subquery_sql = subquery.to_sql()?;
-                        if let Some(alias) = alias {
-                            format!("{} AS {}", subquery_sql, alias)
-                        } else {
-                            subquery_sql
-                        }
-                    }
-                };
-                column_parts.push(part);
+                column_parts.push(col.render(ctx)?);
             }
             sql.push_str(&column_parts.join(", "));
         }
 
         // FROM clause
         sql.push_str(" FROM ");
-        sql.push_str(&self.table_name);
+        sql.push_str(&self.table.render(ctx)?);
 
         // JOIN clauses
         for join in &self.join_clauses {
             sql.push(' ');
-            sql.push_str(&join.join_type.to_string());
-            sql.push_str(" JOIN ");
-            sql.push_str(&join.table);
-
-            if !join.on_conditions.is_empty() {
-                sql.push_str(" ON ");
-
-                for (i, condition) in join.on_conditions.iter().enumerate() {
-                    if i > 0 {
-                        match condition.connector {
-                            JoinConnector::And => sql.push_str(" AND "),
-                            JoinConnector::Or => sql.push_str(" OR "),
-                        }
-                    }
-
-                    sql.push_str(&condition.left_column);
-                    sql.push(' ');
-                    sql.push_str(condition.operator.as_str());
-                    sql.push(' ');
-                    sql.push_str(&condition.right_column);
-                }
-            }
+            sql.push_str(&match join {
+                JoinNode::Clause(join) => join.render(ctx)?,
+                JoinNode::Raw(raw) => raw.render(ctx)?,
+            });
         }
 
         // WHERE clause
-        if !self.where_conditions.is_empty() || !self.subquery_conditions.is_empty() {
+        let where_sql = render_where_nodes(&self.where_conditions, ctx)?;
+        if !where_sql.is_empty() {
             sql.push_str(" WHERE ");
-
-            let mut conditions_added = 0;
-
-            // Regular WHERE conditions
-            for (i, condition) in self.where_conditions.iter().enumerate() {
-                if conditions_added > 0 || i > 0 {
-                    match condition.connector {
-                        WhereConnector::And => sql.push_str(" AND "),
-                        WhereConnector::Or => sql.push_str(" OR "),
-                    }
-                }
-
-                sql.push_str(&condition.column);
-                sql.push(' ');
-                sql.push_str(condition.operator.as_str());
-                sql.push_str(" ?");
-                conditions_added += 1;
-            }
-
-            // Subquery conditions
-            for condition in &self.subquery_conditions {
-                if conditions_added > 0 {
-                    match condition.connector {
-                        WhereConnector::And => sql.push_str(" AND "),
-                        WhereConnector::Or => sql.push_str(" OR "),
-                    }
-                }
-
-                sql.push_str(&condition.column);
-                if !condition.column.is_empty() {
-                    sql.push(' ');
-                }
-                sql.push_str(condition.operator.as_str());
-                sql.push(' ');
-                sql.push_str(&condition.subquery.to_sql()?);
-                conditions_added += 1;
-            }
+            sql.push_str(&where_sql);
         }
 
         // GROUP BY clause
         if let Some(group_by) = &self.group_by_clause {
             sql.push_str(" GROUP BY ");
-            sql.push_str(&group_by.columns.join(", "));
+            let group_parts: Vec<String> =
+                group_by.columns.iter().map(|column| ctx.ident(column)).collect();
+            sql.push_str(&group_parts.join(", "));
 
             // HAVING clause
             if !self.having_conditions.is_empty() {
                 sql.push_str(" HAVING ");
 
-                for (i, condition) in self.having_conditions.iter().enumerate() {
+                for (i, node) in self.having_conditions.iter().enumerate() {
                     if i > 0 {
-                        match condition.connector {
+                        match node.connector() {
                             WhereConnector::And => sql.push_str(" AND "),
                             WhereConnector::Or => sql.push_str(" OR "),
                         }
                     }
 
-                    sql.push_str(&condition.column_or_function);
-                    sql.push(' ');
-                    sql.push_str(condition.operator.as_str());
-                    sql.push_str(" ?");
+                    let condition = match node {
+                        HavingNode::Condition(condition) => condition,
+                        HavingNode::Raw { raw, .. } => {
+                            sql.push_str(&raw.render(ctx)?);
+                            continue;
+                        }
+                    };
+                    sql.push_str(&render_value_comparison(
+                        &condition.column_or_function,
+                        &condition.operator,
+                        &condition.value,
+                        ctx,
+                    )?);
                 }
             }
         }
 
+        // WINDOW clause
+        if !self.windows.is_empty() {
+            sql.push_str(" WINDOW ");
+            sql.push_str(&render_windows(&self.windows, ctx)?);
+        }
+
         // ORDER BY clause
         if !self.order_by_clauses.is_empty() {
             sql.push_str(" ORDER BY ");
-            let order_parts: Vec<String> = self
-                .order_by_clauses
-                .iter()
-                .map(|clause| format!("{} {}", clause.column, clause.direction))
-                .collect();
-            sql.push_str(&order_parts.join(", "));
+            sql.push_str(&render_order_by(&self.order_by_clauses, ctx)?);
         }
 
-        // LIMIT clause
-        if let Some(limit) = self.limit_value {
-            sql.push_str(&format!(" LIMIT {}", limit));
-        }
-
-        // OFFSET clause
-        if let Some(offset) = self.offset_value {
-            sql.push_str(&format!(" OFFSET {}", offset));
-        }
+        // LIMIT / OFFSET clauses
+        sql.push_str(&ctx.dialect.limit_offset(self.limit_value, self.offset_value));
 
         Ok(sql)
     }
+}
 
-    fn parameters(&self) -> &[Value] {
-        &self.parameters
+impl QueryBuilder for SelectBuilderComplete {
+    fn to_sql_with_params(&self, dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
+        let mut ctx = RenderContext::new(dialect);
+        let sql = self.render(&mut ctx)?;
+        Ok((sql, ctx.into_params()))
     }
 
     fn clone_builder(&self) -> Self {
@@ -1818,24 +2809,75 @@ mod tests {
             .group_by("status")
             .having(("COUNT(*)", op::GT, 5));
 
-        let sql = query.to_sql().unwrap();
-        assert_eq!(sql, "SELECT status, COUNT(*) AS count, SUM(total) AS total_sales FROM orders WHERE created_at >= ? GROUP BY status HAVING COUNT(*) > ?");
+        let sql = query.to_sql().unwrap();
+        assert_eq!(sql, "SELECT status, COUNT(*) AS count, SUM(total) AS total_sales FROM orders WHERE created_at >= ? GROUP BY status HAVING COUNT(*) > ?");
+    }
+
+    #[test]
+    fn test_multiple_having_conditions() {
+        let query = from("orders")
+            .select(vec![
+                crate::col("customer_id"),
+                ColumnSelector::count().as_alias("order_count"),
+                ColumnSelector::sum("total").as_alias("total_spent"),
+            ])
+            .group_by("customer_id")
+            .having(("COUNT(*)", op::GT, 3))
+            .and_having(("SUM(total)", op::GTE, 500));
+
+        let sql = query.to_sql().unwrap();
+        assert_eq!(sql, "SELECT customer_id, COUNT(*) AS order_count, SUM(total) AS total_spent FROM orders GROUP BY customer_id HAVING COUNT(*) > ? AND SUM(total) >= ?");
+    }
+
+    #[test]
+    fn test_having_in_lists() {
+        use crate::dialect::{PostgresDialect, SqliteDialect};
+
+        let query = from("orders")
+            .select("customer_id")
+            .group_by("customer_id")
+            .having(("COUNT(*)", op::IN, vec![1, 2]))
+            .and_having(("MAX(status)", op::NOT_IN, vec!["void", "lost"]));
+
+        let (sql, params) = query.to_sql_with_params(&SqliteDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"SELECT "customer_id" FROM "orders" GROUP BY "customer_id" HAVING COUNT(*) IN (?, ?) AND MAX(status) NOT IN (?, ?)"#
+        );
+        assert_eq!(
+            params,
+            vec![Value::I32(1), Value::I32(2), Value::from("void"), Value::from("lost")]
+        );
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"SELECT "customer_id" FROM "orders" GROUP BY "customer_id" HAVING COUNT(*) = ANY($1) AND MAX(status) <> ALL($2)"#
+        );
+        assert_eq!(params.len(), 2);
     }
 
     #[test]
-    fn test_multiple_having_conditions() {
+    fn test_having_empty_lists_and_null_checks() {
+        let empty: Vec<i32> = Vec::new();
         let query = from("orders")
-            .select(vec![
-                crate::col("customer_id"),
-                ColumnSelector::count().as_alias("order_count"),
-                ColumnSelector::sum("total").as_alias("total_spent"),
-            ])
+            .select("customer_id")
             .group_by("customer_id")
-            .having(("COUNT(*)", op::GT, 3))
-            .and_having(("SUM(total)", op::GTE, 500));
+            .having(("COUNT(*)", op::IN, empty.clone()))
+            .or_having(("SUM(total)", op::NOT_IN, empty))
+            .or_having(("MAX(shipped_at)", op::IS_NULL, Value::Null));
 
-        let sql = query.to_sql().unwrap();
-        assert_eq!(sql, "SELECT customer_id, COUNT(*) AS order_count, SUM(total) AS total_spent FROM orders GROUP BY customer_id HAVING COUNT(*) > ? AND SUM(total) >= ?");
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "SELECT customer_id FROM orders GROUP BY customer_id HAVING FALSE OR TRUE OR MAX(shipped_at) IS NULL"
+        );
+        assert!(query.parameters().unwrap().is_empty());
+
+        let invalid = from("orders")
+            .select("customer_id")
+            .group_by("customer_id")
+            .having(("COUNT(*)", "; DROP TABLE", 1));
+        assert!(matches!(invalid.to_sql(), Err(Error::InvalidQuery { .. })));
     }
 
     #[test]
@@ -1908,6 +2950,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_subquery_parameters_follow_placeholders() {
+        let query = from("customers")
+            .select((
+                "name",
+                ColumnSelector::subquery_as(
+                    from("orders").select("COUNT(*)").where_(("status", "open")),
+                    "open_orders",
+                ),
+            ))
+            .where_(("active", true))
+            .where_exists(from("payments").select("1").where_(("amount", op::GT, 100)))
+            .group_by("name")
+            .having(("COUNT(*)", op::GT, 2));
+
+        assert_eq!(
+            query.parameters().unwrap(),
+            vec![
+                Value::from("open"),
+                Value::Bool(true),
+                Value::I32(100),
+                Value::I32(2)
+            ]
+        );
+    }
+
     #[test]
     fn test_subquery_in_select() {
         let subquery = from("orders")
@@ -1984,7 +3052,7 @@ mod tests {
             .where_(("active", true));
 
         let sql = query.to_sql().unwrap();
-        assert_eq!(sql, "SELECT name FROM customers WHERE active = ? AND id IN (SELECT customer_id FROM orders WHERE status = ? AND total > ?)");
+        assert_eq!(sql, "SELECT name FROM customers WHERE id IN (SELECT customer_id FROM orders WHERE status = ? AND total > ?) AND active = ?");
     }
 
     #[test]
@@ -2074,4 +3142,457 @@ mod tests {
         let sql = query.to_sql().unwrap();
         assert_eq!(sql, "SELECT * FROM customers WHERE active = ? AND id IN (SELECT customer_id FROM orders WHERE total > ?)");
     }
+
+    #[test]
+    fn test_subquery_conditions_keep_their_position_and_connector() {
+        let query = from("t")
+            .select("a")
+            .where_in("x", from("s").select("x").where_(("z", 2)))
+            .or_where(("y", 1));
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "SELECT a FROM t WHERE x IN (SELECT x FROM s WHERE z = ?) OR y = ?"
+        );
+        assert_eq!(query.parameters().unwrap(), vec![Value::I32(2), Value::I32(1)]);
+
+        let query = from("t")
+            .select("a")
+            .where_(("y", 1))
+            .or_where_not_exists(from("s").select("x"));
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "SELECT a FROM t WHERE y = ? OR NOT EXISTS (SELECT x FROM s)"
+        );
+    }
+
+    #[test]
+    fn test_subquery_conditions_inside_groups() {
+        use crate::dialect::PostgresDialect;
+
+        let query = from("users")
+            .select("id")
+            .where_(("active", true))
+            .where_group(|g| {
+                g.where_in("id", from("admins").select("user_id").where_(("level", 3)))
+                    .or_where_exists(from("owners").select("id").where_(("plan", "pro")))
+            })
+            .and_where(("age", op::GT, 18));
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"SELECT "id" FROM "users" WHERE "active" = $1 AND ("id" IN (SELECT "user_id" FROM "admins" WHERE "level" = $2) OR EXISTS (SELECT "id" FROM "owners" WHERE "plan" = $3)) AND "age" > $4"#
+        );
+        assert_eq!(
+            params,
+            vec![Value::Bool(true), Value::I32(3), Value::from("pro"), Value::I32(18)]
+        );
+    }
+
+    #[test]
+    fn test_postgres_dialect_numbers_placeholders() {
+        use crate::dialect::PostgresDialect;
+        let query = from("users")
+            .select(("id", "name"))
+            .where_(("age", op::GT, 18))
+            .where_(("status", "active"))
+            .limit(10)
+            .offset(20);
+
+        let sql = query.to_sql_with(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"SELECT "id", "name" FROM "users" WHERE "age" > $1 AND "status" = $2 LIMIT 10 OFFSET 20"#
+        );
+    }
+
+    #[test]
+    fn test_postgres_dialect_continues_numbering_into_subqueries() {
+        use crate::dialect::PostgresDialect;
+
+        let subquery = from("orders")
+            .select("customer_id")
+            .where_(("total", op::GT, 100));
+
+        let query = from("customers")
+            .select("*")
+            .where_(("active", true))
+            .where_in("id", subquery);
+
+        let sql = query.to_sql_with(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"SELECT * FROM "customers" WHERE "active" = $1 AND "id" IN (SELECT "customer_id" FROM "orders" WHERE "total" > $2)"#
+        );
+    }
+
+    #[test]
+    fn test_dialect_quoting_leaves_expressions_alone() {
+        use crate::dialect::MySqlDialect;
+
+        let query = from("orders")
+            .select(vec![
+                crate::col("orders.status").as_alias("state"),
+                ColumnSelector::count().as_alias("total"),
+            ])
+            .inner_join("users", "users.id", "orders.user_id")
+            .group_by("orders.status")
+            .having(("COUNT(*)", op::GT, 5))
+            .order_by_desc("total");
+
+        let sql = query.to_sql_with(&MySqlDialect).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT `orders`.`status` AS `state`, COUNT(*) AS `total` FROM `orders` INNER JOIN `users` ON `users`.`id` = `orders`.`user_id` GROUP BY `orders`.`status` HAVING COUNT(*) > ? ORDER BY `total` DESC"
+        );
+    }
+
+    #[test]
+    fn test_sqlite_dialect_offset_without_limit() {
+        use crate::dialect::SqliteDialect;
+
+        let query = from("users").select("*").offset(5);
+
+        let sql = query.to_sql_with(&SqliteDialect).unwrap();
+        assert_eq!(sql, r#"SELECT * FROM "users" LIMIT -1 OFFSET 5"#);
+    }
+
+    #[test]
+    fn test_where_group() {
+        let query = from("users")
+            .select("*")
+            .where_(("active", true))
+            .where_group(|q| q.where_(("role", "admin")).or_where(("age", op::GT, 65)));
+
+        let sql = query.to_sql().unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM users WHERE active = ? AND (role = ? OR age > ?)"
+        );
+        assert_eq!(
+            query.parameters().unwrap(),
+            &[Value::Bool(true), Value::from("admin"), Value::I32(65)]
+        );
+    }
+
+    #[test]
+    fn test_or_where_group_nested() {
+        let query = from("products")
+            .where_group(|q| {
+                q.where_(("category", "books"))
+                    .where_(("price", op::LT, 20))
+            })
+            .or_where_group(|q| {
+                q.where_(("category", "music"))
+                    .where_group(|q| q.where_(("format", "vinyl")).or_where(("format", "cd")))
+            })
+            .select("*");
+
+        let sql = query.to_sql().unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM products WHERE (category = ? AND price < ?) OR (category = ? AND (format = ? OR format = ?))"
+        );
+        assert_eq!(query.parameters().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_empty_where_group_is_skipped() {
+        let query = from("users")
+            .select("*")
+            .where_group(|q| q)
+            .where_(("id", 1));
+
+        let sql = query.to_sql().unwrap();
+        assert_eq!(sql, "SELECT * FROM users WHERE id = ?");
+    }
+
+    #[test]
+    fn test_where_group_validates_operators() {
+        let query = from("users")
+            .select("*")
+            .where_group(|q| q.where_(("age", "BOGUS", 1)));
+
+        assert!(query.to_sql().is_err());
+    }
+
+    #[test]
+    fn test_in_list_expands_placeholders() {
+        let query = from("players")
+            .select("*")
+            .where_(("score", op::IN, vec![100, 200, 300]))
+            .and_where(("active", true));
+
+        let sql = query.to_sql().unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM players WHERE score IN (?, ?, ?) AND active = ?"
+        );
+        assert_eq!(query.parameters().unwrap().len(), 4);
+        assert_eq!(query.parameters().unwrap()[0], Value::I32(100));
+        assert_eq!(query.parameters().unwrap()[3], Value::Bool(true));
+    }
+
+    #[test]
+    fn test_empty_in_list_is_constant_predicate() {
+        use crate::dialect::SqliteDialect;
+
+        let query = from("players")
+            .select("*")
+            .where_(("score", op::IN, Vec::<i32>::new()))
+            .or_where(("name", op::NOT_IN, Vec::<String>::new()));
+
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "SELECT * FROM players WHERE FALSE OR TRUE"
+        );
+        assert_eq!(
+            query.to_sql_with(&SqliteDialect).unwrap(),
+            "SELECT * FROM \"players\" WHERE 0 OR 1"
+        );
+        assert!(query.parameters().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_postgres_in_list_binds_array() {
+        use crate::dialect::PostgresDialect;
+
+        let query = from("players")
+            .select("*")
+            .where_(("id", 7))
+            .and_where(("score", op::IN, vec![100, 200]))
+            .and_where(("name", op::NOT_IN, vec!["a", "b"]));
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM \"players\" WHERE \"id\" = $1 AND \"score\" = ANY($2) AND \"name\" <> ALL($3)"
+        );
+        assert_eq!(params.len(), 3);
+        assert_eq!(
+            params[1],
+            Value::Array(vec![Value::I32(100), Value::I32(200)])
+        );
+    }
+
+    #[test]
+    fn test_postgres_mixed_in_list_is_expanded() {
+        use crate::dialect::PostgresDialect;
+
+        let mixed = Value::Array(vec![Value::I32(1), Value::String("two".to_string())]);
+        let query = from("things").select("*").where_(("tag", op::IN, mixed));
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(sql, "SELECT * FROM \"things\" WHERE \"tag\" IN ($1, $2)");
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_null_checks_bind_no_value() {
+        let query = from("users")
+            .select("*")
+            .where_(("deleted_at", op::IS_NULL, Value::Null))
+            .or_where(("banned_at", op::IS_NOT_NULL, Value::Null));
+
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "SELECT * FROM users WHERE deleted_at IS NULL OR banned_at IS NOT NULL"
+        );
+        assert!(query.parameters().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_raw_fragments_continue_placeholder_numbering() {
+        use crate::dialect::PostgresDialect;
+        use crate::raw;
+
+        let orders = "LEFT JOIN orders ON orders.user_id = users.id AND orders.total > ?";
+        let query = from("users")
+            .select("users.id")
+            .select_raw(raw("COALESCE(nickname, ?) AS handle", ["anon"]))
+            .join_raw(Raw::new(orders).bind(100))
+            .where_(("users.active", true))
+            .where_raw(raw("users.created_at > ?", ["2024-01-01"]))
+            .or_where_raw("users.role = 'admin'")
+            .group_by("users.id")
+            .having_raw(raw("SUM(orders.total) > ?", [1000]))
+            .order_by_raw(raw("ABS(users.score - ?)", [50]))
+            .order_by_desc("users.id");
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT \"users\".\"id\", COALESCE(nickname, $1) AS handle FROM \"users\" LEFT JOIN orders ON orders.user_id = users.id AND orders.total > $2 WHERE \"users\".\"active\" = $3 AND users.created_at > $4 OR users.role = 'admin' GROUP BY \"users\".\"id\" HAVING SUM(orders.total) > $5 ORDER BY ABS(users.score - $6), \"users\".\"id\" DESC"
+        );
+        assert_eq!(
+            params,
+            vec![
+                Value::from("anon"),
+                Value::I32(100),
+                Value::Bool(true),
+                Value::from("2024-01-01"),
+                Value::I32(1000),
+                Value::I32(50),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_raw_fragment_placeholder_mismatch_fails_to_render() {
+        let query = from("users")
+            .select("*")
+            .where_raw(Raw::new("age BETWEEN ? AND ?").bind(18));
+
+        assert!(matches!(query.to_sql(), Err(Error::InvalidQuery { .. })));
+        assert!(matches!(query.parameters(), Err(Error::InvalidQuery { .. })));
+    }
+
+    #[test]
+    fn test_raw_selectors_and_groups() {
+        use crate::raw;
+
+        let query = from("products")
+            .where_group(|q| q.where_(("stock", 0)).or_where_raw(raw("restock_at < ?", [7])))
+            .having(("COUNT(*)", op::GT, 1))
+            .or_having_raw("MAX(price) IS NULL")
+            .group_by("category")
+            .select((
+                "category",
+                Raw::new("MAX(price)"),
+                ColumnSelector::raw("MIN(price)").as_alias("cheapest"),
+            ))
+            .select_raw(raw("AVG(price) * ?", [1.2]))
+            .order_by_raw("category");
+
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "SELECT category, MAX(price), MIN(price) AS cheapest, AVG(price) * ? FROM products WHERE (stock = ? OR restock_at < ?) GROUP BY category HAVING COUNT(*) > ? OR MAX(price) IS NULL ORDER BY category"
+        );
+        assert_eq!(
+            query.parameters().unwrap(),
+            vec![Value::F64(1.2), Value::I32(0), Value::I32(7), Value::I32(1)]
+        );
+    }
+
+    #[test]
+    fn test_leaderboard_with_window_functions() {
+        use crate::dialect::PostgresDialect;
+
+        let query = from("scores")
+            .select((
+                "player",
+                ColumnSelector::row_number()
+                    .over(|w| w.partition_by("game").order_by_desc("points"))
+                    .as_alias("position"),
+                ColumnSelector::dense_rank().over(|w| w.order_by_desc("points")),
+                ColumnSelector::lead_or("points", 1, 0).over_named("w").as_alias("next_points"),
+            ))
+            .where_(("season", 2024))
+            .window("w", |w| w.order_by_desc("points"));
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"SELECT "player", ROW_NUMBER() OVER (PARTITION BY "game" ORDER BY "points" DESC) AS "position", DENSE_RANK() OVER (ORDER BY "points" DESC), LEAD("points", 1, $1) OVER "w" AS "next_points" FROM "scores" WHERE "season" = $2 WINDOW "w" AS (ORDER BY "points" DESC)"#
+        );
+        assert_eq!(params, vec![Value::I32(0), Value::I32(2024)]);
+    }
+
+    #[test]
+    fn test_windowed_aggregates_and_raw() {
+        use crate::FrameBound;
+
+        let query = from("payments").select((
+            ColumnSelector::sum("amount")
+                .as_alias("balance")
+                .over(|w| w.partition_by("account_id"))
+                .over(|w| {
+                    w.order_by_asc("paid_at")
+                        .rows_between(FrameBound::UnboundedPreceding, FrameBound::CurrentRow)
+                }),
+            ColumnSelector::count().over(|w| w),
+            ColumnSelector::raw("NTILE(4)").over(|w| w.order_by_asc("amount")).as_alias("quartile"),
+        ));
+
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "SELECT SUM(amount) OVER (PARTITION BY account_id ORDER BY paid_at ASC ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS balance, COUNT(*) OVER (), NTILE(4) OVER (ORDER BY amount ASC) AS quartile FROM payments"
+        );
+    }
+
+    #[test]
+    fn test_invalid_windows_fail_to_render() {
+        let plain_column = from("t").select(ColumnSelector::column("id").over(|w| w));
+        assert!(matches!(plain_column.to_sql(), Err(Error::InvalidQuery { .. })));
+
+        let duplicate = from("t")
+            .select(ColumnSelector::rank().over_named("w"))
+            .window("w", |w| w.order_by_asc("a"))
+            .window("w", |w| w.order_by_asc("b"));
+        assert!(matches!(duplicate.to_sql(), Err(Error::InvalidQuery { .. })));
+    }
+
+    #[test]
+    fn test_join_with_binds_values_in_order() {
+        use crate::dialect::PostgresDialect;
+
+        let query = from("users")
+            .select(("users.id", "orders.total"))
+            .where_(("users.active", true))
+            .join_with("orders", |j| {
+                j.on("users.id", op::EQ, "orders.user_id")
+                    .and_on_val("orders.status", "paid")
+                    .and_on_where(("orders.region", op::IN, vec!["eu", "us"]))
+                    .or_on_val("orders.priority", true)
+            })
+            .left_join_with("refunds", |j| {
+                j.on("refunds.order_id", "=", "orders.id").and_on_where(("refunds.amount", ">", 5))
+            })
+            .where_in("users.id", from("vips").select("user_id").where_(("level", 3)));
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"SELECT "users"."id", "orders"."total" FROM "users" INNER JOIN "orders" ON "users"."id" = "orders"."user_id" AND "orders"."status" = $1 AND "orders"."region" = ANY($2) OR "orders"."priority" = $3 LEFT JOIN "refunds" ON "refunds"."order_id" = "orders"."id" AND "refunds"."amount" > $4 WHERE "users"."active" = $5 AND "users"."id" IN (SELECT "user_id" FROM "vips" WHERE "level" = $6)"#
+        );
+        assert_eq!(
+            params,
+            vec![
+                Value::from("paid"),
+                Value::from(vec!["eu", "us"]),
+                Value::Bool(true),
+                Value::I32(5),
+                Value::Bool(true),
+                Value::I32(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_join_using() {
+        let query = from("orders")
+            .select("*")
+            .join_with("order_items", |j| j.using(("order_id", "tenant_id")));
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "SELECT * FROM orders INNER JOIN order_items USING (order_id, tenant_id)"
+        );
+
+        let both = from("orders")
+            .select("*")
+            .join_with("order_items", |j| j.using("order_id").on_val("tenant_id", 1));
+        assert!(matches!(both.to_sql(), Err(Error::InvalidQuery { .. })));
+    }
+
+    #[test]
+    fn test_join_requires_a_condition() {
+        let neither = from("users").select("*").join_with("orders", |j| j);
+        assert!(matches!(neither.to_sql(), Err(Error::InvalidQuery { .. })));
+
+        let left = from("users").select("*").left_join_with("orders", |j| j);
+        assert!(matches!(left.to_sql(), Err(Error::InvalidQuery { .. })));
+
+        let cross = from("users").select("*").cross_join("orders");
+        assert_eq!(cross.to_sql().unwrap(), "SELECT * FROM users CROSS JOIN orders");
+    }
 }
@@ -0,0 +1,235 @@
+//! Tables and derived tables used in FROM and JOIN clauses
+
+use super::common::RenderContext;
+use super::select::SelectQuery;
+use crate::{Error, Result};
+
+/// Something rows can be selected from: a table, optionally aliased, or a
+/// subquery with an alias (a derived table)
+///
+/// Strings convert into unaliased tables, so every method taking a table
+/// also accepts `"users"`.
+///
+/// # Examples
+/// ```
+/// use archibald::{from, table, QueryBuilder, TableRef};
+///
+/// let totals = from("orders")
+///     .select("user_id")
+///     .select_raw("SUM(total) AS spent")
+///     .group_by("user_id");
+/// let query = from(table("users").as_("u"))
+///     .inner_join(TableRef::subquery(totals, "t"), "t.user_id", "u.id")
+///     .select(("u.name", "t.spent"));
+/// assert_eq!(
+///     query.to_sql().unwrap(),
+///     "SELECT u.name, t.spent FROM users AS u INNER JOIN (SELECT user_id, SUM(total) AS spent FROM orders GROUP BY user_id) AS t ON t.user_id = u.id"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub enum TableRef {
+    Table {
+        name: String,
+        alias: Option<String>,
+    },
+    Derived {
+        query: Box<SelectQuery>,
+        alias: String,
+        lateral: bool,
+    },
+}
+
+impl TableRef {
+    /// Create a reference to a table
+    pub fn table(name: &str) -> Self {
+        Self::Table {
+            name: name.to_string(),
+            alias: None,
+        }
+    }
+
+    /// Create a derived table from a subquery, which must be given an alias
+    pub fn subquery<Q>(query: Q, alias: &str) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        Self::Derived {
+            query: Box::new(query.into()),
+            alias: alias.to_string(),
+            lateral: false,
+        }
+    }
+
+    /// Create a LATERAL derived table, whose query can refer to the tables
+    /// before it in the FROM clause
+    ///
+    /// Supported by PostgreSQL and MySQL; SQLite rejects it when rendering.
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, QueryBuilder, TableRef};
+    /// use archibald::dialect::PostgresDialect;
+    ///
+    /// let latest = from("orders")
+    ///     .select("total")
+    ///     .where_raw(r#""orders"."user_id" = "users"."id""#)
+    ///     .order_by_desc("created_at")
+    ///     .limit(1);
+    /// let query = from("users")
+    ///     .cross_join(TableRef::lateral(latest, "latest"))
+    ///     .select(("users.name", "latest.total"));
+    /// assert_eq!(
+    ///     query.to_sql_with(&PostgresDialect).unwrap(),
+    ///     r#"SELECT "users"."name", "latest"."total" FROM "users" CROSS JOIN LATERAL (SELECT "total" FROM "orders" WHERE "orders"."user_id" = "users"."id" ORDER BY "created_at" DESC LIMIT 1) AS "latest""#
+    /// );
+    /// ```
+    pub fn lateral<Q>(query: Q, alias: &str) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        Self::Derived {
+            query: Box::new(query.into()),
+            alias: alias.to_string(),
+            lateral: true,
+        }
+    }
+
+    /// Set the alias the table is referred to by in the rest of the query
+    pub fn as_(self, alias: &str) -> Self {
+        match self {
+            Self::Table { name, .. } => Self::Table {
+                name,
+                alias: Some(alias.to_string()),
+            },
+            Self::Derived { query, lateral, .. } => Self::Derived {
+                query,
+                alias: alias.to_string(),
+                lateral,
+            },
+        }
+    }
+
+    /// The name the table is referred to by: its alias, or the table name
+    pub fn reference(&self) -> &str {
+        match self {
+            Self::Table { name, alias } => alias.as_deref().unwrap_or(name),
+            Self::Derived { alias, .. } => alias,
+        }
+    }
+
+    /// Render the table with its alias, continuing the outer placeholder numbering
+    pub(crate) fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        match self {
+            Self::Table { name, alias } => Ok(ctx.aliased(ctx.ident(name), alias.as_ref())),
+            Self::Derived {
+                query,
+                alias,
+                lateral,
+            } => {
+                if *lateral && !ctx.dialect.supports_lateral() {
+                    return Err(Error::invalid_query(format!(
+                        "{} does not support LATERAL derived tables",
+                        ctx.dialect.name()
+                    )));
+                }
+                let keyword = if *lateral { "LATERAL " } else { "" };
+                Ok(format!(
+                    "{}({}) AS {}",
+                    keyword,
+                    query.render(ctx)?,
+                    ctx.ident(alias)
+                ))
+            }
+        }
+    }
+}
+
+/// Render tables separated by `separator`, continuing the outer placeholder
+/// numbering
+pub(crate) fn render_table_list(
+    tables: &[TableRef],
+    separator: &str,
+    ctx: &mut RenderContext,
+) -> Result<String> {
+    let mut parts = Vec::with_capacity(tables.len());
+    for table in tables {
+        parts.push(table.render(ctx)?);
+    }
+    Ok(parts.join(separator))
+}
+
+impl From<&str> for TableRef {
+    fn from(name: &str) -> Self {
+        TableRef::table(name)
+    }
+}
+
+impl From<String> for TableRef {
+    fn from(name: String) -> Self {
+        TableRef::Table { name, alias: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MySqlDialect, PostgresDialect, SqliteDialect};
+    use crate::{QueryBuilder, Value, from, from_subquery, op, table};
+
+    #[test]
+    fn test_aliased_tables() {
+        let query = from(table("users").as_("u"))
+            .inner_join(table("orders").as_("o"), "o.user_id", "u.id")
+            .select(("u.id", "o.total"));
+
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"SELECT "u"."id", "o"."total" FROM "users" AS "u" INNER JOIN "orders" AS "o" ON "o"."user_id" = "u"."id""#
+        );
+        assert_eq!(
+            query.to_sql_with(&MySqlDialect).unwrap(),
+            "SELECT `u`.`id`, `o`.`total` FROM `users` AS `u` INNER JOIN `orders` AS `o` ON `o`.`user_id` = `u`.`id`"
+        );
+    }
+
+    #[test]
+    fn test_from_subquery_numbers_placeholders_in_order() {
+        let recent = from("orders")
+            .select(("user_id", "total"))
+            .where_(("total", op::GT, 10));
+        let query = from_subquery(recent, "recent")
+            .join_with(
+                TableRef::subquery(
+                    from("users").select("id").where_(("active", true)),
+                    "active",
+                ),
+                |j| j.on("active.id", "=", "recent.user_id"),
+            )
+            .select("recent.total")
+            .where_(("recent.total", op::LT, 100));
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"SELECT "recent"."total" FROM (SELECT "user_id", "total" FROM "orders" WHERE "total" > $1) AS "recent" INNER JOIN (SELECT "id" FROM "users" WHERE "active" = $2) AS "active" ON "active"."id" = "recent"."user_id" WHERE "recent"."total" < $3"#
+        );
+        assert_eq!(
+            params,
+            vec![Value::I32(10), Value::Bool(true), Value::I32(100)]
+        );
+    }
+
+    #[test]
+    fn test_lateral_requires_dialect_support() {
+        let latest = from("orders").select("total").limit(1);
+        let query = from("users")
+            .cross_join(TableRef::lateral(latest, "latest"))
+            .select("*");
+
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"SELECT * FROM "users" CROSS JOIN LATERAL (SELECT "total" FROM "orders" LIMIT 1) AS "latest""#
+        );
+        assert!(query.to_sql_with(&SqliteDialect).is_err());
+    }
+}
@@ -1,40 +1,104 @@
 //! UPDATE query builder module
 
-use super::common::{IntoCondition, QueryBuilder, WhereCondition, WhereConnector};
-use crate::{Result, Value};
+use super::common::{
+    IntoColumns, IntoCondition, QueryBuilder, RenderContext, WhereCondition, WhereConnector,
+    WhereGroupBuilder, WhereNode, render_where_nodes,
+};
+use super::cte::{Cte, render_with};
+use super::raw::Raw;
+use super::returning::ReturningBuilder;
+use super::select::SelectQuery;
+use super::table_ref::{TableRef, render_table_list};
+use crate::dialect::{Dialect, MultiTableSyntax};
+use crate::{Error, Result, Value};
 
 /// Initial UPDATE query builder - requires SET clause
 #[derive(Debug, Clone)]
 pub struct UpdateBuilderInitial {
+    ctes: Vec<Cte>,
     table_name: String,
 }
 
 /// UPDATE query builder with SET clause - requires WHERE clause
 #[derive(Debug, Clone)]
 pub struct UpdateBuilderWithSet {
+    ctes: Vec<Cte>,
     table_name: String,
-    set_clauses: Vec<(String, Value)>,
-    set_parameters: Vec<Value>,
+    set_clauses: Vec<(String, SetValue)>,
+    from_tables: Vec<TableRef>,
+}
+
+/// The new value of a column in a SET clause
+#[derive(Debug, Clone)]
+enum SetValue {
+    /// A bound value
+    Value(Value),
+    /// The value of another column (`SET a = b`)
+    Column(String),
+    /// The column's current value plus a bound amount
+    Increment(Value),
+    /// The column's current value minus a bound amount
+    Decrement(Value),
+    /// A raw SQL expression, with its own bound values
+    Expr(Raw),
 }
 
 /// Complete UPDATE query builder - has both SET and WHERE clauses
 #[derive(Debug, Clone)]
 pub struct UpdateBuilderComplete {
+    ctes: Vec<Cte>,
     table_name: String,
-    set_clauses: Vec<(String, Value)>,
-    where_conditions: Vec<WhereCondition>,
-    where_parameters: Vec<Value>,
-    all_parameters: Vec<Value>,
+    set_clauses: Vec<(String, SetValue)>,
+    from_tables: Vec<TableRef>,
+    where_conditions: Vec<WhereNode>,
 }
 
 impl UpdateBuilderInitial {
     /// Create a new UPDATE query builder
     pub fn new(table: &str) -> Self {
         Self {
+            ctes: Vec::new(),
             table_name: table.to_string(),
         }
     }
 
+    /// Add a common table expression to a WITH clause before the statement
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{from, update, QueryBuilder};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut updates = HashMap::new();
+    /// updates.insert("tier".to_string(), "gold".into());
+    ///
+    /// let query = update("customers")
+    ///     .with("vips", from("orders").select("customer_id").group_by("customer_id"))
+    ///     .set(updates)
+    ///     .where_raw("id IN (SELECT customer_id FROM vips)");
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "WITH vips AS (SELECT customer_id FROM orders GROUP BY customer_id) UPDATE customers SET tier = ? WHERE id IN (SELECT customer_id FROM vips)"
+    /// );
+    /// ```
+    pub fn with<Q>(mut self, name: &str, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::new(name, query));
+        self
+    }
+
+    /// Add a recursive common table expression, rendering `WITH RECURSIVE`
+    pub fn with_recursive<C, Q>(mut self, name: &str, columns: C, query: Q) -> Self
+    where
+        C: IntoColumns,
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::recursive(name, columns, query));
+        self
+    }
+
     /// Set column values, transitioning to UpdateBuilderWithSet
     ///
     /// # Examples
@@ -52,18 +116,203 @@ impl UpdateBuilderInitial {
     where
         T: IntoUpdateData,
     {
-        let updates = data.into_update_data();
-        let set_parameters: Vec<Value> = updates.iter().map(|(_, v)| v.clone()).collect();
+        self.into_with_set().set(data)
+    }
 
+    /// Add a bound amount to a column, transitioning to UpdateBuilderWithSet
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{update, QueryBuilder};
+    ///
+    /// let query = update("posts").increment("views", 1).where_(("id", 7));
+    /// assert_eq!(query.to_sql().unwrap(), "UPDATE posts SET views = views + ? WHERE id = ?");
+    /// ```
+    pub fn increment<V>(self, column: &str, amount: V) -> UpdateBuilderWithSet
+    where
+        V: Into<Value>,
+    {
+        self.into_with_set().increment(column, amount)
+    }
+
+    /// Subtract a bound amount from a column, transitioning to UpdateBuilderWithSet
+    pub fn decrement<V>(self, column: &str, amount: V) -> UpdateBuilderWithSet
+    where
+        V: Into<Value>,
+    {
+        self.into_with_set().decrement(column, amount)
+    }
+
+    /// Set a column to a raw SQL expression, transitioning to UpdateBuilderWithSet
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{raw, update, QueryBuilder};
+    ///
+    /// let query = update("users")
+    ///     .set_expr("updated_at", "CURRENT_TIMESTAMP")
+    ///     .set_expr("score", raw("score * ?", [2]))
+    ///     .where_(("id", 1));
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "UPDATE users SET updated_at = CURRENT_TIMESTAMP, score = score * ? WHERE id = ?"
+    /// );
+    /// ```
+    pub fn set_expr<R>(self, column: &str, expr: R) -> UpdateBuilderWithSet
+    where
+        R: Into<Raw>,
+    {
+        self.into_with_set().set_expr(column, expr)
+    }
+
+    /// Set a column to the value of another column, transitioning to
+    /// UpdateBuilderWithSet
+    pub fn set_column(self, column: &str, other: &str) -> UpdateBuilderWithSet {
+        self.into_with_set().set_column(column, other)
+    }
+
+    fn into_with_set(self) -> UpdateBuilderWithSet {
         UpdateBuilderWithSet {
+            ctes: self.ctes,
             table_name: self.table_name,
-            set_clauses: updates,
-            set_parameters,
+            set_clauses: Vec::new(),
+            from_tables: Vec::new(),
         }
     }
 }
 
 impl UpdateBuilderWithSet {
+    /// Add a common table expression to a WITH clause before the statement
+    pub fn with<Q>(mut self, name: &str, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::new(name, query));
+        self
+    }
+
+    /// Add a recursive common table expression, rendering `WITH RECURSIVE`
+    pub fn with_recursive<C, Q>(mut self, name: &str, columns: C, query: Q) -> Self
+    where
+        C: IntoColumns,
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::recursive(name, columns, query));
+        self
+    }
+
+    /// Set more column values
+    ///
+    /// Assignments render in the order they were added, whichever method
+    /// added them.
+    pub fn set<T>(mut self, data: T) -> Self
+    where
+        T: IntoUpdateData,
+    {
+        let values = data.into_update_data().into_iter();
+        self.set_clauses.extend(values.map(|(column, value)| (column, SetValue::Value(value))));
+        self
+    }
+
+    /// Add a bound amount to a column (`SET column = column + ?`)
+    pub fn increment<V>(mut self, column: &str, amount: V) -> Self
+    where
+        V: Into<Value>,
+    {
+        self.set_clauses.push((column.to_string(), SetValue::Increment(amount.into())));
+        self
+    }
+
+    /// Subtract a bound amount from a column (`SET column = column - ?`)
+    pub fn decrement<V>(mut self, column: &str, amount: V) -> Self
+    where
+        V: Into<Value>,
+    {
+        self.set_clauses.push((column.to_string(), SetValue::Decrement(amount.into())));
+        self
+    }
+
+    /// Set a column to a raw SQL expression, whose bound values are numbered
+    /// in place
+    pub fn set_expr<R>(mut self, column: &str, expr: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.set_clauses.push((column.to_string(), SetValue::Expr(expr.into())));
+        self
+    }
+
+    /// Set a column to the value of another column, which may belong to a
+    /// table added with [`from`](Self::from)
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{update, QueryBuilder};
+    ///
+    /// let query = update("orders")
+    ///     .set_column("shipping_address", "customers.address")
+    ///     .from("customers")
+    ///     .where_col("orders.customer_id", "customers.id");
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "UPDATE orders SET shipping_address = customers.address FROM customers WHERE orders.customer_id = customers.id"
+    /// );
+    /// ```
+    pub fn set_column(mut self, column: &str, other: &str) -> Self {
+        self.set_clauses.push((column.to_string(), SetValue::Column(other.to_string())));
+        self
+    }
+
+    /// Read another table in the statement, to filter or compute the new
+    /// values from its rows
+    ///
+    /// Renders `UPDATE ... FROM` on PostgreSQL and SQLite 3.33+, and a
+    /// multi-table `UPDATE ... INNER JOIN` on MySQL. Relate the tables with
+    /// [`where_col`](Self::where_col).
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{update, QueryBuilder};
+    /// use archibald::dialect::{MySqlDialect, PostgresDialect};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut updates = HashMap::new();
+    /// updates.insert("status".to_string(), "vip".into());
+    ///
+    /// let query = update("orders")
+    ///     .set(updates)
+    ///     .from("customers")
+    ///     .where_col("orders.customer_id", "customers.id")
+    ///     .and_where(("customers.tier", "gold"));
+    /// assert_eq!(
+    ///     query.to_sql_with(&PostgresDialect).unwrap(),
+    ///     r#"UPDATE "orders" SET "status" = $1 FROM "customers" WHERE "orders"."customer_id" = "customers"."id" AND "customers"."tier" = $2"#
+    /// );
+    /// assert_eq!(
+    ///     query.to_sql_with(&MySqlDialect).unwrap(),
+    ///     "UPDATE `orders` INNER JOIN `customers` SET `orders`.`status` = ? WHERE `orders`.`customer_id` = `customers`.`id` AND `customers`.`tier` = ?"
+    /// );
+    /// ```
+    pub fn from<T>(mut self, table: T) -> Self
+    where
+        T: Into<TableRef>,
+    {
+        self.from_tables.push(table.into());
+        self
+    }
+
+    /// Add a WHERE condition comparing two columns for equality, transitioning
+    /// to UpdateBuilderComplete
+    pub fn where_col(self, left: &str, right: &str) -> UpdateBuilderComplete {
+        UpdateBuilderComplete {
+            ctes: self.ctes,
+            table_name: self.table_name,
+            set_clauses: self.set_clauses,
+            from_tables: self.from_tables,
+            where_conditions: vec![WhereNode::column_equals(left, right, WhereConnector::And)],
+        }
+    }
+
     /// Add a WHERE condition, transitioning to UpdateBuilderComplete
     pub fn where_<C>(self, condition: C) -> UpdateBuilderComplete
     where
@@ -71,22 +320,17 @@ impl UpdateBuilderWithSet {
     {
         let (column, operator, value) = condition.into_condition();
 
-        let where_condition = WhereCondition {
-            column,
-            operator,
-            value: value.clone(),
-            connector: WhereConnector::And,
-        };
-
-        let mut all_parameters = self.set_parameters.clone();
-        all_parameters.push(value.clone());
-
         UpdateBuilderComplete {
+            ctes: self.ctes,
             table_name: self.table_name,
             set_clauses: self.set_clauses,
-            where_conditions: vec![where_condition],
-            where_parameters: vec![value],
-            all_parameters,
+            from_tables: self.from_tables,
+            where_conditions: vec![WhereNode::Condition(WhereCondition {
+                column,
+                operator,
+                value,
+                connector: WhereConnector::And,
+            })],
         }
     }
 
@@ -97,9 +341,62 @@ impl UpdateBuilderWithSet {
     {
         self.where_(condition)
     }
+
+    /// Add a raw SQL WHERE condition, transitioning to UpdateBuilderComplete
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::{raw, update, QueryBuilder};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut updates = HashMap::new();
+    /// updates.insert("status".to_string(), "expired".into());
+    ///
+    /// let query = update("sessions")
+    ///     .set(updates)
+    ///     .where_raw(raw("last_seen < NOW() - INTERVAL ? DAY", [30]));
+    /// assert_eq!(
+    ///     query.to_sql().unwrap(),
+    ///     "UPDATE sessions SET status = ? WHERE last_seen < NOW() - INTERVAL ? DAY"
+    /// );
+    /// ```
+    pub fn where_raw<R>(self, raw: R) -> UpdateBuilderComplete
+    where
+        R: Into<Raw>,
+    {
+        UpdateBuilderComplete {
+            ctes: self.ctes,
+            table_name: self.table_name,
+            set_clauses: self.set_clauses,
+            from_tables: self.from_tables,
+            where_conditions: vec![WhereNode::Raw {
+                raw: raw.into(),
+                connector: WhereConnector::And,
+            }],
+        }
+    }
 }
 
 impl UpdateBuilderComplete {
+    /// Add a common table expression to a WITH clause before the statement
+    pub fn with<Q>(mut self, name: &str, query: Q) -> Self
+    where
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::new(name, query));
+        self
+    }
+
+    /// Add a recursive common table expression, rendering `WITH RECURSIVE`
+    pub fn with_recursive<C, Q>(mut self, name: &str, columns: C, query: Q) -> Self
+    where
+        C: IntoColumns,
+        Q: Into<SelectQuery>,
+    {
+        self.ctes.push(Cte::recursive(name, columns, query));
+        self
+    }
+
     /// Add an additional WHERE condition with AND
     pub fn and_where<C>(mut self, condition: C) -> Self
     where
@@ -107,14 +404,12 @@ impl UpdateBuilderComplete {
     {
         let (column, operator, value) = condition.into_condition();
 
-        self.where_conditions.push(WhereCondition {
+        self.where_conditions.push(WhereNode::Condition(WhereCondition {
             column,
             operator,
-            value: value.clone(),
+            value,
             connector: WhereConnector::And,
-        });
-        self.where_parameters.push(value.clone());
-        self.all_parameters.push(value);
+        }));
 
         self
     }
@@ -126,14 +421,12 @@ impl UpdateBuilderComplete {
     {
         let (column, operator, value) = condition.into_condition();
 
-        self.where_conditions.push(WhereCondition {
+        self.where_conditions.push(WhereNode::Condition(WhereCondition {
             column,
             operator,
-            value: value.clone(),
+            value,
             connector: WhereConnector::Or,
-        });
-        self.where_parameters.push(value.clone());
-        self.all_parameters.push(value);
+        }));
 
         self
     }
@@ -145,86 +438,210 @@ impl UpdateBuilderComplete {
     {
         self.and_where(condition)
     }
+
+    /// Read another table in the statement; see [`UpdateBuilderWithSet::from`]
+    pub fn from<T>(mut self, table: T) -> Self
+    where
+        T: Into<TableRef>,
+    {
+        self.from_tables.push(table.into());
+        self
+    }
+
+    /// Add a WHERE condition comparing two columns for equality
+    pub fn where_col(mut self, left: &str, right: &str) -> Self {
+        self.where_conditions.push(WhereNode::column_equals(left, right, WhereConnector::And));
+        self
+    }
+
+    /// Add a WHERE condition comparing two columns for equality, joined with OR
+    pub fn or_where_col(mut self, left: &str, right: &str) -> Self {
+        self.where_conditions.push(WhereNode::column_equals(left, right, WhereConnector::Or));
+        self
+    }
+
+    /// Add a parenthesized group of WHERE conditions joined with AND
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::update;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut updates = HashMap::new();
+    /// updates.insert("status".to_string(), "archived".into());
+    ///
+    /// // UPDATE posts SET status = ? WHERE author_id = ? AND (views < ? OR flagged = ?)
+    /// let query = update("posts")
+    ///     .set(updates)
+    ///     .where_(("author_id", 7))
+    ///     .where_group(|q| q.where_(("views", "<", 10)).or_where(("flagged", true)));
+    /// ```
+    pub fn where_group<F>(self, build: F) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        self.push_where_group(build, WhereConnector::And)
+    }
+
+    /// Add a parenthesized group of WHERE conditions joined with OR
+    pub fn or_where_group<F>(self, build: F) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        self.push_where_group(build, WhereConnector::Or)
+    }
+
+    fn push_where_group<F>(mut self, build: F, connector: WhereConnector) -> Self
+    where
+        F: FnOnce(WhereGroupBuilder) -> WhereGroupBuilder,
+    {
+        let node = build(WhereGroupBuilder::new()).into_node(connector);
+        self.where_conditions.push(node);
+        self
+    }
+
+    /// Add a raw SQL WHERE condition
+    pub fn where_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.where_conditions.push(WhereNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::And,
+        });
+        self
+    }
+
+    /// Add a raw SQL WHERE condition joined with OR
+    pub fn or_where_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.where_conditions.push(WhereNode::Raw {
+            raw: raw.into(),
+            connector: WhereConnector::Or,
+        });
+        self
+    }
+
+    /// Return columns of the updated rows, turning this into a query that can be fetched
+    ///
+    /// Supported on PostgreSQL and SQLite 3.35+; rendering for a dialect without
+    /// RETURNING (such as MySQL) fails with [`Error::InvalidQuery`](crate::Error::InvalidQuery).
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::update;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut updates = HashMap::new();
+    /// updates.insert("status".to_string(), "active".into());
+    ///
+    /// let query = update("users").set(updates).where_(("id", 1)).returning(("id", "status"));
+    /// ```
+    pub fn returning<C>(self, columns: C) -> ReturningBuilder<Self>
+    where
+        C: IntoColumns,
+    {
+        ReturningBuilder::new(self, columns)
+    }
 }
 
 impl QueryBuilder for UpdateBuilderInitial {
-    fn to_sql(&self) -> Result<String> {
+    fn to_sql_with_params(&self, _dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
         Err(crate::Error::invalid_query(
             "UPDATE requires SET clause. Use .set() method.",
         ))
     }
 
-    fn parameters(&self) -> &[Value] {
-        &[]
-    }
-
     fn clone_builder(&self) -> Self {
         self.clone()
     }
 }
 
 impl QueryBuilder for UpdateBuilderWithSet {
-    fn to_sql(&self) -> Result<String> {
+    fn to_sql_with_params(&self, _dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
         Err(crate::Error::invalid_query(
             "UPDATE requires WHERE clause for safety. Use .where_() or .and_where() method.",
         ))
     }
 
-    fn parameters(&self) -> &[Value] {
-        &self.set_parameters
-    }
-
     fn clone_builder(&self) -> Self {
         self.clone()
     }
 }
 
 impl QueryBuilder for UpdateBuilderComplete {
-    fn to_sql(&self) -> Result<String> {
-        // Validate all operators before generating SQL
-        for condition in &self.where_conditions {
-            condition.operator.validate()?;
-        }
+    fn to_sql_with_params(&self, dialect: &dyn Dialect) -> Result<(String, Vec<Value>)> {
+        let mut ctx = RenderContext::new(dialect);
 
-        let mut sql = String::new();
+        // WITH clause
+        let mut sql = render_with(&self.ctes, &mut ctx)?;
 
         // UPDATE clause
         sql.push_str("UPDATE ");
-        sql.push_str(&self.table_name);
+        sql.push_str(&ctx.ident(&self.table_name));
+
+        // Other tables are joined in before SET on MySQL, whose SET columns
+        // then need the table name to not be ambiguous
+        let joined = !self.from_tables.is_empty()
+            && dialect.multi_table_syntax() == MultiTableSyntax::Joins;
+        if joined {
+            sql.push_str(" INNER JOIN ");
+            sql.push_str(&render_table_list(&self.from_tables, " INNER JOIN ", &mut ctx)?);
+        }
 
         // SET clause
+        if self.set_clauses.is_empty() {
+            return Err(Error::invalid_query("UPDATE requires at least one column to set"));
+        }
+        let mut set_parts = Vec::with_capacity(self.set_clauses.len());
+        for (i, (column, value)) in self.set_clauses.iter().enumerate() {
+            if self.set_clauses[..i].iter().any(|(other, _)| other == column) {
+                return Err(Error::invalid_query(format!(
+                    "UPDATE sets `{}` more than once",
+                    column
+                )));
+            }
+            // Reading the column back needs the table name whenever another
+            // table could have a column of the same name
+            let qualified = if column.contains('.') {
+                ctx.ident(column)
+            } else {
+                ctx.ident(&format!("{}.{}", self.table_name, column))
+            };
+            let current = if self.from_tables.is_empty() {
+                ctx.ident(column)
+            } else {
+                qualified.clone()
+            };
+            let target = if joined { qualified } else { ctx.ident(column) };
+            let value = match value {
+                SetValue::Value(value) => ctx.bind(value),
+                SetValue::Column(other) => ctx.ident(other),
+                SetValue::Increment(amount) => format!("{} + {}", current, ctx.bind(amount)),
+                SetValue::Decrement(amount) => format!("{} - {}", current, ctx.bind(amount)),
+                SetValue::Expr(expr) => expr.render(&mut ctx)?,
+            };
+            set_parts.push(format!("{} = {}", target, value));
+        }
         sql.push_str(" SET ");
-        let set_parts: Vec<String> = self
-            .set_clauses
-            .iter()
-            .map(|(column, _)| format!("{} = ?", column))
-            .collect();
         sql.push_str(&set_parts.join(", "));
 
+        // FROM clause
+        if !self.from_tables.is_empty() && !joined {
+            sql.push_str(" FROM ");
+            sql.push_str(&render_table_list(&self.from_tables, ", ", &mut ctx)?);
+        }
+
         // WHERE clause
-        if !self.where_conditions.is_empty() {
+        let where_sql = render_where_nodes(&self.where_conditions, &mut ctx)?;
+        if !where_sql.is_empty() {
             sql.push_str(" WHERE ");
-
-            for (i, condition) in self.where_conditions.iter().enumerate() {
-                if i > 0 {
-                    match condition.connector {
-                        WhereConnector::And => sql.push_str(" AND "),
-                        WhereConnector::Or => sql.push_str(" OR "),
-                    }
-                }
-
-                sql.push_str(&condition.column);
-                sql.push(' ');
-                sql.push_str(condition.operator.as_str());
-                sql.push_str(" ?");
-            }
+            sql.push_str(&where_sql);
         }
 
-        Ok(sql)
-    }
-
-    fn parameters(&self) -> &[Value] {
-        &self.all_parameters
+        Ok((sql, ctx.into_params()))
     }
 
     fn clone_builder(&self) -> Self {
@@ -329,4 +746,186 @@ mod tests {
         let result = complete_builder.to_sql();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_update_postgres_dialect() {
+        use crate::dialect::PostgresDialect;
+
+        let mut data = HashMap::new();
+        data.insert("name".to_string(), "Jane".into());
+
+        let query = update("users")
+            .set(data)
+            .where_(("id", 1))
+            .or_where(("admin", true));
+
+        let sql = query.to_sql_with(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"UPDATE "users" SET "name" = $1 WHERE "id" = $2 OR "admin" = $3"#
+        );
+    }
+
+    #[test]
+    fn test_update_where_group() {
+        let mut data = HashMap::new();
+        data.insert("status".to_string(), "archived".into());
+
+        let query = update("posts")
+            .set(data)
+            .where_(("author_id", 7))
+            .where_group(|q| q.where_(("views", "<", 10)).or_where(("flagged", true)));
+
+        let sql = query.to_sql().unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE posts SET status = ? WHERE author_id = ? AND (views < ? OR flagged = ?)"
+        );
+        assert_eq!(
+            query.parameters().unwrap(),
+            &[
+                Value::from("archived"),
+                Value::I32(7),
+                Value::I32(10),
+                Value::Bool(true)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_from_per_dialect() {
+        use crate::dialect::{MySqlDialect, PostgresDialect, SqliteDialect};
+        use crate::{TableRef, from, op};
+
+        let mut data = HashMap::new();
+        data.insert("discount".to_string(), Value::I32(10));
+
+        let spenders = from("payments")
+            .select("customer_id")
+            .where_(("amount", op::GT, 1000))
+            .group_by("customer_id");
+        let query = update("orders")
+            .set(data)
+            .from(TableRef::subquery(spenders, "big"))
+            .where_col("orders.customer_id", "big.customer_id")
+            .and_where(("orders.status", "open"));
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"UPDATE "orders" SET "discount" = $1 FROM (SELECT "customer_id" FROM "payments" WHERE "amount" > $2 GROUP BY "customer_id") AS "big" WHERE "orders"."customer_id" = "big"."customer_id" AND "orders"."status" = $3"#
+        );
+        assert_eq!(params, vec![Value::I32(10), Value::I32(1000), Value::from("open")]);
+
+        assert_eq!(
+            query.to_sql_with(&SqliteDialect).unwrap(),
+            r#"UPDATE "orders" SET "discount" = ? FROM (SELECT "customer_id" FROM "payments" WHERE "amount" > ? GROUP BY "customer_id") AS "big" WHERE "orders"."customer_id" = "big"."customer_id" AND "orders"."status" = ?"#
+        );
+
+        // MySQL joins the derived table before SET, so its parameters come first
+        let (sql, params) = query.to_sql_with_params(&MySqlDialect).unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE `orders` INNER JOIN (SELECT `customer_id` FROM `payments` WHERE `amount` > ? GROUP BY `customer_id`) AS `big` SET `orders`.`discount` = ? WHERE `orders`.`customer_id` = `big`.`customer_id` AND `orders`.`status` = ?"
+        );
+        assert_eq!(params, vec![Value::I32(1000), Value::I32(10), Value::from("open")]);
+    }
+
+    #[test]
+    fn test_update_where_col_with_several_tables() {
+        let mut data = HashMap::new();
+        data.insert("region".to_string(), "eu".into());
+
+        let query = update("orders")
+            .set(data)
+            .where_(("orders.id", 1))
+            .from("customers")
+            .from("countries")
+            .where_col("orders.customer_id", "customers.id")
+            .or_where_col("customers.country", "countries.code");
+
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "UPDATE orders SET region = ? FROM customers, countries WHERE orders.id = ? AND orders.customer_id = customers.id OR customers.country = countries.code"
+        );
+        assert_eq!(query.parameters().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_set_expressions_keep_parameter_order() {
+        use crate::dialect::PostgresDialect;
+        use crate::raw;
+
+        let mut data = HashMap::new();
+        data.insert("title".to_string(), "Hello".into());
+
+        let query = update("posts")
+            .increment("views", 1)
+            .set(data)
+            .decrement("credits", 5)
+            .set_expr("updated_at", "CURRENT_TIMESTAMP")
+            .set_expr("score", raw("score * ? + ?", [2, 3]))
+            .set_column("published_title", "title")
+            .where_(("id", 7));
+
+        let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+        assert_eq!(
+            sql,
+            r#"UPDATE "posts" SET "views" = "views" + $1, "title" = $2, "credits" = "credits" - $3, "updated_at" = CURRENT_TIMESTAMP, "score" = score * $4 + $5, "published_title" = "title" WHERE "id" = $6"#
+        );
+        assert_eq!(
+            params,
+            vec![
+                Value::I32(1),
+                Value::from("Hello"),
+                Value::I32(5),
+                Value::I32(2),
+                Value::I32(3),
+                Value::I32(7)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_expressions_with_mysql_joins() {
+        use crate::dialect::MySqlDialect;
+
+        let query = update("orders")
+            .increment("items", 1)
+            .set_column("region", "customers.region")
+            .from("customers")
+            .where_col("orders.customer_id", "customers.id");
+        assert_eq!(
+            query.to_sql_with(&MySqlDialect).unwrap(),
+            "UPDATE `orders` INNER JOIN `customers` SET `orders`.`items` = `orders`.`items` + ?, `orders`.`region` = `customers`.`region` WHERE `orders`.`customer_id` = `customers`.`id`"
+        );
+    }
+
+    #[test]
+    fn test_increment_from_table_with_the_same_column() {
+        use crate::dialect::{MySqlDialect, PostgresDialect};
+
+        let query = update("orders")
+            .increment("items", 1)
+            .decrement("stock", 2)
+            .from("carts")
+            .where_col("orders.cart_id", "carts.id");
+        assert_eq!(
+            query.to_sql_with(&PostgresDialect).unwrap(),
+            r#"UPDATE "orders" SET "items" = "orders"."items" + $1, "stock" = "orders"."stock" - $2 FROM "carts" WHERE "orders"."cart_id" = "carts"."id""#
+        );
+        assert_eq!(
+            query.to_sql_with(&MySqlDialect).unwrap(),
+            "UPDATE `orders` INNER JOIN `carts` SET `orders`.`items` = `orders`.`items` + ?, `orders`.`stock` = `orders`.`stock` - ? WHERE `orders`.`cart_id` = `carts`.`id`"
+        );
+    }
+
+    #[test]
+    fn test_update_rejects_repeated_and_missing_columns() {
+        let query = update("posts").increment("views", 1).increment("views", 1).where_(("id", 7));
+        assert!(matches!(query.to_sql(), Err(Error::InvalidQuery { .. })));
+
+        let empty = update("posts").set(HashMap::new()).where_(("id", 7));
+        assert!(matches!(empty.to_sql(), Err(Error::InvalidQuery { .. })));
+    }
 }
@@ -0,0 +1,388 @@
+//! Window specifications and window functions for OVER clauses
+
+use super::common::{
+    IntoColumnRef, IntoColumns, OrderByClause, OrderByNode, RenderContext, SortDirection,
+    render_order_by,
+};
+use super::raw::Raw;
+use crate::{Error, Result, Value};
+
+/// A function that can only be used with an OVER clause
+///
+/// Created through [`ColumnSelector::row_number`](crate::ColumnSelector::row_number)
+/// and friends. Aggregates and raw expressions can also be windowed with
+/// [`ColumnSelector::over`](crate::ColumnSelector::over).
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowFunction {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag {
+        column: String,
+        offset: u64,
+        default: Option<Value>,
+    },
+    Lead {
+        column: String,
+        offset: u64,
+        default: Option<Value>,
+    },
+}
+
+impl WindowFunction {
+    /// Render the function call, binding the default value if there is one
+    pub(crate) fn render(&self, ctx: &mut RenderContext) -> String {
+        match self {
+            WindowFunction::RowNumber => "ROW_NUMBER()".to_string(),
+            WindowFunction::Rank => "RANK()".to_string(),
+            WindowFunction::DenseRank => "DENSE_RANK()".to_string(),
+            WindowFunction::Lag {
+                column,
+                offset,
+                default,
+            } => render_offset_function("LAG", column, *offset, default.as_ref(), ctx),
+            WindowFunction::Lead {
+                column,
+                offset,
+                default,
+            } => render_offset_function("LEAD", column, *offset, default.as_ref(), ctx),
+        }
+    }
+}
+
+fn render_offset_function(
+    name: &str,
+    column: &str,
+    offset: u64,
+    default: Option<&Value>,
+    ctx: &mut RenderContext,
+) -> String {
+    match default {
+        Some(value) => format!(
+            "{}({}, {}, {})",
+            name,
+            ctx.ident(column),
+            offset,
+            ctx.bind(value)
+        ),
+        None => format!("{}({}, {})", name, ctx.ident(column), offset),
+    }
+}
+
+/// One end of a window frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
+impl FrameBound {
+    /// Position of the bound, used to reject frames that end before they start
+    fn rank(&self) -> u8 {
+        match self {
+            FrameBound::UnboundedPreceding => 0,
+            FrameBound::Preceding(_) => 1,
+            FrameBound::CurrentRow => 2,
+            FrameBound::Following(_) => 3,
+            FrameBound::UnboundedFollowing => 4,
+        }
+    }
+}
+
+impl std::fmt::Display for FrameBound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrameBound::UnboundedPreceding => write!(f, "UNBOUNDED PRECEDING"),
+            FrameBound::Preceding(n) => write!(f, "{} PRECEDING", n),
+            FrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            FrameBound::Following(n) => write!(f, "{} FOLLOWING", n),
+            FrameBound::UnboundedFollowing => write!(f, "UNBOUNDED FOLLOWING"),
+        }
+    }
+}
+
+/// Whether a frame counts rows or ranges of ORDER BY values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameUnits {
+    Rows,
+    Range,
+}
+
+impl std::fmt::Display for FrameUnits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrameUnits::Rows => write!(f, "ROWS"),
+            FrameUnits::Range => write!(f, "RANGE"),
+        }
+    }
+}
+
+/// A window frame: `ROWS|RANGE BETWEEN start AND end`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowFrame {
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    pub end: FrameBound,
+}
+
+/// A window specification: the part of an OVER clause inside the parentheses
+///
+/// Built with closures passed to [`ColumnSelector::over`](crate::ColumnSelector::over)
+/// and [`SelectBuilderComplete::window`](crate::SelectBuilderComplete::window).
+/// A window can start from a named window with [`Window::based_on`].
+///
+/// # Examples
+/// ```
+/// use archibald::{from, ColumnSelector, FrameBound, QueryBuilder};
+///
+/// let query = from("payments").select((
+///     "account_id",
+///     "amount",
+///     ColumnSelector::sum("amount")
+///         .over(|w| {
+///             w.partition_by("account_id")
+///                 .order_by_asc("paid_at")
+///                 .rows_between(FrameBound::UnboundedPreceding, FrameBound::CurrentRow)
+///         })
+///         .as_alias("balance"),
+/// ));
+/// assert_eq!(
+///     query.to_sql().unwrap(),
+///     "SELECT account_id, amount, SUM(amount) OVER (PARTITION BY account_id ORDER BY paid_at ASC ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS balance FROM payments"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Window {
+    pub base: Option<String>,
+    pub partition_by: Vec<String>,
+    pub order_by: Vec<OrderByNode>,
+    pub frame: Option<WindowFrame>,
+}
+
+impl Window {
+    /// Create an empty window specification, covering all rows
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from a named window defined with
+    /// [`SelectBuilderComplete::window`](crate::SelectBuilderComplete::window)
+    pub fn based_on(mut self, name: &str) -> Self {
+        self.base = Some(name.to_string());
+        self
+    }
+
+    /// Add PARTITION BY columns
+    pub fn partition_by<C>(mut self, columns: C) -> Self
+    where
+        C: IntoColumns,
+    {
+        self.partition_by.extend(columns.into_columns());
+        self
+    }
+
+    /// Add an ORDER BY column within each partition
+    pub fn order_by<C>(mut self, column: C, direction: SortDirection) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        self.order_by.push(OrderByNode::Clause(OrderByClause {
+            column: column.into_column_ref(),
+            direction,
+        }));
+        self
+    }
+
+    /// Add an ascending ORDER BY column within each partition
+    pub fn order_by_asc<C>(self, column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        self.order_by(column, SortDirection::Asc)
+    }
+
+    /// Add a descending ORDER BY column within each partition
+    pub fn order_by_desc<C>(self, column: C) -> Self
+    where
+        C: IntoColumnRef,
+    {
+        self.order_by(column, SortDirection::Desc)
+    }
+
+    /// Add a raw SQL ORDER BY expression, including its direction
+    pub fn order_by_raw<R>(mut self, raw: R) -> Self
+    where
+        R: Into<Raw>,
+    {
+        self.order_by.push(OrderByNode::Raw(raw.into()));
+        self
+    }
+
+    /// Set a `ROWS BETWEEN start AND end` frame
+    pub fn rows_between(mut self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame = Some(WindowFrame {
+            units: FrameUnits::Rows,
+            start,
+            end,
+        });
+        self
+    }
+
+    /// Set a `RANGE BETWEEN start AND end` frame
+    pub fn range_between(mut self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame = Some(WindowFrame {
+            units: FrameUnits::Range,
+            start,
+            end,
+        });
+        self
+    }
+
+    /// Whether the window is only a reference to a named window
+    fn is_reference(&self) -> bool {
+        self.base.is_some()
+            && self.partition_by.is_empty()
+            && self.order_by.is_empty()
+            && self.frame.is_none()
+    }
+
+    /// Render the specification without the surrounding parentheses
+    pub(crate) fn render(&self, ctx: &mut RenderContext) -> Result<String> {
+        let mut parts = Vec::new();
+
+        if let Some(base) = &self.base {
+            parts.push(ctx.ident(base));
+        }
+
+        if !self.partition_by.is_empty() {
+            let columns: Vec<String> = self.partition_by.iter().map(|c| ctx.ident(c)).collect();
+            parts.push(format!("PARTITION BY {}", columns.join(", ")));
+        }
+
+        if !self.order_by.is_empty() {
+            parts.push(format!(
+                "ORDER BY {}",
+                render_order_by(&self.order_by, ctx)?
+            ));
+        }
+
+        if let Some(frame) = &self.frame {
+            if frame.start == FrameBound::UnboundedFollowing
+                || frame.end == FrameBound::UnboundedPreceding
+                || frame.start.rank() > frame.end.rank()
+            {
+                return Err(Error::invalid_query(format!(
+                    "window frame cannot start at {} and end at {}",
+                    frame.start, frame.end
+                )));
+            }
+            parts.push(format!(
+                "{} BETWEEN {} AND {}",
+                frame.units, frame.start, frame.end
+            ));
+        }
+
+        Ok(parts.join(" "))
+    }
+
+    /// Render an OVER clause for this window, starting with a space
+    ///
+    /// A bare reference to a named window renders as `OVER name`.
+    pub(crate) fn render_over(&self, ctx: &mut RenderContext) -> Result<String> {
+        if self.is_reference() {
+            return Ok(format!(" OVER {}", self.render(ctx)?));
+        }
+        Ok(format!(" OVER ({})", self.render(ctx)?))
+    }
+}
+
+/// Render a WINDOW clause (without the keyword) for named windows
+pub(crate) fn render_windows(
+    windows: &[(String, Window)],
+    ctx: &mut RenderContext,
+) -> Result<String> {
+    let mut parts = Vec::with_capacity(windows.len());
+    for (i, (name, window)) in windows.iter().enumerate() {
+        if windows[..i].iter().any(|(other, _)| other == name) {
+            return Err(Error::invalid_query(format!(
+                "WINDOW clause defines `{}` more than once",
+                name
+            )));
+        }
+        parts.push(format!("{} AS ({})", ctx.ident(name), window.render(ctx)?));
+    }
+    Ok(parts.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{GenericDialect, PostgresDialect};
+
+    #[test]
+    fn test_window_rendering() {
+        let window = Window::new()
+            .partition_by(("team", "season"))
+            .order_by_desc("points")
+            .order_by_raw("LOWER(name)")
+            .range_between(FrameBound::Preceding(2), FrameBound::Following(1));
+
+        let mut ctx = RenderContext::new(&PostgresDialect);
+        assert_eq!(
+            window.render_over(&mut ctx).unwrap(),
+            r#" OVER (PARTITION BY "team", "season" ORDER BY "points" DESC, LOWER(name) RANGE BETWEEN 2 PRECEDING AND 1 FOLLOWING)"#
+        );
+    }
+
+    #[test]
+    fn test_named_window_references() {
+        let mut ctx = RenderContext::new(&GenericDialect);
+        assert_eq!(Window::new().render_over(&mut ctx).unwrap(), " OVER ()");
+        assert_eq!(
+            Window::new().based_on("w").render_over(&mut ctx).unwrap(),
+            " OVER w"
+        );
+        assert_eq!(
+            Window::new()
+                .based_on("w")
+                .order_by_asc("id")
+                .render_over(&mut ctx)
+                .unwrap(),
+            " OVER (w ORDER BY id ASC)"
+        );
+    }
+
+    #[test]
+    fn test_backwards_frames_are_rejected() {
+        let mut ctx = RenderContext::new(&GenericDialect);
+        let backwards =
+            Window::new().rows_between(FrameBound::CurrentRow, FrameBound::Preceding(1));
+        assert!(backwards.render(&mut ctx).is_err());
+
+        let end = FrameBound::UnboundedFollowing;
+        let unbounded = Window::new().rows_between(FrameBound::UnboundedFollowing, end);
+        assert!(unbounded.render(&mut ctx).is_err());
+    }
+
+    #[test]
+    fn test_lag_lead_bind_defaults() {
+        let mut ctx = RenderContext::new(&PostgresDialect);
+        let lag = WindowFunction::Lag {
+            column: "price".to_string(),
+            offset: 1,
+            default: None,
+        };
+        let lead = WindowFunction::Lead {
+            column: "price".to_string(),
+            offset: 2,
+            default: Some(Value::I32(0)),
+        };
+
+        assert_eq!(lag.render(&mut ctx), r#"LAG("price", 1)"#);
+        assert_eq!(lead.render(&mut ctx), r#"LEAD("price", 2, $1)"#);
+        assert_eq!(ctx.into_params(), vec![Value::I32(0)]);
+    }
+}
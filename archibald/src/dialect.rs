@@ -0,0 +1,451 @@
+//! SQL dialects controlling database-specific rendering
+//!
+//! Query builders render SQL through a [`Dialect`], which decides the placeholder
+//! style, how identifiers are quoted, how LIMIT/OFFSET is spelled and how boolean
+//! literals are written. [`QueryBuilder::to_sql`](crate::QueryBuilder::to_sql) uses
+//! [`GenericDialect`]; connection pools pick the dialect matching their backend.
+
+use crate::schema::ColumnType;
+use std::fmt::Debug;
+
+/// Database-specific SQL rendering rules
+pub trait Dialect: Debug + Send + Sync {
+    /// Name of the dialect (e.g. "postgres")
+    fn name(&self) -> &'static str;
+
+    /// Placeholder for the parameter at the given 1-based position
+    fn placeholder(&self, index: usize) -> String;
+
+    /// Quote a single identifier (table, column or alias name)
+    fn quote_identifier(&self, ident: &str) -> String;
+
+    /// Render the LIMIT/OFFSET tail of a SELECT, including the leading space
+    fn limit_offset(&self, limit: Option<u64>, offset: Option<u64>) -> String {
+        let mut sql = String::new();
+        if let Some(limit) = limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+        if let Some(offset) = offset {
+            sql.push_str(&format!(" OFFSET {}", offset));
+        }
+        sql
+    }
+
+    /// Whether a list of values can be bound as a single array parameter
+    /// (rendering `col = ANY($1)` instead of `col IN ($1, $2, ...)`)
+    fn supports_array_parameters(&self) -> bool {
+        false
+    }
+
+    /// Whether INSERT, UPDATE and DELETE accept a RETURNING clause
+    fn supports_returning(&self) -> bool {
+        true
+    }
+
+    /// Syntax used to resolve INSERT conflicts (upserts)
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        UpsertSyntax::OnConflict
+    }
+
+    /// Syntax used for UPDATE and DELETE statements that read other tables
+    fn multi_table_syntax(&self) -> MultiTableSyntax {
+        MultiTableSyntax::FromUsing
+    }
+
+    /// Whether DELETE can name other tables to filter on (`DELETE ... USING`,
+    /// or a multi-table DELETE on MySQL)
+    fn supports_delete_using(&self) -> bool {
+        true
+    }
+
+    /// Whether the queries combined by UNION, INTERSECT and EXCEPT may be
+    /// parenthesized, which is needed to give one its own ORDER BY or LIMIT
+    fn supports_parenthesized_set_operands(&self) -> bool {
+        true
+    }
+
+    /// Whether an INSERT can be preceded by a WITH clause
+    ///
+    /// MySQL only accepts WITH on the SELECT of an `INSERT ... SELECT`.
+    fn supports_insert_with(&self) -> bool {
+        true
+    }
+
+    /// Whether a derived table can be LATERAL, referring to the tables before
+    /// it in the FROM clause
+    fn supports_lateral(&self) -> bool {
+        true
+    }
+
+    /// Most parameters a single statement may bind, if the database limits it
+    ///
+    /// Multi-row INSERTs are split into several statements to stay under it.
+    fn max_parameters(&self) -> Option<usize> {
+        None
+    }
+
+    /// Whether the SELECT of an `INSERT ... SELECT` needs a WHERE clause to
+    /// be followed by an upsert clause
+    ///
+    /// SQLite would otherwise parse `ON CONFLICT` as a join constraint.
+    fn upsert_select_requires_where(&self) -> bool {
+        false
+    }
+
+    /// Concrete SQL type for a schema column type
+    ///
+    /// Auto-incrementing types render together with their PRIMARY KEY
+    /// constraint, since some databases only accept the two in combination.
+    fn column_type(&self, column_type: &ColumnType) -> String {
+        standard_column_type(column_type)
+    }
+
+    /// Whether ALTER TABLE can add primary key and foreign key constraints
+    fn supports_alter_constraints(&self) -> bool {
+        true
+    }
+
+    /// Whether DROP INDEX needs an `ON <table>` clause
+    ///
+    /// MySQL scopes index names to their table rather than the schema.
+    fn drop_index_requires_table(&self) -> bool {
+        false
+    }
+
+    /// Literal used for a boolean constant
+    fn boolean_literal(&self, value: bool) -> &'static str {
+        if value { "TRUE" } else { "FALSE" }
+    }
+
+    /// Quote a possibly qualified reference such as `users.id` or `users.*`
+    ///
+    /// Anything that isn't a plain (optionally dotted) identifier, such as
+    /// `COUNT(*)` or `1`, is passed through untouched.
+    fn quote_reference(&self, reference: &str) -> String {
+        let parts: Vec<&str> = reference.split('.').collect();
+        let last = parts.len() - 1;
+        let simple = parts.iter().enumerate().all(|(i, part)| {
+            is_plain_identifier(part) || (i == last && i > 0 && *part == "*")
+        });
+
+        if !simple {
+            return reference.to_string();
+        }
+
+        parts
+            .iter()
+            .map(|part| {
+                if *part == "*" {
+                    part.to_string()
+                } else {
+                    self.quote_identifier(part)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+}
+
+/// How a dialect spells conflict handling for INSERT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertSyntax {
+    /// `ON CONFLICT (...) DO NOTHING` / `DO UPDATE SET ...` (PostgreSQL, SQLite)
+    OnConflict,
+    /// `ON DUPLICATE KEY UPDATE ...` (MySQL)
+    OnDuplicateKeyUpdate,
+}
+
+/// How a dialect spells UPDATE and DELETE statements that read other tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiTableSyntax {
+    /// `UPDATE t SET ... FROM other` / `DELETE FROM t USING other`
+    /// (PostgreSQL, SQLite 3.33+ for UPDATE)
+    FromUsing,
+    /// `UPDATE t INNER JOIN other SET ...` / `DELETE t FROM t INNER JOIN other`
+    /// (MySQL)
+    Joins,
+}
+
+/// Standard SQL spelling of the portable column types
+fn standard_column_type(column_type: &ColumnType) -> String {
+    match column_type {
+        ColumnType::Increments => {
+            "INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY".to_string()
+        }
+        ColumnType::BigIncrements => {
+            "BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY".to_string()
+        }
+        ColumnType::SmallInteger => "SMALLINT".to_string(),
+        ColumnType::Integer => "INTEGER".to_string(),
+        ColumnType::BigInteger => "BIGINT".to_string(),
+        ColumnType::String(length) => format!("VARCHAR({})", length),
+        ColumnType::Text => "TEXT".to_string(),
+        ColumnType::Boolean => "BOOLEAN".to_string(),
+        ColumnType::Float => "REAL".to_string(),
+        ColumnType::Double => "DOUBLE PRECISION".to_string(),
+        ColumnType::Decimal(precision, scale) => format!("DECIMAL({}, {})", precision, scale),
+        ColumnType::Date => "DATE".to_string(),
+        ColumnType::Time => "TIME".to_string(),
+        ColumnType::Timestamp => "TIMESTAMP".to_string(),
+        ColumnType::TimestampTz => "TIMESTAMP WITH TIME ZONE".to_string(),
+        ColumnType::Json | ColumnType::Jsonb => "JSON".to_string(),
+        ColumnType::Binary => "BLOB".to_string(),
+        ColumnType::Uuid => "UUID".to_string(),
+        ColumnType::Custom(name) => name.clone(),
+    }
+}
+
+fn is_plain_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn quote_with(ident: &str, quote: char) -> String {
+    let escaped = ident.replace(quote, &format!("{}{}", quote, quote));
+    format!("{}{}{}", quote, escaped, quote)
+}
+
+/// Dialect-neutral rendering: `?` placeholders and unquoted identifiers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GenericDialect;
+
+impl Dialect for GenericDialect {
+    fn name(&self) -> &'static str {
+        "generic"
+    }
+
+    fn placeholder(&self, _index: usize) -> String {
+        "?".to_string()
+    }
+
+    fn quote_identifier(&self, ident: &str) -> String {
+        ident.to_string()
+    }
+}
+
+/// PostgreSQL: `$1, $2, ...` placeholders and double-quoted identifiers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PostgresDialect;
+
+impl Dialect for PostgresDialect {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        format!("${}", index)
+    }
+
+    fn quote_identifier(&self, ident: &str) -> String {
+        quote_with(ident, '"')
+    }
+
+    fn supports_array_parameters(&self) -> bool {
+        true
+    }
+
+    fn max_parameters(&self) -> Option<usize> {
+        Some(65535)
+    }
+
+    fn column_type(&self, column_type: &ColumnType) -> String {
+        match column_type {
+            ColumnType::Increments => "SERIAL PRIMARY KEY".to_string(),
+            ColumnType::BigIncrements => "BIGSERIAL PRIMARY KEY".to_string(),
+            ColumnType::TimestampTz => "TIMESTAMPTZ".to_string(),
+            ColumnType::Jsonb => "JSONB".to_string(),
+            ColumnType::Binary => "BYTEA".to_string(),
+            other => standard_column_type(other),
+        }
+    }
+}
+
+/// SQLite: `?` placeholders, double-quoted identifiers and `1`/`0` booleans
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SqliteDialect;
+
+impl Dialect for SqliteDialect {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn placeholder(&self, _index: usize) -> String {
+        "?".to_string()
+    }
+
+    fn quote_identifier(&self, ident: &str) -> String {
+        quote_with(ident, '"')
+    }
+
+    fn limit_offset(&self, limit: Option<u64>, offset: Option<u64>) -> String {
+        match (limit, offset) {
+            (Some(limit), Some(offset)) => format!(" LIMIT {} OFFSET {}", limit, offset),
+            (Some(limit), None) => format!(" LIMIT {}", limit),
+            // SQLite only accepts OFFSET after a LIMIT; -1 means "no limit"
+            (None, Some(offset)) => format!(" LIMIT -1 OFFSET {}", offset),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn boolean_literal(&self, value: bool) -> &'static str {
+        if value { "1" } else { "0" }
+    }
+
+    fn column_type(&self, column_type: &ColumnType) -> String {
+        match column_type {
+            // Only INTEGER PRIMARY KEY aliases the rowid, whatever the width
+            ColumnType::Increments | ColumnType::BigIncrements => {
+                "INTEGER PRIMARY KEY AUTOINCREMENT".to_string()
+            }
+            ColumnType::TimestampTz => "TIMESTAMP".to_string(),
+            ColumnType::Json | ColumnType::Jsonb | ColumnType::Uuid => "TEXT".to_string(),
+            other => standard_column_type(other),
+        }
+    }
+
+    fn supports_alter_constraints(&self) -> bool {
+        false
+    }
+
+    fn supports_parenthesized_set_operands(&self) -> bool {
+        false
+    }
+
+    fn supports_lateral(&self) -> bool {
+        false
+    }
+
+    fn upsert_select_requires_where(&self) -> bool {
+        true
+    }
+
+    fn supports_delete_using(&self) -> bool {
+        false
+    }
+
+    fn max_parameters(&self) -> Option<usize> {
+        // SQLITE_MAX_VARIABLE_NUMBER since SQLite 3.32
+        Some(32766)
+    }
+}
+
+/// MySQL: `?` placeholders and backtick-quoted identifiers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MySqlDialect;
+
+impl Dialect for MySqlDialect {
+    fn name(&self) -> &'static str {
+        "mysql"
+    }
+
+    fn placeholder(&self, _index: usize) -> String {
+        "?".to_string()
+    }
+
+    fn quote_identifier(&self, ident: &str) -> String {
+        quote_with(ident, '`')
+    }
+
+    fn supports_returning(&self) -> bool {
+        false
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        UpsertSyntax::OnDuplicateKeyUpdate
+    }
+
+    fn multi_table_syntax(&self) -> MultiTableSyntax {
+        MultiTableSyntax::Joins
+    }
+
+    fn supports_insert_with(&self) -> bool {
+        false
+    }
+
+    fn max_parameters(&self) -> Option<usize> {
+        Some(65535)
+    }
+
+    fn drop_index_requires_table(&self) -> bool {
+        true
+    }
+
+    fn column_type(&self, column_type: &ColumnType) -> String {
+        match column_type {
+            ColumnType::Increments => "INT UNSIGNED AUTO_INCREMENT PRIMARY KEY".to_string(),
+            ColumnType::BigIncrements => "BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY".to_string(),
+            ColumnType::Integer => "INT".to_string(),
+            ColumnType::Double => "DOUBLE".to_string(),
+            ColumnType::TimestampTz => "TIMESTAMP".to_string(),
+            ColumnType::Jsonb => "JSON".to_string(),
+            ColumnType::Uuid => "CHAR(36)".to_string(),
+            other => standard_column_type(other),
+        }
+    }
+
+    fn limit_offset(&self, limit: Option<u64>, offset: Option<u64>) -> String {
+        match (limit, offset) {
+            (Some(limit), Some(offset)) => format!(" LIMIT {} OFFSET {}", limit, offset),
+            (Some(limit), None) => format!(" LIMIT {}", limit),
+            // MySQL requires a LIMIT before OFFSET; use the documented maximum
+            (None, Some(offset)) => format!(" LIMIT 18446744073709551615 OFFSET {}", offset),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(GenericDialect.placeholder(3), "?");
+        assert_eq!(PostgresDialect.placeholder(3), "$3");
+        assert_eq!(SqliteDialect.placeholder(3), "?");
+        assert_eq!(MySqlDialect.placeholder(3), "?");
+    }
+
+    #[test]
+    fn test_quote_reference() {
+        assert_eq!(PostgresDialect.quote_reference("users.id"), "\"users\".\"id\"");
+        assert_eq!(PostgresDialect.quote_reference("users.*"), "\"users\".*");
+        assert_eq!(MySqlDialect.quote_reference("name"), "`name`");
+        assert_eq!(GenericDialect.quote_reference("users.id"), "users.id");
+    }
+
+    #[test]
+    fn test_quote_reference_passes_expressions_through() {
+        assert_eq!(PostgresDialect.quote_reference("*"), "*");
+        assert_eq!(PostgresDialect.quote_reference("COUNT(*)"), "COUNT(*)");
+        assert_eq!(PostgresDialect.quote_reference("1"), "1");
+        assert_eq!(PostgresDialect.quote_reference("a, b"), "a, b");
+    }
+
+    #[test]
+    fn test_quote_identifier_escapes_quotes() {
+        assert_eq!(PostgresDialect.quote_identifier("we\"ird"), "\"we\"\"ird\"");
+        assert_eq!(MySqlDialect.quote_identifier("we`ird"), "`we``ird`");
+    }
+
+    #[test]
+    fn test_limit_offset() {
+        assert_eq!(PostgresDialect.limit_offset(None, Some(5)), " OFFSET 5");
+        assert_eq!(SqliteDialect.limit_offset(None, Some(5)), " LIMIT -1 OFFSET 5");
+        assert_eq!(
+            MySqlDialect.limit_offset(None, Some(5)),
+            " LIMIT 18446744073709551615 OFFSET 5"
+        );
+        assert_eq!(GenericDialect.limit_offset(Some(10), Some(5)), " LIMIT 10 OFFSET 5");
+    }
+
+    #[test]
+    fn test_boolean_literals() {
+        assert_eq!(PostgresDialect.boolean_literal(true), "TRUE");
+        assert_eq!(SqliteDialect.boolean_literal(false), "0");
+    }
+}
@@ -28,6 +28,10 @@ pub enum Error {
     /// Table not found error
     #[error("Table '{table}' not found")]
     TableNotFound { table: String },
+
+    /// A result row couldn't be decoded into the requested type
+    #[error("Decode error: {message}")]
+    Decode { message: String },
 }
 
 /// Convenience Result type for Archibald operations
@@ -62,6 +66,20 @@ impl Error {
             table: table.into(),
         }
     }
+
+    /// Create a new decode error
+    pub fn decode(message: impl Into<String>) -> Self {
+        Self::Decode {
+            message: message.into(),
+        }
+    }
+}
+
+// Lets rows be decoded by a serde `Deserializer` that reports archibald errors
+impl serde::de::Error for Error {
+    fn custom<T: std::fmt::Display>(message: T) -> Self {
+        Self::decode(message.to_string())
+    }
 }
 
 #[cfg(test)]
@@ -98,4 +116,11 @@ mod tests {
         assert!(matches!(err, Error::TableNotFound { .. }));
         assert_eq!(err.to_string(), "Table 'non_existent_table' not found");
     }
+
+    #[test]
+    fn test_decode_error() {
+        let err = <Error as serde::de::Error>::missing_field("email");
+        assert!(matches!(err, Error::Decode { .. }));
+        assert_eq!(err.to_string(), "Decode error: missing field `email`");
+    }
 }
@@ -1,21 +1,55 @@
 //! Query execution and connection pool interface
 
 use crate::builder::common::QueryBuilder;
+use crate::dialect::{Dialect, GenericDialect};
 use crate::{Result, Value};
+use futures::{Stream, StreamExt, TryStreamExt};
 use serde::de::DeserializeOwned;
 use std::future::Future;
 
+pub mod row;
+
+pub use row::{FromRow, Row};
+
 /// Trait for database connection pools
 pub trait ConnectionPool: Send + Sync + Clone {
     /// The connection type for this pool
     type Connection;
 
+    /// The SQL dialect queries are rendered with for this pool
+    fn dialect(&self) -> &'static dyn Dialect {
+        &GenericDialect
+    }
+
     /// Acquire a connection from the pool
     fn acquire(&self) -> impl Future<Output = Result<Self::Connection>> + Send;
 
     /// Execute a query that returns no results (INSERT, UPDATE, DELETE)
     fn execute(&self, sql: &str, params: &[Value]) -> impl Future<Output = Result<u64>> + Send;
 
+    /// Execute several statements as one unit, returning the total number of
+    /// affected rows
+    ///
+    /// Used for INSERTs split into chunks. Either every statement applies or
+    /// none does: the built-in pools run them in a transaction, while the
+    /// default only runs a single statement and returns an
+    /// [`InvalidQuery`](crate::Error::InvalidQuery) error for more than one.
+    fn execute_all(
+        &self,
+        statements: &[(String, Vec<Value>)],
+    ) -> impl Future<Output = Result<u64>> + Send {
+        async move {
+            match statements {
+                [] => Ok(0),
+                [(sql, params)] => self.execute(sql, params).await,
+                _ => Err(crate::Error::invalid_query(
+                    "This pool cannot run several statements atomically; \
+                     implement ConnectionPool::execute_all",
+                )),
+            }
+        }
+    }
+
     /// Execute a query that returns multiple rows
     fn fetch_all<T>(
         &self,
@@ -38,6 +72,45 @@ pub trait ConnectionPool: Send + Sync + Clone {
     ) -> impl Future<Output = Result<Option<T>>> + Send
     where
         T: DeserializeOwned + Send + Unpin;
+
+    /// Execute a query and stream its rows, decoding one row at a time
+    ///
+    /// Rows are only read as the stream is polled, and dropping the stream
+    /// cancels the query. The default implementation buffers every row with
+    /// [`fetch_all`](ConnectionPool::fetch_all); the built-in pools stream
+    /// natively.
+    fn fetch_stream<'a, T>(
+        &'a self,
+        sql: &'a str,
+        params: &'a [Value],
+    ) -> impl Stream<Item = Result<T>> + Send + 'a
+    where
+        T: DeserializeOwned + Send + Unpin + 'a,
+    {
+        futures::stream::once(self.fetch_all(sql, params))
+            .map_ok(|rows| futures::stream::iter(rows.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Execute a query that returns multiple rows, decoded with [`FromRow`]
+    ///
+    /// The default implementation fetches each row as a JSON object, so
+    /// columns can only be looked up by name; the built-in pools read them
+    /// straight from the driver.
+    fn fetch_all_as<T>(
+        &self,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Result<Vec<T>>> + Send
+    where
+        T: FromRow + Send,
+    {
+        async move {
+            let rows: Vec<serde_json::Map<String, serde_json::Value>> =
+                self.fetch_all(sql, params).await?;
+            rows.iter().map(|row| T::from_row(&Row::new(row))).collect()
+        }
+    }
 }
 
 /// Transaction isolation levels
@@ -62,6 +135,11 @@ impl IsolationLevel {
 
 /// Trait for database transactions
 pub trait Transaction: Send {
+    /// The SQL dialect queries are rendered with for this transaction
+    fn dialect(&self) -> &'static dyn Dialect {
+        &GenericDialect
+    }
+
     /// Execute a query that returns no results (INSERT, UPDATE, DELETE)
     fn execute(&mut self, sql: &str, params: &[Value]) -> impl Future<Output = Result<u64>> + Send;
 
@@ -92,6 +170,42 @@ pub trait Transaction: Send {
     where
         T: DeserializeOwned + Send + Unpin;
 
+    /// Execute a query and stream its rows, decoding one row at a time
+    ///
+    /// The transaction is borrowed until the stream is dropped. Like
+    /// [`ConnectionPool::fetch_stream`], the default implementation buffers
+    /// every row.
+    fn fetch_stream<'a, T>(
+        &'a mut self,
+        sql: &'a str,
+        params: &'a [Value],
+    ) -> impl Stream<Item = Result<T>> + Send + 'a
+    where
+        T: DeserializeOwned + Send + Unpin + 'a,
+    {
+        futures::stream::once(self.fetch_all(sql, params))
+            .map_ok(|rows| futures::stream::iter(rows.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Execute a query that returns multiple rows, decoded with [`FromRow`]
+    ///
+    /// See [`ConnectionPool::fetch_all_as`].
+    fn fetch_all_as<T>(
+        &mut self,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Result<Vec<T>>> + Send
+    where
+        T: FromRow + Send,
+    {
+        async move {
+            let rows: Vec<serde_json::Map<String, serde_json::Value>> =
+                self.fetch_all(sql, params).await?;
+            rows.iter().map(|row| T::from_row(&Row::new(row))).collect()
+        }
+    }
+
     /// Commit the transaction
     fn commit(self) -> impl Future<Output = Result<()>> + Send
     where
@@ -148,183 +262,301 @@ where
     }
 }
 
+/// Run statements in a single transaction, returning the total number of
+/// affected rows
+///
+/// Rolls back and returns the error if any statement fails.
+#[cfg(any(feature = "postgres", feature = "sqlite", feature = "mysql"))]
+async fn execute_all_in_transaction<P>(
+    pool: &P,
+    statements: &[(String, Vec<Value>)],
+) -> Result<u64>
+where
+    P: TransactionalPool,
+{
+    let mut txn = pool.begin_transaction().await?;
+    let mut total = 0;
+    for (sql, params) in statements {
+        match txn.execute(sql, params).await {
+            Ok(affected) => total += affected,
+            Err(e) => {
+                let _ = txn.rollback().await; // Ignore rollback errors
+                return Err(e);
+            }
+        }
+    }
+    txn.commit().await?;
+    Ok(total)
+}
+
 /// Extension trait for query builders to add execution methods
-pub trait ExecutableQuery<T>: QueryBuilder {
+pub trait ExecutableQuery<T>: QueryBuilder + Sized + Send {
     /// Execute the query and return all results
     fn fetch_all<P>(self, pool: &P) -> impl Future<Output = Result<Vec<T>>> + Send
     where
         P: ConnectionPool,
-        T: DeserializeOwned + Send + Unpin;
+        T: DeserializeOwned + Send + Unpin,
+    {
+        async move {
+            let (sql, params) = self.to_sql_with_params(pool.dialect())?;
+            pool.fetch_all(&sql, &params).await
+        }
+    }
 
     /// Execute the query and return the first result
     fn fetch_one<P>(self, pool: &P) -> impl Future<Output = Result<T>> + Send
     where
         P: ConnectionPool,
-        T: DeserializeOwned + Send + Unpin;
+        T: DeserializeOwned + Send + Unpin,
+    {
+        async move {
+            let (sql, params) = self.to_sql_with_params(pool.dialect())?;
+            pool.fetch_one(&sql, &params).await
+        }
+    }
 
     /// Execute the query and return an optional result
     fn fetch_optional<P>(self, pool: &P) -> impl Future<Output = Result<Option<T>>> + Send
     where
         P: ConnectionPool,
-        T: DeserializeOwned + Send + Unpin;
+        T: DeserializeOwned + Send + Unpin,
+    {
+        async move {
+            let (sql, params) = self.to_sql_with_params(pool.dialect())?;
+            pool.fetch_optional(&sql, &params).await
+        }
+    }
 
     /// Execute the query within a transaction and return all results
     fn fetch_all_tx<Tx>(self, tx: &mut Tx) -> impl Future<Output = Result<Vec<T>>> + Send
     where
         Tx: Transaction,
-        T: DeserializeOwned + Send + Unpin;
+        T: DeserializeOwned + Send + Unpin,
+    {
+        async move {
+            let (sql, params) = self.to_sql_with_params(tx.dialect())?;
+            tx.fetch_all(&sql, &params).await
+        }
+    }
 
     /// Execute the query within a transaction and return the first result
     fn fetch_one_tx<Tx>(self, tx: &mut Tx) -> impl Future<Output = Result<T>> + Send
     where
         Tx: Transaction,
-        T: DeserializeOwned + Send + Unpin;
+        T: DeserializeOwned + Send + Unpin,
+    {
+        async move {
+            let (sql, params) = self.to_sql_with_params(tx.dialect())?;
+            tx.fetch_one(&sql, &params).await
+        }
+    }
 
     /// Execute the query within a transaction and return an optional result
     fn fetch_optional_tx<Tx>(self, tx: &mut Tx) -> impl Future<Output = Result<Option<T>>> + Send
     where
         Tx: Transaction,
-        T: DeserializeOwned + Send + Unpin;
-}
-
-/// Extension trait for modification queries (INSERT, UPDATE, DELETE)
-pub trait ExecutableModification: QueryBuilder {
-    /// Execute the modification query and return the number of affected rows
-    fn execute<P>(self, pool: &P) -> impl Future<Output = Result<u64>> + Send
-    where
-        P: ConnectionPool;
-
-    /// Execute the modification query within a transaction and return the number of affected rows
-    fn execute_tx<Tx>(self, tx: &mut Tx) -> impl Future<Output = Result<u64>> + Send
-    where
-        Tx: Transaction;
-}
+        T: DeserializeOwned + Send + Unpin,
+    {
+        async move {
+            let (sql, params) = self.to_sql_with_params(tx.dialect())?;
+            tx.fetch_optional(&sql, &params).await
+        }
+    }
 
-impl<T> ExecutableQuery<T> for crate::builder::select::SelectBuilderComplete
-where
-    T: DeserializeOwned + Send + Unpin,
-{
-    async fn fetch_all<P>(self, pool: &P) -> Result<Vec<T>>
+    /// Execute the query and stream the results, decoding one row at a time
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[cfg(feature = "sqlite")]
+    /// # async fn example() -> archibald::Result<()> {
+    /// use archibald::executor::sqlite::SqlitePool;
+    /// use archibald::{from, ExecutableQuery};
+    /// use futures::TryStreamExt;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Event {
+    ///     id: i64,
+    /// }
+    ///
+    /// let pool = SqlitePool::new("sqlite:events.db").await?;
+    /// let mut events = std::pin::pin!(from("events").select("id").fetch_stream(&pool));
+    /// while let Some(Event { id }) = events.try_next().await? {
+    ///     println!("{}", id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn fetch_stream<P>(self, pool: &P) -> impl Stream<Item = Result<T>> + Send
     where
         P: ConnectionPool,
+        T: DeserializeOwned + Send + Unpin,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        pool.fetch_all(&sql, params).await
+        let rendered = self.to_sql_with_params(pool.dialect());
+        async_stream::try_stream! {
+            let (sql, params) = rendered?;
+            let mut rows = std::pin::pin!(pool.fetch_stream(&sql, &params));
+            while let Some(row) = rows.next().await {
+                yield row?;
+            }
+        }
     }
 
-    async fn fetch_one<P>(self, pool: &P) -> Result<T>
+    /// Execute the query within a transaction and stream the results
+    fn fetch_stream_tx<Tx>(self, tx: &mut Tx) -> impl Stream<Item = Result<T>> + Send
     where
-        P: ConnectionPool,
+        Tx: Transaction,
+        T: DeserializeOwned + Send + Unpin,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        pool.fetch_one(&sql, params).await
+        let rendered = self.to_sql_with_params(tx.dialect());
+        async_stream::try_stream! {
+            let (sql, params) = rendered?;
+            let mut rows = std::pin::pin!(tx.fetch_stream(&sql, &params));
+            while let Some(row) = rows.next().await {
+                yield row?;
+            }
+        }
     }
 
-    async fn fetch_optional<P>(self, pool: &P) -> Result<Option<T>>
+    /// Execute the query and decode all results with [`FromRow`]
+    fn fetch_all_as<P>(self, pool: &P) -> impl Future<Output = Result<Vec<T>>> + Send
     where
         P: ConnectionPool,
+        T: FromRow + Send,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        pool.fetch_optional(&sql, params).await
+        async move {
+            let (sql, params) = self.to_sql_with_params(pool.dialect())?;
+            pool.fetch_all_as(&sql, &params).await
+        }
     }
 
-    async fn fetch_all_tx<Tx>(self, tx: &mut Tx) -> Result<Vec<T>>
+    /// Execute the query within a transaction and decode all results with [`FromRow`]
+    fn fetch_all_as_tx<Tx>(self, tx: &mut Tx) -> impl Future<Output = Result<Vec<T>>> + Send
     where
         Tx: Transaction,
+        T: FromRow + Send,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        tx.fetch_all(&sql, params).await
+        async move {
+            let (sql, params) = self.to_sql_with_params(tx.dialect())?;
+            tx.fetch_all_as(&sql, &params).await
+        }
     }
+}
+
+/// Extension trait for modification queries (INSERT, UPDATE, DELETE)
+pub trait ExecutableModification: QueryBuilder {
+    /// Execute the modification query and return the number of affected rows
+    fn execute<P>(self, pool: &P) -> impl Future<Output = Result<u64>> + Send
+    where
+        P: ConnectionPool;
 
-    async fn fetch_one_tx<Tx>(self, tx: &mut Tx) -> Result<T>
+    /// Execute the modification query within a transaction and return the number of affected rows
+    fn execute_tx<Tx>(self, tx: &mut Tx) -> impl Future<Output = Result<u64>> + Send
     where
-        Tx: Transaction,
+        Tx: Transaction;
+}
+
+impl<T> ExecutableQuery<T> for crate::builder::select::SelectBuilderComplete {}
+
+impl<Q, T> ExecutableQuery<T> for crate::builder::ReturningBuilder<Q> where
+    Q: QueryBuilder + Clone + Send
+{
+}
+
+impl<T> ExecutableQuery<T> for crate::builder::Raw {}
+
+impl<T> ExecutableQuery<T> for crate::builder::CompoundSelectBuilder {}
+
+impl ExecutableModification for crate::builder::InsertBuilderComplete {
+    async fn execute<P>(self, pool: &P) -> Result<u64>
+    where
+        P: ConnectionPool,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        tx.fetch_one(&sql, params).await
+        let mut statements = self.to_sql_chunks(pool.dialect())?;
+        if statements.len() > 1 {
+            return pool.execute_all(&statements).await;
+        }
+        let (sql, params) = statements.remove(0);
+        pool.execute(&sql, &params).await
     }
 
-    async fn fetch_optional_tx<Tx>(self, tx: &mut Tx) -> Result<Option<T>>
+    async fn execute_tx<Tx>(self, tx: &mut Tx) -> Result<u64>
     where
         Tx: Transaction,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        tx.fetch_optional(&sql, params).await
+        let mut total = 0;
+        for (sql, params) in self.to_sql_chunks(tx.dialect())? {
+            total += tx.execute(&sql, &params).await?;
+        }
+        Ok(total)
     }
 }
 
-impl ExecutableModification for crate::builder::InsertBuilderComplete {
+impl ExecutableModification for crate::builder::UpdateBuilderComplete {
     async fn execute<P>(self, pool: &P) -> Result<u64>
     where
         P: ConnectionPool,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        pool.execute(&sql, params).await
+        let (sql, params) = self.to_sql_with_params(pool.dialect())?;
+        pool.execute(&sql, &params).await
     }
 
     async fn execute_tx<Tx>(self, tx: &mut Tx) -> Result<u64>
     where
         Tx: Transaction,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        tx.execute(&sql, params).await
+        let (sql, params) = self.to_sql_with_params(tx.dialect())?;
+        tx.execute(&sql, &params).await
     }
 }
 
-impl ExecutableModification for crate::builder::UpdateBuilderComplete {
+impl ExecutableModification for crate::builder::DeleteBuilderComplete {
     async fn execute<P>(self, pool: &P) -> Result<u64>
     where
         P: ConnectionPool,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        pool.execute(&sql, params).await
+        let (sql, params) = self.to_sql_with_params(pool.dialect())?;
+        pool.execute(&sql, &params).await
     }
 
     async fn execute_tx<Tx>(self, tx: &mut Tx) -> Result<u64>
     where
         Tx: Transaction,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        tx.execute(&sql, params).await
+        let (sql, params) = self.to_sql_with_params(tx.dialect())?;
+        tx.execute(&sql, &params).await
     }
 }
 
-impl ExecutableModification for crate::builder::DeleteBuilderComplete {
+impl ExecutableModification for crate::builder::Raw {
     async fn execute<P>(self, pool: &P) -> Result<u64>
     where
         P: ConnectionPool,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        pool.execute(&sql, params).await
+        let (sql, params) = self.to_sql_with_params(pool.dialect())?;
+        pool.execute(&sql, &params).await
     }
 
     async fn execute_tx<Tx>(self, tx: &mut Tx) -> Result<u64>
     where
         Tx: Transaction,
     {
-        let sql = self.to_sql()?;
-        let params = self.parameters();
-        tx.execute(&sql, params).await
+        let (sql, params) = self.to_sql_with_params(tx.dialect())?;
+        tx.execute(&sql, &params).await
     }
 }
 
 /// PostgreSQL connection pool wrapper
 #[cfg(feature = "postgres")]
 pub mod postgres {
+    use super::row::{Field, RawRow};
     use super::*;
+    use crate::dialect::PostgresDialect;
     use sqlx::PgPool;
 
+    mod copy;
+
+    pub use copy::{BulkCopy, CopyFormat};
+
     /// PostgreSQL connection pool wrapper
     #[derive(Clone)]
     pub struct PostgresPool {
@@ -347,6 +579,10 @@ pub mod postgres {
     impl ConnectionPool for PostgresPool {
         type Connection = sqlx::pool::PoolConnection<sqlx::Postgres>;
 
+        fn dialect(&self) -> &'static dyn Dialect {
+            &PostgresDialect
+        }
+
         async fn acquire(&self) -> Result<Self::Connection> {
             Ok(self.inner.acquire().await?)
         }
@@ -358,6 +594,10 @@ pub mod postgres {
             Ok(result.rows_affected())
         }
 
+        async fn execute_all(&self, statements: &[(String, Vec<Value>)]) -> Result<u64> {
+            execute_all_in_transaction(self, statements).await
+        }
+
         async fn fetch_all<T>(&self, sql: &str, params: &[Value]) -> Result<Vec<T>>
         where
             T: DeserializeOwned + Send + Unpin,
@@ -368,8 +608,7 @@ pub mod postgres {
 
             let mut results = Vec::with_capacity(rows.len());
             for row in rows {
-                let json_value = row_to_json_value(&row)?;
-                let item: T = serde_json::from_value(json_value)?;
+                let item: T = Row::new(&row).deserialize()?;
                 results.push(item);
             }
             Ok(results)
@@ -383,8 +622,7 @@ pub mod postgres {
             let bound_query = bind_values_to_query(query, params);
             let row = bound_query.fetch_one(&self.inner).await?;
 
-            let json_value = row_to_json_value(&row)?;
-            let item: T = serde_json::from_value(json_value)?;
+            let item: T = Row::new(&row).deserialize()?;
             Ok(item)
         }
 
@@ -395,13 +633,37 @@ pub mod postgres {
             let query = sqlx::query(sql);
             let bound_query = bind_values_to_query(query, params);
             if let Some(row) = bound_query.fetch_optional(&self.inner).await? {
-                let json_value = row_to_json_value(&row)?;
-                let item: T = serde_json::from_value(json_value)?;
+                let item: T = Row::new(&row).deserialize()?;
                 Ok(Some(item))
             } else {
                 Ok(None)
             }
         }
+
+        fn fetch_stream<'a, T>(
+            &'a self,
+            sql: &'a str,
+            params: &'a [Value],
+        ) -> impl Stream<Item = Result<T>> + Send + 'a
+        where
+            T: DeserializeOwned + Send + Unpin + 'a,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            bound_query
+                .fetch(&self.inner)
+                .map(|row| Row::new(&row?).deserialize())
+        }
+
+        async fn fetch_all_as<T>(&self, sql: &str, params: &[Value]) -> Result<Vec<T>>
+        where
+            T: FromRow + Send,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let rows = bound_query.fetch_all(&self.inner).await?;
+            rows.iter().map(|row| T::from_row(&Row::new(row))).collect()
+        }
     }
 
     /// PostgreSQL transaction wrapper
@@ -410,6 +672,10 @@ pub mod postgres {
     }
 
     impl Transaction for PostgresTransaction {
+        fn dialect(&self) -> &'static dyn Dialect {
+            &PostgresDialect
+        }
+
         async fn execute(&mut self, sql: &str, params: &[Value]) -> Result<u64> {
             let query = sqlx::query(sql);
             let bound_query = bind_values_to_query(query, params);
@@ -427,8 +693,7 @@ pub mod postgres {
 
             let mut results = Vec::with_capacity(rows.len());
             for row in rows {
-                let json_value = row_to_json_value(&row)?;
-                let item: T = serde_json::from_value(json_value)?;
+                let item: T = Row::new(&row).deserialize()?;
                 results.push(item);
             }
             Ok(results)
@@ -442,8 +707,7 @@ pub mod postgres {
             let bound_query = bind_values_to_query(query, params);
             let row = bound_query.fetch_one(&mut *self.inner).await?;
 
-            let json_value = row_to_json_value(&row)?;
-            let item: T = serde_json::from_value(json_value)?;
+            let item: T = Row::new(&row).deserialize()?;
             Ok(item)
         }
 
@@ -454,14 +718,38 @@ pub mod postgres {
             let query = sqlx::query(sql);
             let bound_query = bind_values_to_query(query, params);
             if let Some(row) = bound_query.fetch_optional(&mut *self.inner).await? {
-                let json_value = row_to_json_value(&row)?;
-                let item: T = serde_json::from_value(json_value)?;
+                let item: T = Row::new(&row).deserialize()?;
                 Ok(Some(item))
             } else {
                 Ok(None)
             }
         }
 
+        fn fetch_stream<'a, T>(
+            &'a mut self,
+            sql: &'a str,
+            params: &'a [Value],
+        ) -> impl Stream<Item = Result<T>> + Send + 'a
+        where
+            T: DeserializeOwned + Send + Unpin + 'a,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            bound_query
+                .fetch(&mut *self.inner)
+                .map(|row| Row::new(&row?).deserialize())
+        }
+
+        async fn fetch_all_as<T>(&mut self, sql: &str, params: &[Value]) -> Result<Vec<T>>
+        where
+            T: FromRow + Send,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let rows = bound_query.fetch_all(&mut *self.inner).await?;
+            rows.iter().map(|row| T::from_row(&Row::new(row))).collect()
+        }
+
         async fn commit(self) -> Result<()> {
             self.inner.commit().await?;
             Ok(())
@@ -526,13 +814,28 @@ pub mod postgres {
                 Value::String(s) => query.bind(s.as_str()),
                 Value::Bytes(b) => query.bind(b.as_slice()),
                 Value::Json(j) => query.bind(j), // sqlx supports serde_json::Value directly
-                Value::Array(arr) => {
-                    // For arrays, we need to convert to a format that PostgreSQL understands
-                    // For now, serialize simple arrays to JSON
-                    let json_array =
-                        serde_json::Value::Array(arr.iter().map(value_to_json).collect());
-                    query.bind(json_array)
-                }
+                #[cfg(feature = "uuid-support")]
+                Value::Uuid(u) => query.bind(*u),
+                #[cfg(feature = "datetime-support")]
+                Value::Date(d) => query.bind(*d),
+                #[cfg(feature = "datetime-support")]
+                Value::Time(t) => query.bind(*t),
+                #[cfg(feature = "datetime-support")]
+                Value::Timestamp(ts) => query.bind(*ts),
+                #[cfg(feature = "datetime-support")]
+                Value::TimestampTz(ts) => query.bind(*ts),
+                #[cfg(feature = "decimal-support")]
+                Value::Decimal(d) => query.bind(*d),
+                Value::Array(arr) => match param.array_element_type() {
+                    // Homogeneous scalar arrays bind as native PostgreSQL arrays (e.g. for `= ANY($1)`)
+                    Some(_) => bind_native_array(query, arr),
+                    None => {
+                        // Mixed or nested arrays fall back to JSON
+                        let json_array =
+                            serde_json::Value::Array(arr.iter().map(value_to_json).collect());
+                        query.bind(json_array)
+                    }
+                },
                 Value::SubqueryPlaceholder => {
                     // Subqueries should have been resolved before this point
                     // This is likely a programming error
@@ -543,6 +846,80 @@ pub mod postgres {
         query
     }
 
+    /// Bind a homogeneous array of scalars as a native PostgreSQL array
+    fn bind_native_array<'q>(
+        query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+        arr: &[Value],
+    ) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+        fn collect<T>(arr: &[Value], f: impl Fn(&Value) -> Option<T>) -> Vec<T> {
+            arr.iter().filter_map(f).collect()
+        }
+
+        match arr.first() {
+            Some(Value::Bool(_)) => query.bind(collect(arr, |v| match v {
+                Value::Bool(b) => Some(*b),
+                _ => None,
+            })),
+            Some(Value::I32(_)) => query.bind(collect(arr, |v| match v {
+                Value::I32(i) => Some(*i),
+                _ => None,
+            })),
+            Some(Value::I64(_)) => query.bind(collect(arr, |v| match v {
+                Value::I64(i) => Some(*i),
+                _ => None,
+            })),
+            Some(Value::F32(_)) => query.bind(collect(arr, |v| match v {
+                Value::F32(f) => Some(*f),
+                _ => None,
+            })),
+            Some(Value::F64(_)) => query.bind(collect(arr, |v| match v {
+                Value::F64(f) => Some(*f),
+                _ => None,
+            })),
+            Some(Value::String(_)) => query.bind(collect(arr, |v| match v {
+                Value::String(s) => Some(s.clone()),
+                _ => None,
+            })),
+            Some(Value::Bytes(_)) => query.bind(collect(arr, |v| match v {
+                Value::Bytes(b) => Some(b.clone()),
+                _ => None,
+            })),
+            #[cfg(feature = "uuid-support")]
+            Some(Value::Uuid(_)) => query.bind(collect(arr, |v| match v {
+                Value::Uuid(u) => Some(*u),
+                _ => None,
+            })),
+            #[cfg(feature = "datetime-support")]
+            Some(Value::Date(_)) => query.bind(collect(arr, |v| match v {
+                Value::Date(d) => Some(*d),
+                _ => None,
+            })),
+            #[cfg(feature = "datetime-support")]
+            Some(Value::Time(_)) => query.bind(collect(arr, |v| match v {
+                Value::Time(t) => Some(*t),
+                _ => None,
+            })),
+            #[cfg(feature = "datetime-support")]
+            Some(Value::Timestamp(_)) => query.bind(collect(arr, |v| match v {
+                Value::Timestamp(ts) => Some(*ts),
+                _ => None,
+            })),
+            #[cfg(feature = "datetime-support")]
+            Some(Value::TimestampTz(_)) => query.bind(collect(arr, |v| match v {
+                Value::TimestampTz(ts) => Some(*ts),
+                _ => None,
+            })),
+            #[cfg(feature = "decimal-support")]
+            Some(Value::Decimal(_)) => query.bind(collect(arr, |v| match v {
+                Value::Decimal(d) => Some(*d),
+                _ => None,
+            })),
+            _ => query.bind(serde_json::Value::Array(
+                arr.iter().map(value_to_json).collect(),
+            )),
+        }
+    }
+
     /// Convert Value to serde_json::Value for array serialization
     fn value_to_json(value: &Value) -> serde_json::Value {
         match value {
@@ -564,79 +941,202 @@ pub mod postgres {
             ),
             Value::Json(j) => j.clone(),
             Value::Array(arr) => serde_json::Value::Array(arr.iter().map(value_to_json).collect()),
+            // Serialized in their text form, e.g. "2024-02-29T12:30:00Z"
+            #[cfg(feature = "uuid-support")]
+            Value::Uuid(u) => serde_json::json!(u),
+            #[cfg(feature = "datetime-support")]
+            Value::Date(d) => serde_json::json!(d),
+            #[cfg(feature = "datetime-support")]
+            Value::Time(t) => serde_json::json!(t),
+            #[cfg(feature = "datetime-support")]
+            Value::Timestamp(ts) => serde_json::json!(ts),
+            #[cfg(feature = "datetime-support")]
+            Value::TimestampTz(ts) => serde_json::json!(ts),
+            #[cfg(feature = "decimal-support")]
+            Value::Decimal(d) => serde_json::json!(d),
             Value::SubqueryPlaceholder => serde_json::Value::Null,
         }
     }
 
-    fn row_to_json_value(row: &sqlx::postgres::PgRow) -> Result<serde_json::Value> {
-        use sqlx::{Column, Row, TypeInfo, ValueRef};
-        use serde_json::{Map, Value as JsonValue};
-        
-        let mut object = Map::new();
-        
-        for (i, column) in row.columns().iter().enumerate() {
-            let column_name = column.name().to_string();
-            let raw_value = row.try_get_raw(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-            
-            // Handle NULL values
-            if raw_value.is_null() {
-                object.insert(column_name, JsonValue::Null);
-                continue;
+    impl RawRow for sqlx::postgres::PgRow {
+        fn len(&self) -> usize {
+            sqlx::Row::len(self)
+        }
+
+        fn column_name(&self, index: usize) -> &str {
+            use sqlx::{Column as _, Row as _};
+            self.columns()[index].name()
+        }
+
+        fn field(&self, index: usize) -> Result<Field<'_>> {
+            use sqlx::postgres::{PgTypeKind, PgValueFormat};
+            use sqlx::{Column as _, Row as _, TypeInfo as _, ValueRef as _};
+
+            let value = self.try_get_raw(index)?;
+            if value.is_null() {
+                return Ok(Field::Null);
             }
-            
-            // Convert based on PostgreSQL type
-            let type_info = column.type_info();
-            let json_value = match type_info.name() {
-                "BOOL" => {
-                    let val: bool = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    JsonValue::Bool(val)
-                }
-                "INT2" | "INT4" => {
-                    let val: i32 = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    JsonValue::Number(serde_json::Number::from(val))
-                }
-                "INT8" => {
-                    let val: i64 = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    JsonValue::Number(serde_json::Number::from(val))
-                }
-                "FLOAT4" => {
-                    let val: f32 = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    JsonValue::Number(serde_json::Number::from_f64(val as f64).unwrap_or_else(|| serde_json::Number::from(0)))
-                }
-                "FLOAT8" => {
-                    let val: f64 = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    JsonValue::Number(serde_json::Number::from_f64(val).unwrap_or_else(|| serde_json::Number::from(0)))
+
+            let type_info = self.columns()[index].type_info();
+            let binary = value.format() == PgValueFormat::Binary;
+            let bytes = || value.as_bytes().map_err(sqlx::Error::Decode);
+            Ok(match type_info.name() {
+                "BOOL" => Field::Bool(self.try_get(index)?),
+                "INT2" => Field::I64(self.try_get::<i16, _>(index)?.into()),
+                "INT4" => Field::I64(self.try_get::<i32, _>(index)?.into()),
+                "INT8" => Field::I64(self.try_get(index)?),
+                "FLOAT4" => Field::F64(self.try_get::<f32, _>(index)?.into()),
+                "FLOAT8" => Field::F64(self.try_get(index)?),
+                "TEXT" | "VARCHAR" | "CHAR" | "NAME" | "UNKNOWN" | "citext" => {
+                    Field::Str(self.try_get_unchecked(index)?)
                 }
-                "TEXT" | "VARCHAR" | "CHAR" => {
-                    let val: String = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    JsonValue::String(val)
+                "BYTEA" if binary => Field::Bytes(bytes()?),
+                "JSON" => Field::Json(self.try_get_unchecked(index)?),
+                // Binary JSONB is the JSON text behind a version byte
+                "JSONB" if binary => match bytes()?.split_first() {
+                    Some((1, json)) => Field::Json(
+                        std::str::from_utf8(json).map_err(|e| sqlx::Error::Decode(e.into()))?,
+                    ),
+                    _ => return Err(crate::Error::decode("unsupported JSONB format version")),
+                },
+                name @ ("UUID" | "NUMERIC" | "DATE" | "TIME" | "TIMESTAMP" | "TIMESTAMPTZ")
+                    if binary =>
+                {
+                    let text = decode_binary_text(name, bytes()?).ok_or_else(|| {
+                        crate::Error::decode(format!("malformed binary {} value", name))
+                    })?;
+                    Field::String(text)
                 }
-                "BYTEA" => {
-                    let val: Vec<u8> = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    // Convert bytes to array of numbers for JSON representation
-                    JsonValue::Array(val.into_iter().map(|b| JsonValue::Number(serde_json::Number::from(b))).collect())
+                "BOOL[]" => list(self.try_get::<Vec<Option<bool>>, _>(index)?, Field::Bool),
+                "INT2[]" => list(self.try_get::<Vec<Option<i16>>, _>(index)?, |i| {
+                    Field::I64(i.into())
+                }),
+                "INT4[]" => list(self.try_get::<Vec<Option<i32>>, _>(index)?, |i| {
+                    Field::I64(i.into())
+                }),
+                "INT8[]" => list(self.try_get::<Vec<Option<i64>>, _>(index)?, Field::I64),
+                "FLOAT4[]" => list(self.try_get::<Vec<Option<f32>>, _>(index)?, |f| {
+                    Field::F64(f.into())
+                }),
+                "FLOAT8[]" => list(self.try_get::<Vec<Option<f64>>, _>(index)?, Field::F64),
+                "TEXT[]" | "VARCHAR[]" => {
+                    list(self.try_get::<Vec<Option<String>>, _>(index)?, Field::String)
                 }
-                "JSON" | "JSONB" => {
-                    let val: serde_json::Value = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    val
+                // Enums and text format values are sent as their text
+                _ if !binary || matches!(type_info.kind(), PgTypeKind::Enum(_)) => {
+                    Field::Str(value.as_str().map_err(sqlx::Error::Decode)?)
                 }
-                // Add more types as needed
-                _ => {
-                    // Fallback: try to get as string
-                    match row.try_get::<String, _>(i) {
-                        Ok(val) => JsonValue::String(val),
-                        Err(_) => {
-                            // If all else fails, represent as string of the raw value
-                            JsonValue::String(format!("Unsupported type: {}", type_info.name()))
-                        }
+                name => Field::Unsupported(name),
+            })
+        }
+    }
+
+    fn list<'r, T, F>(items: Vec<Option<T>>, field: F) -> Field<'r>
+    where
+        F: Fn(T) -> Field<'r>,
+    {
+        Field::List(items.into_iter().map(|item| item.map_or(Field::Null, &field)).collect())
+    }
+
+    /// Seconds between the Unix epoch and PostgreSQL's 2000-01-01 epoch
+    const POSTGRES_EPOCH_SECS: i64 = 946_684_800;
+
+    /// Text for binary values without a JSON counterpart, see [`super::row`]
+    fn decode_binary_text(type_name: &str, bytes: &[u8]) -> Option<String> {
+        match type_name {
+            "UUID" => {
+                let bytes: [u8; 16] = bytes.try_into().ok()?;
+                let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+                Some(format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..]
+                ))
+            }
+            "NUMERIC" => decode_numeric(bytes),
+            "DATE" => Some(match i32::from_be_bytes(bytes.try_into().ok()?) {
+                i32::MAX => "infinity".to_string(),
+                i32::MIN => "-infinity".to_string(),
+                days => row::format_date(i64::from(days) + POSTGRES_EPOCH_SECS / 86_400),
+            }),
+            "TIME" => {
+                let micros = u64::from_be_bytes(bytes.try_into().ok()?);
+                Some(row::format_time(
+                    micros / 3_600_000_000,
+                    micros / 60_000_000 % 60,
+                    micros / 1_000_000 % 60,
+                    micros % 1_000_000,
+                ))
+            }
+            "TIMESTAMP" | "TIMESTAMPTZ" => Some(match i64::from_be_bytes(bytes.try_into().ok()?) {
+                i64::MAX => "infinity".to_string(),
+                i64::MIN => "-infinity".to_string(),
+                micros => {
+                    let timestamp =
+                        row::format_timestamp(micros + POSTGRES_EPOCH_SECS * 1_000_000);
+                    if type_name == "TIMESTAMPTZ" {
+                        timestamp + "+00:00"
+                    } else {
+                        timestamp
                     }
                 }
-            };
-            
-            object.insert(column_name, json_value);
+            }),
+            _ => None,
         }
-        
-        Ok(JsonValue::Object(object))
+    }
+
+    /// Render a binary NUMERIC: base 10000 digits, a weight, a sign and a display scale
+    fn decode_numeric(bytes: &[u8]) -> Option<String> {
+        use std::fmt::Write;
+
+        let word = |i: usize| {
+            let pair = bytes.get(i * 2..i * 2 + 2)?;
+            Some(u16::from_be_bytes([pair[0], pair[1]]))
+        };
+        let ndigits = usize::from(word(0)?);
+        let weight = i64::from(word(1)? as i16);
+        let sign = word(2)?;
+        let scale = usize::from(word(3)?);
+        let digits = (0..ndigits)
+            .map(|i| word(4 + i))
+            .collect::<Option<Vec<_>>>()?;
+
+        match sign {
+            0x0000 | 0x4000 => {}
+            0xC000 => return Some("NaN".to_string()),
+            0xD000 => return Some("Infinity".to_string()),
+            0xF000 => return Some("-Infinity".to_string()),
+            _ => return None,
+        }
+
+        let digit = |i: i64| usize::try_from(i).ok().and_then(|i| digits.get(i)).copied();
+        let mut text = String::new();
+        if sign == 0x4000 {
+            text.push('-');
+        }
+        if weight < 0 {
+            text.push('0');
+        } else {
+            write!(text, "{}", digit(0).unwrap_or(0)).ok()?;
+            for i in 1..=weight {
+                write!(text, "{:04}", digit(i).unwrap_or(0)).ok()?;
+            }
+        }
+        if scale > 0 {
+            let mut fraction = String::new();
+            let mut i = weight + 1;
+            while fraction.len() < scale {
+                write!(fraction, "{:04}", digit(i).unwrap_or(0)).ok()?;
+                i += 1;
+            }
+            fraction.truncate(scale);
+            text.push('.');
+            text.push_str(&fraction);
+        }
+        Some(text)
     }
 
     #[cfg(test)]
@@ -678,8 +1178,8 @@ pub mod postgres {
                 Value::Bool(true),
                 Value::I32(42),
                 Value::I64(123456),
-                Value::F32(3.14),
-                Value::F64(2.718),
+                Value::F32(1.5),
+                Value::F64(2.25),
                 Value::String("hello".to_string()),
                 Value::Bytes(vec![1, 2, 3, 4]),
                 Value::Json(serde_json::json!({"key": "value"})),
@@ -707,14 +1207,19 @@ pub mod postgres {
                 .where_(("status", "active"))
                 .where_(("score", op::IN, vec![100, 200, 300]));
 
-            // Verify SQL generation and parameters
+            // Generic rendering expands the IN list: age, status and three scores
             let sql = query.to_sql().unwrap();
-            let params = query.parameters();
-
-            // Should have 3 parameters: age > 18, status = 'active', score IN [100,200,300]
-            assert_eq!(params.len(), 3);
+            let params = query.parameters().unwrap();
+            assert!(sql.contains("score IN (?, ?, ?)"));
+            assert_eq!(params.len(), 5);
             assert_eq!(params[0], crate::Value::I32(18));
             assert_eq!(params[1], crate::Value::String("active".to_string()));
+            assert_eq!(params[4], crate::Value::I32(300));
+
+            // PostgreSQL binds the whole list as a single native array
+            let (sql, params) = query.to_sql_with_params(&PostgresDialect).unwrap();
+            assert!(sql.contains("\"score\" = ANY($3)"));
+            assert_eq!(params.len(), 3);
             assert_eq!(
                 params[2],
                 crate::Value::Array(vec![
@@ -724,12 +1229,9 @@ pub mod postgres {
                 ])
             );
 
-            // Verify SQL contains proper placeholders
-            assert!(sql.contains("?"));
-
             // Test that we can bind these parameters without panicking
             let sqlx_query = sqlx::query(&sql);
-            let _bound_query = bind_values_to_query(sqlx_query, params);
+            let _bound_query = bind_values_to_query(sqlx_query, &params);
         }
 
         #[test]
@@ -741,6 +1243,89 @@ pub mod postgres {
             assert_eq!(IsolationLevel::Serializable.to_sql(), "SERIALIZABLE");
         }
 
+        #[cfg(all(
+            feature = "uuid-support",
+            feature = "datetime-support",
+            feature = "decimal-support"
+        ))]
+        #[test]
+        fn test_typed_values() {
+            use chrono::{NaiveDate, TimeZone, Utc};
+
+            let timestamp = NaiveDate::from_ymd_opt(2024, 2, 29)
+                .unwrap()
+                .and_hms_opt(12, 30, 0)
+                .unwrap();
+            let params = vec![
+                Value::Uuid(uuid::Uuid::nil()),
+                Value::Date(timestamp.date()),
+                Value::Time(timestamp.time()),
+                Value::Timestamp(timestamp),
+                Value::TimestampTz(Utc.from_utc_datetime(&timestamp)),
+                Value::Decimal(rust_decimal::Decimal::new(1250, 2)),
+                Value::from(vec![uuid::Uuid::nil()]),
+            ];
+            let json: Vec<_> = params.iter().map(value_to_json).collect();
+            assert_eq!(
+                json,
+                vec![
+                    serde_json::json!("00000000-0000-0000-0000-000000000000"),
+                    serde_json::json!("2024-02-29"),
+                    serde_json::json!("12:30:00"),
+                    serde_json::json!("2024-02-29T12:30:00"),
+                    serde_json::json!("2024-02-29T12:30:00Z"),
+                    serde_json::json!("12.50"),
+                    serde_json::json!(["00000000-0000-0000-0000-000000000000"]),
+                ]
+            );
+
+            let _bound_query = bind_values_to_query(sqlx::query("SELECT 1"), &params);
+        }
+
+        #[test]
+        fn test_binary_values_render_as_text() {
+            let uuid = [
+                0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+                0xe0, 0xc8,
+            ];
+            assert_eq!(
+                decode_binary_text("UUID", &uuid).unwrap(),
+                "67e55044-10b1-426f-9247-bb680e5fe0c8"
+            );
+
+            // ndigits, weight, sign, dscale, then base 10000 digits
+            let numeric = |words: &[u16]| -> Vec<u8> {
+                words.iter().flat_map(|w| w.to_be_bytes()).collect()
+            };
+            let decode = |words: &[u16]| decode_numeric(&numeric(words)).unwrap();
+            assert_eq!(decode(&[2, 1, 0, 2, 1, 2345]), "12345.00");
+            assert_eq!(decode(&[2, 0, 0x4000, 1, 12, 5000]), "-12.5");
+            assert_eq!(decode(&[1, 0xFFFF, 0, 3, 500]), "0.050");
+            assert_eq!(decode(&[1, 2, 0, 0, 7]), "700000000");
+            assert_eq!(decode(&[0, 0, 0, 0]), "0");
+            assert_eq!(decode(&[0, 0, 0xC000, 0]), "NaN");
+            assert!(decode_numeric(&[0, 1]).is_none());
+
+            let date = 8_825i32.to_be_bytes();
+            assert_eq!(decode_binary_text("DATE", &date).unwrap(), "2024-02-29");
+            let time = 45_296_500_000i64.to_be_bytes();
+            assert_eq!(decode_binary_text("TIME", &time).unwrap(), "12:34:56.5");
+            let timestamp = 762_525_296_000_001i64.to_be_bytes();
+            assert_eq!(
+                decode_binary_text("TIMESTAMP", &timestamp).unwrap(),
+                "2024-02-29T12:34:56.000001"
+            );
+            assert_eq!(
+                decode_binary_text("TIMESTAMPTZ", &timestamp).unwrap(),
+                "2024-02-29T12:34:56.000001+00:00"
+            );
+            assert_eq!(
+                decode_binary_text("TIMESTAMP", &i64::MAX.to_be_bytes()).unwrap(),
+                "infinity"
+            );
+            assert!(decode_binary_text("DATE", &[0, 1]).is_none());
+        }
+
         #[tokio::test]
         async fn test_transaction_convenience_function() {
             use crate::transaction;
@@ -914,6 +1499,7 @@ pub mod postgres {
             }
         }
 
+        #[allow(dead_code)]
         #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
         struct User {
             id: i32,
@@ -926,7 +1512,9 @@ pub mod postgres {
 /// SQLite connection pool wrapper
 #[cfg(feature = "sqlite")]
 pub mod sqlite {
+    use super::row::{Field, RawRow};
     use super::*;
+    use crate::dialect::SqliteDialect;
     use sqlx::SqlitePool as SqlxSqlitePool;
 
     /// SQLite connection pool wrapper
@@ -950,6 +1538,10 @@ pub mod sqlite {
     impl ConnectionPool for SqlitePool {
         type Connection = sqlx::pool::PoolConnection<sqlx::Sqlite>;
 
+        fn dialect(&self) -> &'static dyn Dialect {
+            &SqliteDialect
+        }
+
         async fn acquire(&self) -> Result<Self::Connection> {
             Ok(self.inner.acquire().await?)
         }
@@ -961,6 +1553,10 @@ pub mod sqlite {
             Ok(result.rows_affected())
         }
 
+        async fn execute_all(&self, statements: &[(String, Vec<Value>)]) -> Result<u64> {
+            execute_all_in_transaction(self, statements).await
+        }
+
         async fn fetch_all<T>(&self, sql: &str, params: &[Value]) -> Result<Vec<T>>
         where
             T: DeserializeOwned + Send + Unpin,
@@ -971,8 +1567,7 @@ pub mod sqlite {
 
             let mut results = Vec::with_capacity(rows.len());
             for row in rows {
-                let json_value = row_to_json_value(&row)?;
-                let item: T = serde_json::from_value(json_value)?;
+                let item: T = Row::new(&row).deserialize()?;
                 results.push(item);
             }
             Ok(results)
@@ -986,8 +1581,7 @@ pub mod sqlite {
             let bound_query = bind_values_to_query(query, params);
             let row = bound_query.fetch_one(&self.inner).await?;
 
-            let json_value = row_to_json_value(&row)?;
-            let item: T = serde_json::from_value(json_value)?;
+            let item: T = Row::new(&row).deserialize()?;
             Ok(item)
         }
 
@@ -998,13 +1592,37 @@ pub mod sqlite {
             let query = sqlx::query(sql);
             let bound_query = bind_values_to_query(query, params);
             if let Some(row) = bound_query.fetch_optional(&self.inner).await? {
-                let json_value = row_to_json_value(&row)?;
-                let item: T = serde_json::from_value(json_value)?;
+                let item: T = Row::new(&row).deserialize()?;
                 Ok(Some(item))
             } else {
                 Ok(None)
             }
         }
+
+        fn fetch_stream<'a, T>(
+            &'a self,
+            sql: &'a str,
+            params: &'a [Value],
+        ) -> impl Stream<Item = Result<T>> + Send + 'a
+        where
+            T: DeserializeOwned + Send + Unpin + 'a,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            bound_query
+                .fetch(&self.inner)
+                .map(|row| Row::new(&row?).deserialize())
+        }
+
+        async fn fetch_all_as<T>(&self, sql: &str, params: &[Value]) -> Result<Vec<T>>
+        where
+            T: FromRow + Send,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let rows = bound_query.fetch_all(&self.inner).await?;
+            rows.iter().map(|row| T::from_row(&Row::new(row))).collect()
+        }
     }
 
     /// SQLite transaction wrapper
@@ -1015,6 +1633,10 @@ pub mod sqlite {
     }
 
     impl Transaction for SqliteTransaction {
+        fn dialect(&self) -> &'static dyn Dialect {
+            &SqliteDialect
+        }
+
         async fn execute(&mut self, sql: &str, params: &[Value]) -> Result<u64> {
             let query = sqlx::query(sql);
             let bound_query = bind_values_to_query(query, params);
@@ -1032,8 +1654,7 @@ pub mod sqlite {
 
             let mut results = Vec::with_capacity(rows.len());
             for row in rows {
-                let json_value = row_to_json_value(&row)?;
-                let item: T = serde_json::from_value(json_value)?;
+                let item: T = Row::new(&row).deserialize()?;
                 results.push(item);
             }
             Ok(results)
@@ -1047,8 +1668,7 @@ pub mod sqlite {
             let bound_query = bind_values_to_query(query, params);
             let row = bound_query.fetch_one(&mut *self.inner).await?;
 
-            let json_value = row_to_json_value(&row)?;
-            let item: T = serde_json::from_value(json_value)?;
+            let item: T = Row::new(&row).deserialize()?;
             Ok(item)
         }
 
@@ -1059,76 +1679,1448 @@ pub mod sqlite {
             let query = sqlx::query(sql);
             let bound_query = bind_values_to_query(query, params);
             if let Some(row) = bound_query.fetch_optional(&mut *self.inner).await? {
-                let json_value = row_to_json_value(&row)?;
-                let item: T = serde_json::from_value(json_value)?;
+                let item: T = Row::new(&row).deserialize()?;
                 Ok(Some(item))
             } else {
                 Ok(None)
             }
         }
 
-        async fn commit(self) -> Result<()> {
-            self.inner.commit().await?;
-            Ok(())
+        fn fetch_stream<'a, T>(
+            &'a mut self,
+            sql: &'a str,
+            params: &'a [Value],
+        ) -> impl Stream<Item = Result<T>> + Send + 'a
+        where
+            T: DeserializeOwned + Send + Unpin + 'a,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            bound_query
+                .fetch(&mut *self.inner)
+                .map(|row| Row::new(&row?).deserialize())
         }
 
-        async fn rollback(self) -> Result<()> {
-            self.inner.rollback().await?;
-            Ok(())
-        }
+        async fn fetch_all_as<T>(&mut self, sql: &str, params: &[Value]) -> Result<Vec<T>>
+        where
+            T: FromRow + Send,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let rows = bound_query.fetch_all(&mut *self.inner).await?;
+            rows.iter().map(|row| T::from_row(&Row::new(row))).collect()
+        }
+
+        async fn commit(self) -> Result<()> {
+            self.inner.commit().await?;
+            Ok(())
+        }
+
+        async fn rollback(self) -> Result<()> {
+            self.inner.rollback().await?;
+            Ok(())
+        }
+
+        async fn savepoint(&mut self, name: &str) -> Result<()> {
+            let sql = format!("SAVEPOINT {}", name);
+            sqlx::query(&sql).execute(&mut *self.inner).await?;
+            Ok(())
+        }
+
+        async fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
+            let sql = format!("ROLLBACK TO SAVEPOINT {}", name);
+            sqlx::query(&sql).execute(&mut *self.inner).await?;
+            Ok(())
+        }
+
+        async fn release_savepoint(&mut self, name: &str) -> Result<()> {
+            let sql = format!("RELEASE SAVEPOINT {}", name);
+            sqlx::query(&sql).execute(&mut *self.inner).await?;
+            Ok(())
+        }
+    }
+
+    impl TransactionalPool for SqlitePool {
+        type Transaction = SqliteTransaction;
+
+        async fn begin_transaction(&self) -> Result<Self::Transaction> {
+            let txn = self.inner.begin().await?;
+            Ok(SqliteTransaction { inner: txn })
+        }
+
+        async fn begin_transaction_with_isolation(
+            &self,
+            isolation: IsolationLevel,
+        ) -> Result<Self::Transaction> {
+            let mut txn = self.inner.begin().await?;
+
+            // SQLite supports fewer isolation levels than PostgreSQL
+            // Map to SQLite pragma settings
+            let pragma = match isolation {
+                IsolationLevel::ReadUncommitted => "PRAGMA read_uncommitted = true",
+                IsolationLevel::ReadCommitted => "PRAGMA read_uncommitted = false", // SQLite default
+                IsolationLevel::RepeatableRead => "PRAGMA read_uncommitted = false", // Best we can do
+                IsolationLevel::Serializable => "PRAGMA read_uncommitted = false", // SQLite default is serializable
+            };
+
+            sqlx::query(pragma).execute(&mut *txn).await?;
+            Ok(SqliteTransaction { inner: txn })
+        }
+    }
+
+    /// Bind Archibald Values to a SQLx SQLite query
+    fn bind_values_to_query<'q>(
+        mut query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+        params: &'q [Value],
+    ) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+        for param in params {
+            query = match param {
+                Value::Null => query.bind(None::<i32>), // Use Option<T> for NULL values
+                Value::Bool(b) => query.bind(*b),
+                Value::I32(i) => query.bind(*i),
+                Value::I64(i) => query.bind(*i),
+                Value::F32(f) => query.bind(*f),
+                Value::F64(f) => query.bind(*f),
+                Value::String(s) => query.bind(s.as_str()),
+                Value::Bytes(b) => query.bind(b.as_slice()),
+                Value::Json(j) => {
+                    // SQLite doesn't have native JSON support, serialize as string
+                    let json_str = serde_json::to_string(j).unwrap_or_else(|_| "null".to_string());
+                    query.bind(json_str)
+                }
+                #[cfg(feature = "uuid-support")]
+                Value::Uuid(u) => query.bind(*u),
+                #[cfg(feature = "datetime-support")]
+                Value::Date(d) => query.bind(*d),
+                #[cfg(feature = "datetime-support")]
+                Value::Time(t) => query.bind(*t),
+                #[cfg(feature = "datetime-support")]
+                Value::Timestamp(ts) => query.bind(*ts),
+                #[cfg(feature = "datetime-support")]
+                Value::TimestampTz(ts) => query.bind(*ts),
+                // sqlx has no DECIMAL support for SQLite, so bind the exact text
+                #[cfg(feature = "decimal-support")]
+                Value::Decimal(d) => query.bind(d.to_string()),
+                Value::Array(arr) => {
+                    // SQLite doesn't have native array support, serialize as JSON string
+                    let json_array =
+                        serde_json::Value::Array(arr.iter().map(value_to_json).collect());
+                    let json_str =
+                        serde_json::to_string(&json_array).unwrap_or_else(|_| "[]".to_string());
+                    query.bind(json_str)
+                }
+                Value::SubqueryPlaceholder => {
+                    // Subqueries should have been resolved before this point
+                    // This is likely a programming error
+                    continue; // Skip for now, could panic or error in the future
+                }
+            };
+        }
+        query
+    }
+
+    /// Convert Value to serde_json::Value for array serialization
+    fn value_to_json(value: &Value) -> serde_json::Value {
+        match value {
+            Value::Null => serde_json::Value::Null,
+            Value::Bool(b) => serde_json::Value::Bool(*b),
+            Value::I32(i) => serde_json::Value::Number(serde_json::Number::from(*i)),
+            Value::I64(i) => serde_json::Value::Number(serde_json::Number::from(*i)),
+            Value::F32(f) => serde_json::Number::from_f64(*f as f64)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Value::F64(f) => serde_json::Number::from_f64(*f)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Value::String(s) => serde_json::Value::String(s.clone()),
+            Value::Bytes(b) => serde_json::Value::Array(
+                b.iter()
+                    .map(|byte| serde_json::Value::Number(serde_json::Number::from(*byte)))
+                    .collect(),
+            ),
+            Value::Json(j) => j.clone(),
+            Value::Array(arr) => serde_json::Value::Array(arr.iter().map(value_to_json).collect()),
+            // Serialized in their text form, e.g. "2024-02-29T12:30:00Z"
+            #[cfg(feature = "uuid-support")]
+            Value::Uuid(u) => serde_json::json!(u),
+            #[cfg(feature = "datetime-support")]
+            Value::Date(d) => serde_json::json!(d),
+            #[cfg(feature = "datetime-support")]
+            Value::Time(t) => serde_json::json!(t),
+            #[cfg(feature = "datetime-support")]
+            Value::Timestamp(ts) => serde_json::json!(ts),
+            #[cfg(feature = "datetime-support")]
+            Value::TimestampTz(ts) => serde_json::json!(ts),
+            #[cfg(feature = "decimal-support")]
+            Value::Decimal(d) => serde_json::json!(d),
+            Value::SubqueryPlaceholder => serde_json::Value::Null,
+        }
+    }
+
+    impl RawRow for sqlx::sqlite::SqliteRow {
+        fn len(&self) -> usize {
+            sqlx::Row::len(self)
+        }
+
+        fn column_name(&self, index: usize) -> &str {
+            use sqlx::{Column as _, Row as _};
+            self.columns()[index].name()
+        }
+
+        fn field(&self, index: usize) -> Result<Field<'_>> {
+            use sqlx::{Column as _, Row as _, TypeInfo as _, ValueRef as _};
+
+            let value = self.try_get_raw(index)?;
+            if value.is_null() {
+                return Ok(Field::Null);
+            }
+            let declared = self.columns()[index].type_info().name();
+            if declared == "BOOLEAN" {
+                return Ok(Field::Bool(self.try_get(index)?));
+            }
+
+            // Values carry their own storage class, whatever the declared type
+            Ok(match value.type_info().name() {
+                "INTEGER" => Field::I64(self.try_get_unchecked(index)?),
+                "REAL" => Field::F64(self.try_get_unchecked(index)?),
+                "TEXT" => match self.try_get_unchecked::<&str, _>(index)? {
+                    text if declared == "DATETIME" => {
+                        row::iso_datetime(text).map_or(Field::Str(text), Field::String)
+                    }
+                    text => Field::Str(text),
+                },
+                "BLOB" => Field::Bytes(self.try_get_unchecked(index)?),
+                _ => Field::Unsupported("NULL"),
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod sqlite_tests {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_sqlite_pool_creation() {
+            // An in-memory database needs no setup, so we can create a real pool here
+            let pool = SqlitePool::new("sqlite::memory:").await.unwrap();
+            assert_eq!(pool.dialect().name(), "sqlite");
+        }
+
+        /// A single-connection in-memory pool, so every query sees the same database
+        async fn memory_pool() -> SqlitePool {
+            let pool = sqlx::sqlite::SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect("sqlite::memory:")
+                .await
+                .unwrap();
+            SqlitePool::from_pool(pool)
+        }
+
+        #[tokio::test]
+        async fn test_returning_fetches_affected_rows() {
+            use crate::{delete, insert, update, ExecutableQuery};
+            use std::collections::HashMap;
+
+            #[derive(Debug, PartialEq, serde::Deserialize)]
+            struct Account {
+                id: i64,
+                name: String,
+            }
+
+            let pool = memory_pool().await;
+            pool.execute(
+                "CREATE TABLE accounts (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL)",
+                &[],
+            )
+            .await
+            .unwrap();
+
+            let mut data = HashMap::new();
+            data.insert("name".to_string(), Value::from("alice"));
+            let inserted: Account = insert("accounts")
+                .values(data)
+                .returning(("id", "name"))
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(inserted, Account { id: 1, name: "alice".to_string() });
+
+            let mut updates = HashMap::new();
+            updates.insert("name".to_string(), Value::from("bob"));
+            let updated: Vec<Account> = update("accounts")
+                .set(updates)
+                .where_(("id", 1))
+                .returning("*")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(updated, vec![Account { id: 1, name: "bob".to_string() }]);
+
+            let deleted: Option<Account> = delete("accounts")
+                .where_(("name", "bob"))
+                .returning(("id", "name"))
+                .fetch_optional(&pool)
+                .await
+                .unwrap();
+            assert_eq!(deleted, Some(Account { id: 1, name: "bob".to_string() }));
+        }
+
+        #[tokio::test]
+        async fn test_raw_queries_execute_and_fetch() {
+            use crate::{ExecutableModification, ExecutableQuery, Raw, from, raw};
+
+            #[derive(Debug, PartialEq, serde::Deserialize)]
+            struct Tag {
+                name: String,
+                uses: i64,
+            }
+
+            let pool = memory_pool().await;
+            Raw::new("CREATE TABLE tags (name TEXT PRIMARY KEY, uses INTEGER NOT NULL)")
+                .execute(&pool)
+                .await
+                .unwrap();
+            let inserted = raw("INSERT INTO tags (name, uses) VALUES (?, ?), (?, ?)", [
+                Value::from("rust"),
+                Value::from(3),
+                Value::from("sql?"),
+                Value::from(1),
+            ])
+            .execute(&pool)
+            .await
+            .unwrap();
+            assert_eq!(inserted, 2);
+
+            let tags: Vec<Tag> = raw("SELECT * FROM tags WHERE uses >= ? ORDER BY name", [1])
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(tags.len(), 2);
+            assert_eq!(tags[1], Tag { name: "sql?".to_string(), uses: 1 });
+
+            let popular: Tag = from("tags")
+                .select(("name", "uses"))
+                .where_raw(raw("uses * ? > ?", [2, 5]))
+                .order_by_raw("LENGTH(name) DESC")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(popular, Tag { name: "rust".to_string(), uses: 3 });
+
+            let unbound: Result<Option<Tag>> = Raw::new("SELECT * FROM tags WHERE name = ?")
+                .fetch_optional(&pool)
+                .await;
+            assert!(matches!(unbound, Err(crate::Error::InvalidQuery { .. })));
+        }
+
+        #[tokio::test]
+        async fn test_recursive_cte_walks_a_tree() {
+            use crate::{ExecutableQuery, Raw, from};
+
+            let pool = memory_pool().await;
+            pool.execute(
+                "CREATE TABLE categories (id INTEGER PRIMARY KEY, parent_id INTEGER, name TEXT)",
+                &[],
+            )
+            .await
+            .unwrap();
+            pool.execute(
+                "INSERT INTO categories VALUES (1, NULL, 'root'), (2, 1, 'books'), \
+                 (3, 2, 'fiction'), (4, NULL, 'other'), (5, 3, 'crime')",
+                &[],
+            )
+            .await
+            .unwrap();
+
+            let anchor = from("categories")
+                .select(("id", "name", Raw::new("0 AS depth")))
+                .where_(("id", 2));
+            let children = from("categories")
+                .inner_join("tree", "categories.parent_id", "tree.id")
+                .select(("categories.id", "categories.name", Raw::new("tree.depth + 1")));
+            let rows: Vec<(String, i64)> = from("tree")
+                .with_recursive("tree", ("id", "name", "depth"), anchor.union_all(children))
+                .select(("name", "depth"))
+                .where_(("depth", ">=", 0))
+                .order_by_asc("depth")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                rows,
+                vec![
+                    ("books".to_string(), 0),
+                    ("fiction".to_string(), 1),
+                    ("crime".to_string(), 2),
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_set_operations_fetch_combined_rows() {
+            use crate::{ExecutableQuery, from};
+
+            let pool = memory_pool().await;
+            pool.execute("CREATE TABLE customers (email TEXT, active INTEGER)", &[])
+                .await
+                .unwrap();
+            pool.execute("CREATE TABLE leads (email TEXT)", &[]).await.unwrap();
+            pool.execute(
+                "INSERT INTO customers VALUES ('a@x.io', 1), ('b@x.io', 0), ('c@x.io', 1)",
+                &[],
+            )
+            .await
+            .unwrap();
+            pool.execute("INSERT INTO leads VALUES ('c@x.io'), ('d@x.io'), ('e@x.io')", &[])
+                .await
+                .unwrap();
+
+            let active = from("customers").select("email").where_(("active", true));
+            let latest_lead = from("leads").select("email").order_by_desc("email").limit(1);
+
+            let rows: Vec<(String,)> = active
+                .clone()
+                .union(from("leads").select("email"))
+                .except(latest_lead)
+                .order_by_desc("email")
+                .limit(3)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(
+                rows,
+                vec![("d@x.io".to_string(),), ("c@x.io".to_string(),), ("a@x.io".to_string(),)]
+            );
+
+            let both: Vec<(String,)> = active
+                .intersect(from("leads").select("email"))
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(both, vec![("c@x.io".to_string(),)]);
+        }
+
+        #[tokio::test]
+        async fn test_window_functions_rank_and_accumulate() {
+            use crate::{ColumnSelector, ExecutableQuery, FrameBound, from};
+
+            let pool = memory_pool().await;
+            pool.execute("CREATE TABLE scores (player TEXT, game TEXT, points INTEGER)", &[])
+                .await
+                .unwrap();
+            pool.execute(
+                "INSERT INTO scores VALUES ('ann', 'chess', 30), ('bob', 'chess', 50), \
+                 ('cid', 'chess', 40), ('dee', 'go', 10)",
+                &[],
+            )
+            .await
+            .unwrap();
+
+            let rows: Vec<(String, i64, i64)> = from("scores")
+                .select((
+                    "player",
+                    ColumnSelector::rank().over_named("board").as_alias("position"),
+                    ColumnSelector::sum("points")
+                        .over(|w| {
+                            let start = FrameBound::UnboundedPreceding;
+                            w.based_on("board").rows_between(start, FrameBound::CurrentRow)
+                        })
+                        .as_alias("running"),
+                ))
+                .where_(("game", "chess"))
+                .window("board", |w| w.partition_by("game").order_by_desc("points"))
+                .order_by_asc("position")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                rows,
+                vec![
+                    ("bob".to_string(), 1, 50),
+                    ("cid".to_string(), 2, 90),
+                    ("ann".to_string(), 3, 120),
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_select_from_derived_and_aliased_tables() {
+            use crate::{ExecutableQuery, TableRef, from, from_subquery, op, table};
+
+            let pool = memory_pool().await;
+            pool.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)", &[])
+                .await
+                .unwrap();
+            pool.execute("CREATE TABLE orders (user_id INTEGER, total INTEGER)", &[])
+                .await
+                .unwrap();
+            pool.execute("INSERT INTO users VALUES (1, 'ann'), (2, 'bob'), (3, 'cid')", &[])
+                .await
+                .unwrap();
+            pool.execute(
+                "INSERT INTO orders VALUES (1, 10), (1, 90), (2, 30), (3, 5), (3, 200)",
+                &[],
+            )
+            .await
+            .unwrap();
+
+            let totals = from("orders")
+                .select("user_id")
+                .select_raw("SUM(total) AS spent")
+                .where_(("total", op::GT, 6))
+                .group_by("user_id");
+            let rows: Vec<(String, i64)> = from(table("users").as_("u"))
+                .inner_join(TableRef::subquery(totals, "t"), "t.user_id", "u.id")
+                .select(("u.name", "t.spent"))
+                .where_(("t.spent", op::GT, 50))
+                .order_by_asc("u.name")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(rows, vec![("ann".to_string(), 100), ("cid".to_string(), 200)]);
+
+            let (count,): (i64,) = from_subquery(from("orders").select("user_id").distinct(), "d")
+                .select_raw("COUNT(*)")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(count, 3);
+        }
+
+        #[tokio::test]
+        async fn test_insert_select_archives_rows() {
+            use crate::{ExecutableModification, ExecutableQuery, from, insert, op};
+
+            let pool = memory_pool().await;
+            pool.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, age INTEGER)", &[])
+                .await
+                .unwrap();
+            pool.execute("CREATE TABLE archive (id INTEGER PRIMARY KEY, name TEXT)", &[])
+                .await
+                .unwrap();
+            pool.execute(
+                "INSERT INTO users VALUES (1, 'ann', 91), (2, 'bob', 40), (3, 'cid', 95)",
+                &[],
+            )
+            .await
+            .unwrap();
+            pool.execute("INSERT INTO archive VALUES (3, 'stale')", &[]).await.unwrap();
+
+            let archived: Vec<(i64,)> = insert("archive")
+                .columns(("id", "name"))
+                .from_select(from("users").select(("id", "name")).where_(("age", op::GT, 90)))
+                .on_conflict("id")
+                .merge("name")
+                .returning("id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(archived.len(), 2);
+
+            let copied = insert("archive")
+                .columns(("id", "name"))
+                .from_select(from("users").select(("id", "name")).where_(("id", 2)))
+                .execute(&pool)
+                .await
+                .unwrap();
+            assert_eq!(copied, 1);
+
+            let rows: Vec<(i64, String)> = from("archive")
+                .select(("id", "name"))
+                .order_by_asc("id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(
+                rows,
+                vec![(1, "ann".to_string()), (2, "bob".to_string()), (3, "cid".to_string())]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_chunked_insert_runs_in_one_transaction() {
+            use crate::{ExecutableModification, ExecutableQuery, from, insert};
+            use std::collections::HashMap;
+
+            fn rows(ids: &[i32]) -> Vec<HashMap<String, Value>> {
+                ids.iter()
+                    .map(|id| {
+                        HashMap::from([
+                            ("id".to_string(), Value::I32(*id)),
+                            ("name".to_string(), Value::from(format!("item{}", id))),
+                        ])
+                    })
+                    .collect()
+            }
+
+            let pool = memory_pool().await;
+            pool.execute("CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT)", &[])
+                .await
+                .unwrap();
+
+            let inserted = insert("items")
+                .values_many(rows(&[1, 2, 3, 4, 5]))
+                .chunked(2)
+                .execute(&pool)
+                .await
+                .unwrap();
+            assert_eq!(inserted, 5);
+
+            // The last chunk collides with id 1, so the first chunk is rolled back
+            let result = insert("items")
+                .values_many(rows(&[6, 7, 1]))
+                .chunked(2)
+                .execute(&pool)
+                .await;
+            assert!(result.is_err());
+
+            let mut txn = pool.begin_transaction().await.unwrap();
+            let inserted = insert("items")
+                .values_many(rows(&[8, 9, 10]))
+                .chunked(2)
+                .execute_tx(&mut txn)
+                .await
+                .unwrap();
+            assert_eq!(inserted, 3);
+            txn.commit().await.unwrap();
+
+            let ids: Vec<(i64,)> =
+                from("items").select("id").order_by_asc("id").fetch_all(&pool).await.unwrap();
+            assert_eq!(ids, vec![(1,), (2,), (3,), (4,), (5,), (8,), (9,), (10,)]);
+        }
+
+        #[tokio::test]
+        async fn test_update_from_other_table() {
+            use crate::{ExecutableModification, ExecutableQuery, from, update};
+            use std::collections::HashMap;
+
+            let pool = memory_pool().await;
+            pool.execute("CREATE TABLE customers (id INTEGER PRIMARY KEY, tier TEXT)", &[])
+                .await
+                .unwrap();
+            pool.execute(
+                "CREATE TABLE orders (id INTEGER PRIMARY KEY, customer_id INTEGER, discount INTEGER)",
+                &[],
+            )
+            .await
+            .unwrap();
+            pool.execute("INSERT INTO customers VALUES (1, 'gold'), (2, 'basic')", &[])
+                .await
+                .unwrap();
+            pool.execute("INSERT INTO orders VALUES (1, 1, 0), (2, 2, 0), (3, 1, 0)", &[])
+                .await
+                .unwrap();
+
+            let updated = update("orders")
+                .set(HashMap::from([("discount".to_string(), Value::I32(15))]))
+                .from("customers")
+                .where_col("orders.customer_id", "customers.id")
+                .and_where(("customers.tier", "gold"))
+                .execute(&pool)
+                .await
+                .unwrap();
+            assert_eq!(updated, 2);
+
+            let discounts: Vec<(i64, i64)> = from("orders")
+                .select(("id", "discount"))
+                .order_by_asc("id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(discounts, vec![(1, 15), (2, 0), (3, 15)]);
+        }
+
+        #[tokio::test]
+        async fn test_increment_with_a_shared_column_name() {
+            use crate::{ExecutableModification, ExecutableQuery, from, update};
+
+            let pool = memory_pool().await;
+            pool.execute("CREATE TABLE carts (id INTEGER PRIMARY KEY, items INTEGER)", &[])
+                .await
+                .unwrap();
+            pool.execute(
+                "CREATE TABLE orders (id INTEGER PRIMARY KEY, cart_id INTEGER, items INTEGER)",
+                &[],
+            )
+            .await
+            .unwrap();
+            pool.execute("INSERT INTO carts VALUES (1, 100)", &[]).await.unwrap();
+            pool.execute("INSERT INTO orders VALUES (1, 1, 3), (2, 2, 5)", &[])
+                .await
+                .unwrap();
+
+            let updated = update("orders")
+                .increment("items", 1)
+                .from("carts")
+                .where_col("orders.cart_id", "carts.id")
+                .execute(&pool)
+                .await
+                .unwrap();
+            assert_eq!(updated, 1);
+
+            let items: Vec<(i64, i64)> = from("orders")
+                .select(("id", "items"))
+                .order_by_asc("id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(items, vec![(1, 4), (2, 5)]);
+        }
+
+        #[tokio::test]
+        async fn test_update_increments_and_expressions() {
+            use crate::{ExecutableModification, ExecutableQuery, from, update};
+
+            let pool = memory_pool().await;
+            pool.execute(
+                "CREATE TABLE posts (id INTEGER PRIMARY KEY, views INTEGER, stock INTEGER, label TEXT, slug TEXT)",
+                &[],
+            )
+            .await
+            .unwrap();
+            pool.execute(
+                "INSERT INTO posts VALUES (1, 10, 5, 'Hello', NULL), (2, 0, 5, 'Bye', NULL)",
+                &[],
+            )
+            .await
+            .unwrap();
+
+            let updated = update("posts")
+                .increment("views", 3)
+                .decrement("stock", 2)
+                .set_expr("label", crate::raw("UPPER(label) || ?", ["!"]))
+                .set_column("slug", "label")
+                .where_(("id", 1))
+                .execute(&pool)
+                .await
+                .unwrap();
+            assert_eq!(updated, 1);
+
+            let rows: Vec<(i64, i64, String, String)> = from("posts")
+                .select(("views", "stock", "label", "slug"))
+                .where_(("id", 1))
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            // SET expressions all read the row as it was before the update
+            assert_eq!(rows, vec![(13, 3, "HELLO!".to_string(), "Hello".to_string())]);
+        }
+
+        #[tokio::test]
+        async fn test_upsert_on_conflict() {
+            use crate::insert;
+            use std::collections::HashMap;
+
+            let pool = memory_pool().await;
+            pool.execute(
+                "CREATE TABLE counters (name TEXT PRIMARY KEY, hits INTEGER NOT NULL)",
+                &[],
+            )
+            .await
+            .unwrap();
+
+            let row = |hits: i32| {
+                let mut data = HashMap::new();
+                data.insert("name".to_string(), Value::from("home"));
+                data.insert("hits".to_string(), Value::from(hits));
+                data
+            };
+
+            insert("counters").values(row(1)).execute(&pool).await.unwrap();
+            insert("counters")
+                .values(row(2))
+                .on_conflict("name")
+                .do_nothing()
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            let hits: serde_json::Value = pool
+                .fetch_one("SELECT hits FROM counters", &[])
+                .await
+                .unwrap();
+            assert_eq!(hits["hits"], 1);
+
+            insert("counters")
+                .values(row(5))
+                .on_conflict("name")
+                .merge("hits")
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            let hits: serde_json::Value = pool
+                .fetch_one("SELECT hits FROM counters", &[])
+                .await
+                .unwrap();
+            assert_eq!(hits["hits"], 5);
+        }
+
+        #[tokio::test]
+        async fn test_fetch_stream_decodes_rows_lazily() {
+            use crate::{ExecutableQuery, from};
+
+            #[derive(Debug, serde::Deserialize, PartialEq)]
+            struct Event {
+                id: i64,
+                name: String,
+            }
+
+            let pool = memory_pool().await;
+            pool.execute("CREATE TABLE events (id INTEGER PRIMARY KEY, name TEXT)", &[])
+                .await
+                .unwrap();
+            for id in 1..=5 {
+                pool.execute(
+                    "INSERT INTO events (id, name) VALUES (?, ?)",
+                    &[Value::I64(id), Value::from(format!("event {}", id))],
+                )
+                .await
+                .unwrap();
+            }
+
+            let events: Vec<Event> = from("events")
+                .select(("id", "name"))
+                .where_(("id", crate::op::GT, 3))
+                .order_by_asc("id")
+                .fetch_stream(&pool)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(
+                events,
+                vec![
+                    Event { id: 4, name: "event 4".to_string() },
+                    Event { id: 5, name: "event 5".to_string() },
+                ]
+            );
+
+            // Dropping a partially read stream releases the pool's only connection
+            let first_two: Vec<Event> = from("events")
+                .select(("id", "name"))
+                .order_by_asc("id")
+                .fetch_stream(&pool)
+                .take(2)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(first_two.len(), 2);
+            let count: u64 = pool.execute("DELETE FROM events WHERE id = 1", &[]).await.unwrap();
+            assert_eq!(count, 1);
+
+            // Rows that fail to decode surface as errors in the stream
+            let mut bad = std::pin::pin!(pool.fetch_stream::<i64>("SELECT name FROM events", &[]));
+            assert!(bad.next().await.unwrap().is_err());
+        }
+
+        #[tokio::test]
+        async fn test_fetch_stream_in_transaction() {
+            use crate::{ExecutableQuery, from};
+
+            #[derive(serde::Deserialize)]
+            struct Row {
+                n: i64,
+            }
+
+            let pool = memory_pool().await;
+            let mut txn = pool.begin_transaction().await.unwrap();
+            txn.execute("CREATE TABLE numbers (n INTEGER)", &[]).await.unwrap();
+            txn.execute("INSERT INTO numbers (n) VALUES (1), (2), (3)", &[])
+                .await
+                .unwrap();
+
+            let total = from("numbers")
+                .select("n")
+                .fetch_stream_tx(&mut txn)
+                .try_fold(0, |total, row: Row| async move { Ok(total + row.n) })
+                .await
+                .unwrap();
+            assert_eq!(total, 6);
+
+            // The transaction is usable again once the stream is gone
+            txn.rollback().await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_rows_decode_by_storage_class() {
+            use crate::{ExecutableQuery, FromRow, from};
+
+            #[derive(Debug, serde::Deserialize, PartialEq)]
+            struct Document {
+                id: i64,
+                published: bool,
+                score: f64,
+                tags: Vec<String>,
+                body: Vec<u8>,
+                note: Option<String>,
+            }
+
+            struct Summary {
+                id: i64,
+                tags: String,
+            }
+
+            impl FromRow for Summary {
+                fn from_row(row: &Row<'_>) -> Result<Self> {
+                    Ok(Self {
+                        id: row.get_index(0)?,
+                        tags: row.get("tags")?,
+                    })
+                }
+            }
+
+            let pool = memory_pool().await;
+            pool.execute(
+                "CREATE TABLE documents (
+                    id INTEGER PRIMARY KEY,
+                    published BOOLEAN NOT NULL,
+                    score REAL NOT NULL,
+                    tags TEXT NOT NULL,
+                    body BLOB NOT NULL,
+                    note TEXT
+                )",
+                &[],
+            )
+            .await
+            .unwrap();
+            pool.execute(
+                "INSERT INTO documents (id, published, score, tags, body, note)
+                 VALUES (1, 1, 2.5, '[\"a\",\"b\"]', X'0102FF', NULL)",
+                &[],
+            )
+            .await
+            .unwrap();
+
+            let documents: Vec<Document> =
+                from("documents").select("*").fetch_all(&pool).await.unwrap();
+            assert_eq!(
+                documents,
+                vec![Document {
+                    id: 1,
+                    published: true,
+                    score: 2.5,
+                    tags: vec!["a".to_string(), "b".to_string()],
+                    body: vec![1, 2, 255],
+                    note: None,
+                }]
+            );
+
+            // Tuples decode by position and single columns into scalars
+            let pair: (i64, bool) = pool
+                .fetch_one("SELECT id, published FROM documents", &[])
+                .await
+                .unwrap();
+            assert_eq!(pair, (1, true));
+            let count: i64 = pool.fetch_one("SELECT COUNT(*) FROM documents", &[]).await.unwrap();
+            assert_eq!(count, 1);
+
+            let summaries: Vec<Summary> = from("documents")
+                .select(("id", "tags"))
+                .fetch_all_as(&pool)
+                .await
+                .unwrap();
+            assert_eq!(summaries.len(), 1);
+            assert_eq!((summaries[0].id, summaries[0].tags.as_str()), (1, r#"["a","b"]"#));
+
+            let mut txn = pool.begin_transaction().await.unwrap();
+            let summaries: Vec<Summary> = from("documents")
+                .select(("id", "tags"))
+                .fetch_all_as_tx(&mut txn)
+                .await
+                .unwrap();
+            assert_eq!(summaries.len(), 1);
+            txn.rollback().await.unwrap();
+
+            let err = pool
+                .fetch_one::<Document>("SELECT id, 'no' AS published FROM documents", &[])
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("column `published`"), "{}", err);
+        }
+
+        #[cfg(all(
+            feature = "uuid-support",
+            feature = "datetime-support",
+            feature = "decimal-support"
+        ))]
+        #[tokio::test]
+        async fn test_typed_values_round_trip() {
+            use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+            use rust_decimal::Decimal;
+
+            #[derive(Debug, serde::Deserialize, PartialEq)]
+            struct Payment {
+                id: uuid::Uuid,
+                due: NaiveDate,
+                cutoff: NaiveTime,
+                scheduled: NaiveDateTime,
+                paid_at: DateTime<Utc>,
+                amount: Decimal,
+            }
+
+            let pool = memory_pool().await;
+            pool.execute(
+                "CREATE TABLE payments (
+                    id BLOB PRIMARY KEY,
+                    due DATE,
+                    cutoff TIME,
+                    scheduled DATETIME,
+                    paid_at DATETIME,
+                    amount TEXT
+                )",
+                &[],
+            )
+            .await
+            .unwrap();
+
+            let scheduled = NaiveDate::from_ymd_opt(2024, 2, 29)
+                .unwrap()
+                .and_hms_micro_opt(9, 15, 0, 250_000)
+                .unwrap();
+            let payment = Payment {
+                id: uuid::Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8),
+                due: scheduled.date(),
+                cutoff: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+                scheduled,
+                paid_at: Utc.from_utc_datetime(&scheduled),
+                amount: Decimal::new(1250, 2),
+            };
+            pool.execute(
+                "INSERT INTO payments VALUES (?, ?, ?, ?, ?, ?)",
+                &[
+                    payment.id.into(),
+                    payment.due.into(),
+                    payment.cutoff.into(),
+                    payment.scheduled.into(),
+                    payment.paid_at.into(),
+                    payment.amount.into(),
+                ],
+            )
+            .await
+            .unwrap();
+
+            let fetched: Payment = pool
+                .fetch_one("SELECT * FROM payments WHERE due = ?", &[payment.due.into()])
+                .await
+                .unwrap();
+            assert_eq!(fetched, payment);
+
+            // Timestamps written by SQLite itself use a space instead of a `T`
+            let now: NaiveDateTime = pool
+                .fetch_one(
+                    "UPDATE payments SET scheduled = CURRENT_TIMESTAMP RETURNING scheduled",
+                    &[],
+                )
+                .await
+                .unwrap();
+            assert!(now > scheduled);
+        }
+
+        #[tokio::test]
+        async fn test_schema_builder_runs_ddl() {
+            use crate::schema::{alter_table, create_table, drop_table_if_exists, SchemaBuilder};
+            use crate::insert;
+            use std::collections::HashMap;
+
+            let pool = memory_pool().await;
+            create_table("users", |t| {
+                t.increments("id");
+                t.string("email", 255).not_null().unique();
+                t.timestamps();
+            })
+            .execute(&pool)
+            .await
+            .unwrap();
+
+            alter_table("users", |t| {
+                t.string("nickname", 50).unique();
+                t.rename_column("email", "email_address");
+            })
+            .execute(&pool)
+            .await
+            .unwrap();
+
+            let mut data = HashMap::new();
+            data.insert("email_address".to_string(), Value::from("a@example.com"));
+            data.insert("nickname".to_string(), Value::from("a"));
+            insert("users").values(data.clone()).execute(&pool).await.unwrap();
+
+            // The unique index on the added column is enforced
+            assert!(insert("users").values(data).execute(&pool).await.is_err());
+
+            drop_table_if_exists("users").execute(&pool).await.unwrap();
+            drop_table_if_exists("users").execute(&pool).await.unwrap();
+        }
+
+        #[test]
+        fn test_value_to_json_conversion() {
+            // Test basic value conversions
+            assert_eq!(value_to_json(&Value::Null), serde_json::Value::Null);
+            assert_eq!(
+                value_to_json(&Value::Bool(true)),
+                serde_json::Value::Bool(true)
+            );
+            assert_eq!(
+                value_to_json(&Value::I32(42)),
+                serde_json::Value::Number(serde_json::Number::from(42))
+            );
+            assert_eq!(
+                value_to_json(&Value::String("test".to_string())),
+                serde_json::Value::String("test".to_string())
+            );
+
+            // Test array conversion
+            let arr = Value::Array(vec![Value::I32(1), Value::I32(2), Value::I32(3)]);
+            let json_arr = value_to_json(&arr);
+            assert_eq!(json_arr, serde_json::json!([1, 2, 3]));
+        }
+
+        #[test]
+        fn test_parameter_binding_types() {
+            // This test verifies our bind_values_to_query function can handle different Value types
+            // We can't easily test the actual binding without a real database connection,
+            // but we can verify the function doesn't panic with various value types
+            use sqlx::query;
+
+            let params = vec![
+                Value::Null,
+                Value::Bool(true),
+                Value::I32(42),
+                Value::I64(123456),
+                Value::F32(1.5),
+                Value::F64(2.25),
+                Value::String("hello".to_string()),
+                Value::Bytes(vec![1, 2, 3, 4]),
+                Value::Json(serde_json::json!({"key": "value"})),
+                Value::Array(vec![Value::I32(1), Value::I32(2)]),
+            ];
+
+            // Create a dummy query - this won't execute but will test the binding logic
+            let query = query("SELECT * FROM users WHERE id = ?1 AND name = ?2");
+
+            // Test that binding doesn't panic (we can't test execution without a real DB)
+            let _bound_query = bind_values_to_query(query, &params[0..2]);
+            // If we get here without panicking, the binding logic works
+        }
+
+        #[test]
+        fn test_json_and_array_serialization() {
+            // Test that JSON and Array values are properly serialized for SQLite
+            let json_value = Value::Json(serde_json::json!({"key": "value", "number": 42}));
+            let array_value = Value::Array(vec![
+                Value::String("item1".to_string()),
+                Value::I32(42),
+                Value::Bool(true),
+            ]);
+
+            // These should serialize to JSON strings for SQLite storage
+            match json_value {
+                Value::Json(ref j) => {
+                    let serialized = serde_json::to_string(j).unwrap();
+                    assert!(serialized.contains("key"));
+                    assert!(serialized.contains("value"));
+                }
+                _ => panic!("Expected JSON value"),
+            }
+
+            let json_arr = serde_json::Value::Array(
+                array_value
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(value_to_json)
+                    .collect(),
+            );
+            let serialized_array = serde_json::to_string(&json_arr).unwrap();
+            assert!(serialized_array.contains("item1"));
+            assert!(serialized_array.contains("42"));
+            assert!(serialized_array.contains("true"));
+        }
+
+        #[test]
+        fn test_isolation_level_pragmas() {
+            // Test that isolation levels map to appropriate SQLite pragmas
+            // SQLite has limited isolation level support compared to PostgreSQL
+
+            // These are the pragmas we expect for each isolation level
+            let read_uncommitted = "PRAGMA read_uncommitted = true";
+            let read_committed = "PRAGMA read_uncommitted = false";
+            let repeatable_read = "PRAGMA read_uncommitted = false";
+            let serializable = "PRAGMA read_uncommitted = false";
+
+            assert!(read_uncommitted.contains("true"));
+            assert!(read_committed.contains("false"));
+            assert!(repeatable_read.contains("false"));
+            assert!(serializable.contains("false"));
+        }
+    }
+}
+
+/// MySQL connection pool wrapper
+#[cfg(feature = "mysql")]
+pub mod mysql {
+    use super::row::{Field, RawRow};
+    use super::*;
+    use crate::dialect::MySqlDialect;
+    use sqlx::MySqlPool as SqlxMySqlPool;
+
+    /// MySQL connection pool wrapper
+    #[derive(Clone)]
+    pub struct MySqlPool {
+        inner: SqlxMySqlPool,
+    }
+
+    impl MySqlPool {
+        /// Create a new MySQL pool from a connection string
+        pub async fn new(database_url: &str) -> Result<Self> {
+            let pool = SqlxMySqlPool::connect(database_url).await?;
+            Ok(Self { inner: pool })
+        }
+
+        /// Create from an existing sqlx MySqlPool
+        pub fn from_pool(pool: SqlxMySqlPool) -> Self {
+            Self { inner: pool }
+        }
+    }
+
+    impl ConnectionPool for MySqlPool {
+        type Connection = sqlx::pool::PoolConnection<sqlx::MySql>;
+
+        fn dialect(&self) -> &'static dyn Dialect {
+            &MySqlDialect
+        }
+
+        async fn acquire(&self) -> Result<Self::Connection> {
+            Ok(self.inner.acquire().await?)
+        }
+
+        async fn execute(&self, sql: &str, params: &[Value]) -> Result<u64> {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let result = bound_query.execute(&self.inner).await?;
+            Ok(result.rows_affected())
+        }
+
+        async fn execute_all(&self, statements: &[(String, Vec<Value>)]) -> Result<u64> {
+            execute_all_in_transaction(self, statements).await
+        }
+
+        async fn fetch_all<T>(&self, sql: &str, params: &[Value]) -> Result<Vec<T>>
+        where
+            T: DeserializeOwned + Send + Unpin,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let rows = bound_query.fetch_all(&self.inner).await?;
+
+            let mut results = Vec::with_capacity(rows.len());
+            for row in rows {
+                let item: T = Row::new(&row).deserialize()?;
+                results.push(item);
+            }
+            Ok(results)
+        }
+
+        async fn fetch_one<T>(&self, sql: &str, params: &[Value]) -> Result<T>
+        where
+            T: DeserializeOwned + Send + Unpin,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let row = bound_query.fetch_one(&self.inner).await?;
+
+            let item: T = Row::new(&row).deserialize()?;
+            Ok(item)
+        }
+
+        async fn fetch_optional<T>(&self, sql: &str, params: &[Value]) -> Result<Option<T>>
+        where
+            T: DeserializeOwned + Send + Unpin,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            if let Some(row) = bound_query.fetch_optional(&self.inner).await? {
+                let item: T = Row::new(&row).deserialize()?;
+                Ok(Some(item))
+            } else {
+                Ok(None)
+            }
+        }
+
+        fn fetch_stream<'a, T>(
+            &'a self,
+            sql: &'a str,
+            params: &'a [Value],
+        ) -> impl Stream<Item = Result<T>> + Send + 'a
+        where
+            T: DeserializeOwned + Send + Unpin + 'a,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            bound_query
+                .fetch(&self.inner)
+                .map(|row| Row::new(&row?).deserialize())
+        }
+
+        async fn fetch_all_as<T>(&self, sql: &str, params: &[Value]) -> Result<Vec<T>>
+        where
+            T: FromRow + Send,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let rows = bound_query.fetch_all(&self.inner).await?;
+            rows.iter().map(|row| T::from_row(&Row::new(row))).collect()
+        }
+    }
+
+    /// MySQL transaction wrapper
+    pub struct MySqlTransaction {
+        inner: sqlx::Transaction<'static, sqlx::MySql>,
+    }
+
+    impl Transaction for MySqlTransaction {
+        fn dialect(&self) -> &'static dyn Dialect {
+            &MySqlDialect
+        }
+
+        async fn execute(&mut self, sql: &str, params: &[Value]) -> Result<u64> {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let result = bound_query.execute(&mut *self.inner).await?;
+            Ok(result.rows_affected())
+        }
+
+        async fn fetch_all<T>(&mut self, sql: &str, params: &[Value]) -> Result<Vec<T>>
+        where
+            T: DeserializeOwned + Send + Unpin,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let rows = bound_query.fetch_all(&mut *self.inner).await?;
+
+            let mut results = Vec::with_capacity(rows.len());
+            for row in rows {
+                let item: T = Row::new(&row).deserialize()?;
+                results.push(item);
+            }
+            Ok(results)
+        }
+
+        async fn fetch_one<T>(&mut self, sql: &str, params: &[Value]) -> Result<T>
+        where
+            T: DeserializeOwned + Send + Unpin,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let row = bound_query.fetch_one(&mut *self.inner).await?;
+
+            let item: T = Row::new(&row).deserialize()?;
+            Ok(item)
+        }
+
+        async fn fetch_optional<T>(&mut self, sql: &str, params: &[Value]) -> Result<Option<T>>
+        where
+            T: DeserializeOwned + Send + Unpin,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            if let Some(row) = bound_query.fetch_optional(&mut *self.inner).await? {
+                let item: T = Row::new(&row).deserialize()?;
+                Ok(Some(item))
+            } else {
+                Ok(None)
+            }
+        }
+
+        fn fetch_stream<'a, T>(
+            &'a mut self,
+            sql: &'a str,
+            params: &'a [Value],
+        ) -> impl Stream<Item = Result<T>> + Send + 'a
+        where
+            T: DeserializeOwned + Send + Unpin + 'a,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            bound_query
+                .fetch(&mut *self.inner)
+                .map(|row| Row::new(&row?).deserialize())
+        }
+
+        async fn fetch_all_as<T>(&mut self, sql: &str, params: &[Value]) -> Result<Vec<T>>
+        where
+            T: FromRow + Send,
+        {
+            let query = sqlx::query(sql);
+            let bound_query = bind_values_to_query(query, params);
+            let rows = bound_query.fetch_all(&mut *self.inner).await?;
+            rows.iter().map(|row| T::from_row(&Row::new(row))).collect()
+        }
+
+        async fn commit(self) -> Result<()> {
+            self.inner.commit().await?;
+            Ok(())
+        }
+
+        async fn rollback(self) -> Result<()> {
+            self.inner.rollback().await?;
+            Ok(())
+        }
 
         async fn savepoint(&mut self, name: &str) -> Result<()> {
-            let sql = format!("SAVEPOINT {}", name);
+            let sql = format!("SAVEPOINT {}", MySqlDialect.quote_identifier(name));
             sqlx::query(&sql).execute(&mut *self.inner).await?;
             Ok(())
         }
 
         async fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
-            let sql = format!("ROLLBACK TO SAVEPOINT {}", name);
+            let sql = format!("ROLLBACK TO SAVEPOINT {}", MySqlDialect.quote_identifier(name));
             sqlx::query(&sql).execute(&mut *self.inner).await?;
             Ok(())
         }
 
         async fn release_savepoint(&mut self, name: &str) -> Result<()> {
-            let sql = format!("RELEASE SAVEPOINT {}", name);
+            let sql = format!("RELEASE SAVEPOINT {}", MySqlDialect.quote_identifier(name));
             sqlx::query(&sql).execute(&mut *self.inner).await?;
             Ok(())
         }
     }
 
-    impl TransactionalPool for SqlitePool {
-        type Transaction = SqliteTransaction;
+    impl TransactionalPool for MySqlPool {
+        type Transaction = MySqlTransaction;
 
         async fn begin_transaction(&self) -> Result<Self::Transaction> {
             let txn = self.inner.begin().await?;
-            Ok(SqliteTransaction { inner: txn })
+            Ok(MySqlTransaction { inner: txn })
         }
 
         async fn begin_transaction_with_isolation(
             &self,
             isolation: IsolationLevel,
         ) -> Result<Self::Transaction> {
-            let mut txn = self.inner.begin().await?;
-
-            // SQLite supports fewer isolation levels than PostgreSQL
-            // Map to SQLite pragma settings
-            let pragma = match isolation {
-                IsolationLevel::ReadUncommitted => "PRAGMA read_uncommitted = true",
-                IsolationLevel::ReadCommitted => "PRAGMA read_uncommitted = false", // SQLite default
-                IsolationLevel::RepeatableRead => "PRAGMA read_uncommitted = false", // Best we can do
-                IsolationLevel::Serializable => "PRAGMA read_uncommitted = false", // SQLite default is serializable
-            };
-
-            sqlx::query(pragma).execute(&mut *txn).await?;
-            Ok(SqliteTransaction { inner: txn })
+            // MySQL rejects isolation changes inside an open transaction; without
+            // SESSION/GLOBAL the statement only applies to the next one started
+            // on this connection, so run it before BEGIN
+            let mut conn = self.inner.acquire().await?;
+            sqlx::query(&isolation_sql(isolation))
+                .execute(&mut *conn)
+                .await?;
+            let txn = sqlx::Transaction::begin(conn).await?;
+            Ok(MySqlTransaction { inner: txn })
         }
     }
 
-    /// Bind Archibald Values to a SQLx SQLite query
+    fn isolation_sql(isolation: IsolationLevel) -> String {
+        format!("SET TRANSACTION ISOLATION LEVEL {}", isolation.to_sql())
+    }
+
+    /// Bind Archibald Values to a SQLx MySQL query
     fn bind_values_to_query<'q>(
-        mut query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+        mut query: sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments>,
         params: &'q [Value],
-    ) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+    ) -> sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         for param in params {
             query = match param {
                 Value::Null => query.bind(None::<i32>), // Use Option<T> for NULL values
@@ -1139,18 +3131,25 @@ pub mod sqlite {
                 Value::F64(f) => query.bind(*f),
                 Value::String(s) => query.bind(s.as_str()),
                 Value::Bytes(b) => query.bind(b.as_slice()),
-                Value::Json(j) => {
-                    // SQLite doesn't have native JSON support, serialize as string
-                    let json_str = serde_json::to_string(j).unwrap_or_else(|_| "null".to_string());
-                    query.bind(json_str)
-                }
+                Value::Json(j) => query.bind(j),
+                #[cfg(feature = "uuid-support")]
+                Value::Uuid(u) => query.bind(*u),
+                #[cfg(feature = "datetime-support")]
+                Value::Date(d) => query.bind(*d),
+                #[cfg(feature = "datetime-support")]
+                Value::Time(t) => query.bind(*t),
+                #[cfg(feature = "datetime-support")]
+                Value::Timestamp(ts) => query.bind(*ts),
+                #[cfg(feature = "datetime-support")]
+                Value::TimestampTz(ts) => query.bind(*ts),
+                #[cfg(feature = "decimal-support")]
+                Value::Decimal(d) => query.bind(*d),
                 Value::Array(arr) => {
-                    // SQLite doesn't have native array support, serialize as JSON string
+                    // MySQL has no array type; IN lists are expanded by the dialect,
+                    // anything else is stored as a JSON document
                     let json_array =
                         serde_json::Value::Array(arr.iter().map(value_to_json).collect());
-                    let json_str =
-                        serde_json::to_string(&json_array).unwrap_or_else(|_| "[]".to_string());
-                    query.bind(json_str)
+                    query.bind(json_array)
                 }
                 Value::SubqueryPlaceholder => {
                     // Subqueries should have been resolved before this point
@@ -1183,196 +3182,401 @@ pub mod sqlite {
             ),
             Value::Json(j) => j.clone(),
             Value::Array(arr) => serde_json::Value::Array(arr.iter().map(value_to_json).collect()),
+            // Serialized in their text form, e.g. "2024-02-29T12:30:00Z"
+            #[cfg(feature = "uuid-support")]
+            Value::Uuid(u) => serde_json::json!(u),
+            #[cfg(feature = "datetime-support")]
+            Value::Date(d) => serde_json::json!(d),
+            #[cfg(feature = "datetime-support")]
+            Value::Time(t) => serde_json::json!(t),
+            #[cfg(feature = "datetime-support")]
+            Value::Timestamp(ts) => serde_json::json!(ts),
+            #[cfg(feature = "datetime-support")]
+            Value::TimestampTz(ts) => serde_json::json!(ts),
+            #[cfg(feature = "decimal-support")]
+            Value::Decimal(d) => serde_json::json!(d),
             Value::SubqueryPlaceholder => serde_json::Value::Null,
         }
     }
 
-    fn row_to_json_value(row: &sqlx::sqlite::SqliteRow) -> Result<serde_json::Value> {
-        use sqlx::{Column, Row, TypeInfo, ValueRef};
-        use serde_json::{Map, Value as JsonValue};
-        
-        let mut object = Map::new();
-        
-        for (i, column) in row.columns().iter().enumerate() {
-            let column_name = column.name().to_string();
-            let raw_value = row.try_get_raw(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-            
-            // Handle NULL values
-            if raw_value.is_null() {
-                object.insert(column_name, JsonValue::Null);
-                continue;
+    impl RawRow for sqlx::mysql::MySqlRow {
+        fn len(&self) -> usize {
+            sqlx::Row::len(self)
+        }
+
+        fn column_name(&self, index: usize) -> &str {
+            use sqlx::{Column as _, Row as _};
+            self.columns()[index].name()
+        }
+
+        fn field(&self, index: usize) -> Result<Field<'_>> {
+            use sqlx::{Column as _, Row as _, TypeInfo as _, ValueRef as _};
+
+            if self.try_get_raw(index)?.is_null() {
+                return Ok(Field::Null);
             }
-            
-            // SQLite has a simpler type system than PostgreSQL
-            let type_info = column.type_info();
-            let json_value = match type_info.name() {
-                "BOOLEAN" => {
-                    let val: bool = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    JsonValue::Bool(val)
-                }
-                "INTEGER" => {
-                    // SQLite INTEGER can be i32 or i64, try both
-                    if let Ok(val) = row.try_get::<i32, _>(i) {
-                        JsonValue::Number(serde_json::Number::from(val))
-                    } else if let Ok(val) = row.try_get::<i64, _>(i) {
-                        JsonValue::Number(serde_json::Number::from(val))
-                    } else {
-                        JsonValue::Null
-                    }
-                }
-                "REAL" => {
-                    let val: f64 = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    JsonValue::Number(serde_json::Number::from_f64(val).unwrap_or_else(|| serde_json::Number::from(0)))
-                }
-                "TEXT" => {
-                    let val: String = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    // SQLite stores JSON as TEXT, so try to parse it
-                    if let Ok(parsed_json) = serde_json::from_str::<JsonValue>(&val) {
-                        // If it parses as valid JSON, use the parsed value
-                        parsed_json
-                    } else {
-                        // Otherwise, treat as plain string
-                        JsonValue::String(val)
-                    }
+
+            Ok(match self.columns()[index].type_info().name() {
+                // TINYINT(1)
+                "BOOLEAN" => Field::Bool(self.try_get(index)?),
+                "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "BIGINT" => {
+                    Field::I64(self.try_get(index)?)
                 }
-                "BLOB" => {
-                    let val: Vec<u8> = row.try_get(i).map_err(|e| crate::Error::sql_generation(e.to_string()))?;
-                    // Convert bytes to array of numbers for JSON representation
-                    JsonValue::Array(val.into_iter().map(|b| JsonValue::Number(serde_json::Number::from(b))).collect())
+                "TINYINT UNSIGNED" | "SMALLINT UNSIGNED" | "MEDIUMINT UNSIGNED" | "INT UNSIGNED"
+                | "BIGINT UNSIGNED" => Field::U64(self.try_get(index)?),
+                "YEAR" => Field::U64(self.try_get_unchecked::<u16, _>(index)?.into()),
+                "FLOAT" => Field::F64(self.try_get::<f32, _>(index)?.into()),
+                "DOUBLE" => Field::F64(self.try_get(index)?),
+                // Sent as text; kept as a string so no precision is lost
+                "DECIMAL" | "SET" | "CHAR" | "VARCHAR" | "TINYTEXT" | "TEXT" | "MEDIUMTEXT"
+                | "LONGTEXT" | "ENUM" => Field::Str(self.try_get_unchecked(index)?),
+                "BINARY" | "VARBINARY" | "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB" => {
+                    Field::Bytes(self.try_get_unchecked(index)?)
                 }
-                _ => {
-                    // SQLite fallback: try to get as string first, then other types
-                    if let Ok(val) = row.try_get::<String, _>(i) {
-                        JsonValue::String(val)
-                    } else if let Ok(val) = row.try_get::<i64, _>(i) {
-                        JsonValue::Number(serde_json::Number::from(val))
-                    } else if let Ok(val) = row.try_get::<f64, _>(i) {
-                        JsonValue::Number(serde_json::Number::from_f64(val).unwrap_or_else(|| serde_json::Number::from(0)))
-                    } else if let Ok(val) = row.try_get::<bool, _>(i) {
-                        JsonValue::Bool(val)
+                "JSON" => Field::Json(self.try_get_unchecked(index)?),
+                name @ ("DATE" | "DATETIME" | "TIMESTAMP" | "TIME") => {
+                    let bytes: &[u8] = self.try_get_unchecked(index)?;
+                    let text = decode_temporal(name, bytes).unwrap_or_else(|| {
+                        // Text protocol results are already formatted
+                        let text = String::from_utf8_lossy(bytes);
+                        row::iso_datetime(&text).unwrap_or_else(|| text.into_owned())
+                    });
+                    // TIMESTAMP is stored in UTC, and sqlx sessions use UTC
+                    if name == "TIMESTAMP" {
+                        Field::String(text + "+00:00")
                     } else {
-                        JsonValue::String(format!("Unsupported type: {}", type_info.name()))
+                        Field::String(text)
                     }
                 }
-            };
-            
-            object.insert(column_name, json_value);
+                name => Field::Unsupported(name),
+            })
+        }
+    }
+
+    /// Format a binary protocol DATE, DATETIME, TIMESTAMP or TIME
+    ///
+    /// Binary values start with their length (0 to 12) where text protocol
+    /// values start with a digit or a sign, so `None` means "already text".
+    fn decode_temporal(type_name: &str, bytes: &[u8]) -> Option<String> {
+        let (&len, body) = bytes.split_first()?;
+        if len >= b' ' || body.len() != usize::from(len) {
+            return None;
+        }
+        let byte = |i: usize| u64::from(body.get(i).copied().unwrap_or(0));
+        let micros = |at: usize| {
+            body.get(at..at + 4)
+                .map_or(0, |b| u64::from(u32::from_le_bytes([b[0], b[1], b[2], b[3]])))
+        };
+
+        if type_name == "TIME" {
+            let sign = if byte(0) == 1 { "-" } else { "" };
+            let days = body.get(1..5).map_or(0, |b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+            let time = row::format_time(
+                u64::from(days) * 24 + byte(5),
+                byte(6),
+                byte(7),
+                micros(8),
+            );
+            return Some(format!("{}{}", sign, time));
+        }
+
+        let year = body.get(..2).map_or(0, |b| u16::from_le_bytes([b[0], b[1]]));
+        let date = format!("{:04}-{:02}-{:02}", year, byte(2), byte(3));
+        if type_name == "DATE" {
+            return Some(date);
         }
-        
-        Ok(JsonValue::Object(object))
+        Some(format!(
+            "{}T{}",
+            date,
+            row::format_time(byte(4), byte(5), byte(6), micros(7))
+        ))
     }
 
     #[cfg(test)]
-    mod sqlite_tests {
+    mod mysql_tests {
         use super::*;
+        use crate::{ExecutableModification, ExecutableQuery, from, insert};
+        use serde::Deserialize;
+        use std::collections::HashMap;
 
-        #[test]
-        fn test_sqlite_pool_creation() {
-            // Test that SqlitePool can be created from SqlitePool
-            // This is mainly a compilation test since we can't easily create a real SqlitePool in tests
-            assert!(true); // Placeholder test
+        /// Pool for the server in `MYSQL_DATABASE_URL`, or `None` to skip the test
+        ///
+        /// A local container works, e.g. `docker run -p 3306:3306 -e MYSQL_DATABASE=archibald
+        /// -e MYSQL_ALLOW_EMPTY_PASSWORD=yes mysql:8` with
+        /// `MYSQL_DATABASE_URL=mysql://root@localhost/archibald`.
+        async fn test_pool() -> Option<MySqlPool> {
+            let url = std::env::var("MYSQL_DATABASE_URL").ok()?;
+            Some(MySqlPool::new(&url).await.unwrap())
+        }
+
+        fn row(columns: &[(&str, Value)]) -> HashMap<String, Value> {
+            columns
+                .iter()
+                .map(|(column, value)| (column.to_string(), value.clone()))
+                .collect()
         }
 
         #[test]
         fn test_value_to_json_conversion() {
-            // Test basic value conversions
             assert_eq!(value_to_json(&Value::Null), serde_json::Value::Null);
             assert_eq!(
-                value_to_json(&Value::Bool(true)),
-                serde_json::Value::Bool(true)
+                value_to_json(&Value::Bool(false)),
+                serde_json::Value::Bool(false)
             );
             assert_eq!(
-                value_to_json(&Value::I32(42)),
-                serde_json::Value::Number(serde_json::Number::from(42))
+                value_to_json(&Value::I64(7)),
+                serde_json::Value::Number(serde_json::Number::from(7))
             );
             assert_eq!(
-                value_to_json(&Value::String("test".to_string())),
-                serde_json::Value::String("test".to_string())
+                value_to_json(&Value::Bytes(vec![1, 2])),
+                serde_json::json!([1, 2])
+            );
+            assert_eq!(
+                value_to_json(&Value::Array(vec![Value::from("a"), Value::I32(1)])),
+                serde_json::json!(["a", 1])
             );
-
-            // Test array conversion
-            let arr = Value::Array(vec![Value::I32(1), Value::I32(2), Value::I32(3)]);
-            let json_arr = value_to_json(&arr);
-            assert_eq!(json_arr, serde_json::json!([1, 2, 3]));
         }
 
         #[test]
         fn test_parameter_binding_types() {
-            // This test verifies our bind_values_to_query function can handle different Value types
-            // We can't easily test the actual binding without a real database connection,
-            // but we can verify the function doesn't panic with various value types
-            use sqlx::query;
-
+            // Binding needs no connection, so every Value variant can be checked here
             let params = vec![
                 Value::Null,
                 Value::Bool(true),
                 Value::I32(42),
                 Value::I64(123456),
-                Value::F32(3.14),
-                Value::F64(2.718),
+                Value::F32(1.5),
+                Value::F64(2.25),
                 Value::String("hello".to_string()),
                 Value::Bytes(vec![1, 2, 3, 4]),
                 Value::Json(serde_json::json!({"key": "value"})),
                 Value::Array(vec![Value::I32(1), Value::I32(2)]),
             ];
 
-            // Create a dummy query - this won't execute but will test the binding logic
-            let query = query("SELECT * FROM users WHERE id = ?1 AND name = ?2");
-
-            // Test that binding doesn't panic (we can't test execution without a real DB)
-            let _bound_query = bind_values_to_query(query, &params[0..2]);
-            // If we get here without panicking, the binding logic works
+            let query = sqlx::query("SELECT ?, ?, ?, ?, ?, ?, ?, ?, ?, ?");
+            let _bound_query = bind_values_to_query(query, &params);
         }
 
         #[test]
-        fn test_json_and_array_serialization() {
-            // Test that JSON and Array values are properly serialized for SQLite
-            let json_value = Value::Json(serde_json::json!({"key": "value", "number": 42}));
-            let array_value = Value::Array(vec![
-                Value::String("item1".to_string()),
-                Value::I32(42),
-                Value::Bool(true),
-            ]);
+        fn test_queries_render_for_mysql() {
+            use crate::builder::common::QueryBuilder;
 
-            // These should serialize to JSON strings for SQLite storage
-            match json_value {
-                Value::Json(ref j) => {
-                    let serialized = serde_json::to_string(j).unwrap();
-                    assert!(serialized.contains("key"));
-                    assert!(serialized.contains("value"));
-                }
-                _ => panic!("Expected JSON value"),
-            }
+            let query = from("users")
+                .select(("id", "name"))
+                .where_(("id", crate::op::IN, vec![1, 2]))
+                .limit(5);
 
-            let json_arr = serde_json::Value::Array(
-                array_value
-                    .as_array()
-                    .unwrap()
-                    .iter()
-                    .map(value_to_json)
-                    .collect(),
+            let (sql, params) = query.to_sql_with_params(&MySqlDialect).unwrap();
+            assert_eq!(
+                sql,
+                "SELECT `id`, `name` FROM `users` WHERE `id` IN (?, ?) LIMIT 5"
             );
-            let serialized_array = serde_json::to_string(&json_arr).unwrap();
-            assert!(serialized_array.contains("item1"));
-            assert!(serialized_array.contains("42"));
-            assert!(serialized_array.contains("true"));
+            assert_eq!(params, vec![Value::I32(1), Value::I32(2)]);
         }
 
         #[test]
-        fn test_isolation_level_pragmas() {
-            // Test that isolation levels map to appropriate SQLite pragmas
-            // SQLite has limited isolation level support compared to PostgreSQL
+        fn test_isolation_statements() {
+            assert_eq!(
+                isolation_sql(IsolationLevel::RepeatableRead),
+                "SET TRANSACTION ISOLATION LEVEL REPEATABLE READ"
+            );
+            assert_eq!(
+                isolation_sql(IsolationLevel::ReadUncommitted),
+                "SET TRANSACTION ISOLATION LEVEL READ UNCOMMITTED"
+            );
+        }
 
-            // These are the pragmas we expect for each isolation level
-            let read_uncommitted = "PRAGMA read_uncommitted = true";
-            let read_committed = "PRAGMA read_uncommitted = false";
-            let repeatable_read = "PRAGMA read_uncommitted = false";
-            let serializable = "PRAGMA read_uncommitted = false";
+        #[test]
+        fn test_binary_temporal_values() {
+            let datetime = [11, 0xE8, 0x07, 2, 29, 12, 34, 56, 0x20, 0xA1, 0x07, 0];
+            assert_eq!(
+                decode_temporal("DATETIME", &datetime).unwrap(),
+                "2024-02-29T12:34:56.5"
+            );
+            assert_eq!(
+                decode_temporal("DATE", &[4, 0xE8, 0x07, 2, 29]).unwrap(),
+                "2024-02-29"
+            );
+            assert_eq!(
+                decode_temporal("TIMESTAMP", &[0]).unwrap(),
+                "0000-00-00T00:00:00"
+            );
+            assert_eq!(
+                decode_temporal("TIME", &[8, 1, 1, 0, 0, 0, 2, 3, 4]).unwrap(),
+                "-26:03:04"
+            );
+            // Text protocol values are left alone
+            assert!(decode_temporal("DATE", b"2024-02-29").is_none());
+        }
 
-            assert!(read_uncommitted.contains("true"));
-            assert!(read_committed.contains("false"));
-            assert!(repeatable_read.contains("false"));
-            assert!(serializable.contains("false"));
+        #[cfg(feature = "datetime-support")]
+        #[tokio::test]
+        async fn test_temporal_round_trip() {
+            use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+            let Some(pool) = test_pool().await else {
+                return;
+            };
+
+            pool.execute("DROP TABLE IF EXISTS archibald_schedule", &[])
+                .await
+                .unwrap();
+            pool.execute(
+                "CREATE TABLE archibald_schedule (
+                    due DATE NOT NULL,
+                    local_at DATETIME(6) NOT NULL,
+                    created_at TIMESTAMP(6) NOT NULL
+                )",
+                &[],
+            )
+            .await
+            .unwrap();
+
+            let local_at = NaiveDate::from_ymd_opt(2024, 2, 29)
+                .unwrap()
+                .and_hms_micro_opt(9, 15, 0, 250_000)
+                .unwrap();
+            let created_at = Utc.from_utc_datetime(&local_at);
+            pool.execute(
+                "INSERT INTO archibald_schedule VALUES (?, ?, ?)",
+                &[local_at.date().into(), local_at.into(), created_at.into()],
+            )
+            .await
+            .unwrap();
+
+            let row: (NaiveDate, NaiveDateTime, DateTime<Utc>) = pool
+                .fetch_one("SELECT due, local_at, created_at FROM archibald_schedule", &[])
+                .await
+                .unwrap();
+            assert_eq!(row, (local_at.date(), local_at, created_at));
+
+            pool.execute("DROP TABLE archibald_schedule", &[])
+                .await
+                .unwrap();
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Account {
+            id: u64,
+            name: String,
+            active: bool,
+            balance: String,
+            tags: serde_json::Value,
+        }
+
+        #[tokio::test]
+        async fn test_round_trip_types() {
+            let Some(pool) = test_pool().await else {
+                return;
+            };
+
+            pool.execute("DROP TABLE IF EXISTS archibald_accounts", &[])
+                .await
+                .unwrap();
+            pool.execute(
+                "CREATE TABLE archibald_accounts (
+                    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+                    name VARCHAR(255) NOT NULL,
+                    active BOOLEAN NOT NULL,
+                    balance DECIMAL(10, 2) NOT NULL,
+                    tags JSON NOT NULL
+                )",
+                &[],
+            )
+            .await
+            .unwrap();
+
+            let inserted = insert("archibald_accounts")
+                .values(row(&[
+                    ("name", Value::from("Jane")),
+                    ("active", Value::Bool(true)),
+                    ("balance", Value::F64(12.5)),
+                    ("tags", Value::Json(serde_json::json!(["admin"]))),
+                ]))
+                .execute(&pool)
+                .await
+                .unwrap();
+            assert_eq!(inserted, 1);
+
+            let accounts: Vec<Account> = from("archibald_accounts")
+                .select(("id", "name", "active", "balance", "tags"))
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(
+                accounts,
+                vec![Account {
+                    id: 1,
+                    name: "Jane".to_string(),
+                    active: true,
+                    balance: "12.50".to_string(),
+                    tags: serde_json::json!(["admin"]),
+                }]
+            );
+
+            pool.execute("DROP TABLE archibald_accounts", &[])
+                .await
+                .unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_transactions_and_savepoints() {
+            let Some(pool) = test_pool().await else {
+                return;
+            };
+
+            pool.execute("DROP TABLE IF EXISTS archibald_events", &[])
+                .await
+                .unwrap();
+            pool.execute(
+                "CREATE TABLE archibald_events (name VARCHAR(64) NOT NULL) ENGINE = InnoDB",
+                &[],
+            )
+            .await
+            .unwrap();
+
+            let mut txn = pool
+                .begin_transaction_with_isolation(IsolationLevel::Serializable)
+                .await
+                .unwrap();
+            insert("archibald_events")
+                .values(row(&[("name", Value::from("kept"))]))
+                .execute_tx(&mut txn)
+                .await
+                .unwrap();
+            txn.savepoint("before_discard").await.unwrap();
+            insert("archibald_events")
+                .values(row(&[("name", Value::from("discarded"))]))
+                .execute_tx(&mut txn)
+                .await
+                .unwrap();
+            txn.rollback_to_savepoint("before_discard").await.unwrap();
+            txn.release_savepoint("before_discard").await.unwrap();
+            txn.commit().await.unwrap();
+
+            let mut txn = pool.begin_transaction().await.unwrap();
+            insert("archibald_events")
+                .values(row(&[("name", Value::from("rolled back"))]))
+                .execute_tx(&mut txn)
+                .await
+                .unwrap();
+            txn.rollback().await.unwrap();
+
+            #[derive(Deserialize)]
+            struct Event {
+                name: String,
+            }
+            let events: Vec<Event> = from("archibald_events")
+                .select("name")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            let names: Vec<_> = events.into_iter().map(|e| e.name).collect();
+            assert_eq!(names, vec!["kept"]);
+
+            pool.execute("DROP TABLE archibald_events", &[])
+                .await
+                .unwrap();
         }
     }
 }
@@ -1461,7 +3665,7 @@ mod tests {
                 let user: T = serde_json::from_value(user_json)?;
                 Ok(user)
             } else {
-                return Err(crate::Error::sql_generation("No mock data for this type"));
+                Err(crate::Error::sql_generation("No mock data for this type"))
             }
         }
 
@@ -1534,6 +3738,21 @@ mod tests {
         assert_eq!(affected, 1);
     }
 
+    #[tokio::test]
+    async fn test_chunked_insert_needs_execute_all() {
+        let pool = MockPool::new();
+        let rows: Vec<HashMap<String, Value>> = (1..=3)
+            .map(|id| HashMap::from([("id".to_string(), Value::from(id))]))
+            .collect();
+
+        let single = crate::insert("users").values_many(rows.clone()).chunked(3);
+        assert_eq!(single.execute(&pool).await.unwrap(), 1);
+
+        let split = crate::insert("users").values_many(rows).chunked(2);
+        let result = split.execute(&pool).await;
+        assert!(matches!(result, Err(crate::Error::InvalidQuery { .. })));
+    }
+
     #[tokio::test]
     async fn test_update_execute() {
         let pool = MockPool::new();
@@ -1580,4 +3799,88 @@ mod tests {
         let result = query.execute(&pool).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_fetch_stream_falls_back_to_fetch_all() {
+        let users: Vec<User> = from("users")
+            .select(("id", "name", "email"))
+            .fetch_stream(&MockPool::new())
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[1].name, "Jane");
+
+        let pool = MockPool::with_failure();
+        let mut failing = std::pin::pin!(from("users").select("*").fetch_stream(&pool));
+        let first: Option<Result<User>> = failing.next().await;
+        assert!(first.unwrap().is_err());
+        assert!(failing.next().await.is_none());
+    }
+
+    // Mock pool that renders with the PostgreSQL dialect and records the SQL it receives
+    #[derive(Clone, Default)]
+    struct RecordingPool {
+        statements: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl ConnectionPool for RecordingPool {
+        type Connection = ();
+
+        fn dialect(&self) -> &'static dyn Dialect {
+            &crate::dialect::PostgresDialect
+        }
+
+        async fn acquire(&self) -> Result<Self::Connection> {
+            Ok(())
+        }
+
+        async fn execute(&self, sql: &str, _params: &[Value]) -> Result<u64> {
+            self.statements.lock().unwrap().push(sql.to_string());
+            Ok(1)
+        }
+
+        async fn fetch_all<T>(&self, sql: &str, _params: &[Value]) -> Result<Vec<T>>
+        where
+            T: DeserializeOwned + Send + Unpin,
+        {
+            self.statements.lock().unwrap().push(sql.to_string());
+            Ok(Vec::new())
+        }
+
+        async fn fetch_one<T>(&self, _sql: &str, _params: &[Value]) -> Result<T>
+        where
+            T: DeserializeOwned + Send + Unpin,
+        {
+            Err(crate::Error::sql_generation("Mock fetch_one"))
+        }
+
+        async fn fetch_optional<T>(&self, _sql: &str, _params: &[Value]) -> Result<Option<T>>
+        where
+            T: DeserializeOwned + Send + Unpin,
+        {
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_dialect_is_used_for_rendering() {
+        let pool = RecordingPool::default();
+
+        let _: Vec<User> = from("users")
+            .select("*")
+            .where_(("id", 1))
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        crate::delete("users")
+            .where_(("id", 2))
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let statements = pool.statements.lock().unwrap();
+        assert_eq!(statements[0], r#"SELECT * FROM "users" WHERE "id" = $1"#);
+        assert_eq!(statements[1], r#"DELETE FROM "users" WHERE "id" = $1"#);
+    }
 }
@@ -0,0 +1,840 @@
+//! Decoding result rows into Rust types
+//!
+//! Rows are read column by column straight from the driver's row. [`Row`]
+//! exposes those columns to hand-written [`FromRow`] impls, and a serde
+//! `Deserializer` over the same view backs `fetch_all` and friends, so
+//! `#[derive(Deserialize)]` types are filled without building an intermediate
+//! JSON document.
+//!
+//! Structs are matched to columns by name, tuples by position, and a single
+//! column row can be decoded straight into a scalar such as `i64`. Types the
+//! JSON data model has no room for are handed over as strings: NUMERIC and
+//! DECIMAL keep their exact digits, UUIDs use the hyphenated form and dates and
+//! times are ISO 8601 (`2024-01-31T12:30:00`), which chrono's and time's serde
+//! impls accept.
+
+use crate::{Error, Result};
+use serde::Deserialize;
+use serde::de::{
+    self, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+
+/// A single column value, borrowed from the row where possible
+#[derive(Debug, Clone, PartialEq)]
+// Only the PostgreSQL backend produces every kind of field
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+pub(crate) enum Field<'r> {
+    Null,
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Str(&'r str),
+    /// Text rendered from a binary value, such as a NUMERIC or a UUID
+    String(String),
+    Bytes(&'r [u8]),
+    /// A JSON document as text, parsed by the target type
+    Json(&'r str),
+    JsonValue(&'r serde_json::Value),
+    List(Vec<Field<'r>>),
+    /// A column type without a mapping, only an error once it is decoded
+    Unsupported(&'r str),
+}
+
+/// Backend specific access to the columns of a row
+pub(crate) trait RawRow {
+    fn len(&self) -> usize;
+
+    fn column_name(&self, index: usize) -> &str;
+
+    fn field(&self, index: usize) -> Result<Field<'_>>;
+}
+
+/// A borrowed view of one result row
+///
+/// Handed to [`FromRow::from_row`]; columns are decoded on request with
+/// [`get`](Row::get) using the same rules as whole-row deserialization.
+#[derive(Clone, Copy)]
+pub struct Row<'r> {
+    raw: &'r dyn RawRow,
+}
+
+impl<'r> Row<'r> {
+    pub(crate) fn new(raw: &'r dyn RawRow) -> Self {
+        Self { raw }
+    }
+
+    /// Number of columns in the row
+    pub fn len(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Whether the row has no columns
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Column names, in result order
+    pub fn columns(&self) -> impl Iterator<Item = &'r str> + use<'r> {
+        let raw = self.raw;
+        (0..raw.len()).map(move |index| raw.column_name(index))
+    }
+
+    /// Decode the column with the given name
+    pub fn get<T>(&self, column: &str) -> Result<T>
+    where
+        T: Deserialize<'r>,
+    {
+        let index = self
+            .columns()
+            .position(|name| name == column)
+            .ok_or_else(|| Error::decode(format!("no column named `{}` in the row", column)))?;
+        self.get_index(index)
+    }
+
+    /// Decode the column at `index`
+    pub fn get_index<T>(&self, index: usize) -> Result<T>
+    where
+        T: Deserialize<'r>,
+    {
+        if index >= self.len() {
+            return Err(Error::decode(format!(
+                "column index {} out of range for a row of {} columns",
+                index,
+                self.len()
+            )));
+        }
+        let name = self.raw.column_name(index);
+        let field = self.raw.field(index).map_err(|e| in_column(name, e))?;
+        T::deserialize(FieldDeserializer { field }).map_err(|e| in_column(name, e))
+    }
+
+    /// Deserialize the whole row, matching struct fields to column names
+    pub fn deserialize<T>(&self) -> Result<T>
+    where
+        T: Deserialize<'r>,
+    {
+        T::deserialize(RowDeserializer { row: *self })
+    }
+}
+
+/// Hand-written decoding of a result row
+///
+/// The opt-in alternative to serde for the `fetch_all_as` methods, for types
+/// that need full control over how columns are read.
+///
+/// # Examples
+/// ```
+/// use archibald::{FromRow, Result, Row};
+///
+/// struct User {
+///     id: i64,
+///     email: String,
+///     nickname: Option<String>,
+/// }
+///
+/// impl FromRow for User {
+///     fn from_row(row: &Row<'_>) -> Result<Self> {
+///         Ok(Self {
+///             id: row.get("id")?,
+///             email: row.get("email")?,
+///             nickname: row.get("nickname")?,
+///         })
+///     }
+/// }
+/// ```
+pub trait FromRow: Sized {
+    fn from_row(row: &Row<'_>) -> Result<Self>;
+}
+
+/// Prefix decode errors with the column they came from
+fn in_column(column: &str, error: Error) -> Error {
+    match error {
+        Error::Decode { message } => Error::decode(format!("column `{}`: {}", column, message)),
+        Error::Serialization(e) => Error::decode(format!("column `{}`: {}", column, e)),
+        other => other,
+    }
+}
+
+/// Deserialize `T` from JSON text, rejecting trailing characters
+fn from_json<'de, T, F>(json: &'de str, deserialize: F) -> Result<T>
+where
+    F: FnOnce(&mut serde_json::Deserializer<serde_json::de::StrRead<'de>>) -> serde_json::Result<T>,
+{
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let value = deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Deserializes a whole row: a map of columns, or a sequence for tuples
+struct RowDeserializer<'r> {
+    row: Row<'r>,
+}
+
+impl<'r> RowDeserializer<'r> {
+    /// Decode a scalar from a row that must have exactly one column
+    fn single<T, F>(self, decode: F) -> Result<T>
+    where
+        F: FnOnce(FieldDeserializer<'r>) -> Result<T>,
+    {
+        if self.row.len() != 1 {
+            return Err(Error::decode(format!(
+                "expected a single column, found {}",
+                self.row.len()
+            )));
+        }
+        let name = self.row.raw.column_name(0);
+        let field = self.row.raw.field(0).map_err(|e| in_column(name, e))?;
+        decode(FieldDeserializer { field }).map_err(|e| in_column(name, e))
+    }
+}
+
+macro_rules! single_column {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                self.single(|field| field.$method(visitor))
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for RowDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(Columns::new(self.row))
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Columns::new(self.row))
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.single(|field| field.deserialize_enum(name, variants, visitor))
+    }
+
+    single_column! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_f32
+        deserialize_f64 deserialize_char deserialize_str deserialize_string deserialize_bytes
+        deserialize_byte_buf deserialize_identifier
+    }
+
+    serde::forward_to_deserialize_any! {
+        map struct
+    }
+}
+
+/// Walks the columns of a row, as map entries or sequence elements
+struct Columns<'r> {
+    row: Row<'r>,
+    index: usize,
+}
+
+impl<'r> Columns<'r> {
+    fn new(row: Row<'r>) -> Self {
+        Self { row, index: 0 }
+    }
+
+    fn next_field<T, S>(&mut self, seed: S) -> Result<T>
+    where
+        S: DeserializeSeed<'r, Value = T>,
+    {
+        let name = self.row.raw.column_name(self.index);
+        let field = self
+            .row
+            .raw
+            .field(self.index)
+            .map_err(|e| in_column(name, e))?;
+        self.index += 1;
+        seed.deserialize(FieldDeserializer { field })
+            .map_err(|e| in_column(name, e))
+    }
+}
+
+impl<'de> MapAccess<'de> for Columns<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.index >= self.row.len() {
+            return Ok(None);
+        }
+        let name = self.row.raw.column_name(self.index);
+        seed.deserialize(de::value::BorrowedStrDeserializer::new(name))
+            .map(Some)
+    }
+
+    fn next_value_seed<S>(&mut self, seed: S) -> Result<S::Value>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        self.next_field(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.len() - self.index)
+    }
+}
+
+impl<'de> SeqAccess<'de> for Columns<'de> {
+    type Error = Error;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        if self.index >= self.row.len() {
+            return Ok(None);
+        }
+        self.next_field(seed).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.len() - self.index)
+    }
+}
+
+/// Deserializes a single column value
+pub(crate) struct FieldDeserializer<'r> {
+    field: Field<'r>,
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Field<'de> {
+    type Deserializer = FieldDeserializer<'de>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        FieldDeserializer { field: self }
+    }
+}
+
+macro_rules! structured {
+    ($method:ident($($arg:ident: $ty:ty),*)) => {
+        fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value>
+        where
+            V: Visitor<'de>,
+        {
+            match self.field {
+                // Text columns may hold JSON documents (SQLite has no JSON type)
+                Field::Str(json) | Field::Json(json) => {
+                    from_json(json, |de| de.$method($($arg,)* visitor))
+                }
+                Field::JsonValue(value) => Ok(value.$method($($arg,)* visitor)?),
+                _ => self.deserialize_any(visitor),
+            }
+        }
+    };
+}
+
+impl<'de> Deserializer<'de> for FieldDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.field {
+            Field::Null => visitor.visit_unit(),
+            Field::Bool(b) => visitor.visit_bool(b),
+            Field::I64(i) => visitor.visit_i64(i),
+            Field::U64(u) => visitor.visit_u64(u),
+            Field::F64(f) => visitor.visit_f64(f),
+            Field::Str(s) => visitor.visit_borrowed_str(s),
+            Field::String(s) => visitor.visit_string(s),
+            // A sequence of numbers, which suits both `Vec<u8>` and `serde_json::Value`
+            Field::Bytes(bytes) => {
+                de::value::SeqDeserializer::new(bytes.iter().copied()).deserialize_any(visitor)
+            }
+            Field::Json(json) => from_json(json, |de| de.deserialize_any(visitor)),
+            Field::JsonValue(value) => Ok(value.deserialize_any(visitor)?),
+            Field::List(items) => {
+                de::value::SeqDeserializer::new(items.into_iter()).deserialize_any(visitor)
+            }
+            Field::Unsupported(type_name) => Err(Error::decode(format!(
+                "unsupported column type `{}`",
+                type_name
+            ))),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.field {
+            Field::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // SQLite and MySQL store booleans as integers
+        match self.field {
+            Field::I64(i @ (0 | 1)) => visitor.visit_bool(i == 1),
+            Field::U64(u @ (0 | 1)) => visitor.visit_bool(u == 1),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.field {
+            Field::Bytes(bytes) => visitor.visit_borrowed_bytes(bytes),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // Lets types such as `Uuid` accept their binary form
+        match self.field {
+            Field::Bytes(bytes) => visitor.visit_borrowed_bytes(bytes),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.field {
+            Field::Null => visitor.visit_unit(),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.field {
+            // Unit variants stored by name, e.g. a PostgreSQL enum or a status column
+            Field::Str(variant) => {
+                visitor.visit_enum(de::value::BorrowedStrDeserializer::new(variant))
+            }
+            Field::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Field::Json(json) => from_json(json, |de| de.deserialize_enum(name, variants, visitor)),
+            Field::JsonValue(value) => Ok(value.deserialize_enum(name, variants, visitor)?),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    structured!(deserialize_seq());
+    structured!(deserialize_tuple(len: usize));
+    structured!(deserialize_tuple_struct(name: &'static str, len: usize));
+    structured!(deserialize_map());
+    structured!(deserialize_struct(name: &'static str, fields: &'static [&'static str]));
+
+    serde::forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char unit_struct identifier
+    }
+}
+
+/// Days since 1970-01-01 to a (year, month, day) civil date
+#[cfg(feature = "postgres")]
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// `YYYY-MM-DD` for a number of days since 1970-01-01
+#[cfg(feature = "postgres")]
+pub(crate) fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// `HH:MM:SS[.ffffff]`, trailing zeros of the fraction removed
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+pub(crate) fn format_time(hours: u64, minutes: u64, seconds: u64, micros: u64) -> String {
+    let mut time = format!("{:02}:{:02}:{:02}", hours, minutes, seconds);
+    if micros > 0 {
+        let fraction = format!("{:06}", micros);
+        time.push('.');
+        time.push_str(fraction.trim_end_matches('0'));
+    }
+    time
+}
+
+/// ISO 8601 form of `YYYY-MM-DD HH:MM:SS` text, as databases print it with a
+/// space where chrono's serde impls expect a `T`
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+pub(crate) fn iso_datetime(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let datetime = bytes.len() >= 19 && bytes[4] == b'-' && bytes[10] == b' ' && bytes[13] == b':';
+    datetime.then(|| format!("{}T{}", &text[..10], &text[11..]))
+}
+
+/// `YYYY-MM-DDTHH:MM:SS[.ffffff]` for microseconds since 1970-01-01
+#[cfg(feature = "postgres")]
+pub(crate) fn format_timestamp(micros: i64) -> String {
+    const MICROS_PER_DAY: i64 = 86_400_000_000;
+    let days = micros.div_euclid(MICROS_PER_DAY);
+    let time = micros.rem_euclid(MICROS_PER_DAY) as u64;
+    format!(
+        "{}T{}",
+        format_date(days),
+        format_time(
+            time / 3_600_000_000,
+            time / 60_000_000 % 60,
+            time / 1_000_000 % 60,
+            time % 1_000_000
+        )
+    )
+}
+
+impl RawRow for serde_json::Map<String, serde_json::Value> {
+    fn len(&self) -> usize {
+        serde_json::Map::len(self)
+    }
+
+    fn column_name(&self, index: usize) -> &str {
+        self.keys()
+            .nth(index)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    fn field(&self, index: usize) -> Result<Field<'_>> {
+        use serde_json::Value as JsonValue;
+
+        Ok(match self.values().nth(index) {
+            None | Some(JsonValue::Null) => Field::Null,
+            Some(JsonValue::Bool(b)) => Field::Bool(*b),
+            Some(JsonValue::String(s)) => Field::Str(s),
+            Some(JsonValue::Number(n)) => match (n.as_i64(), n.as_u64()) {
+                (Some(i), _) => Field::I64(i),
+                (_, Some(u)) => Field::U64(u),
+                _ => Field::F64(n.as_f64().unwrap_or_default()),
+            },
+            Some(value) => Field::JsonValue(value),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    struct TestRow(Vec<(&'static str, Field<'static>)>);
+
+    impl RawRow for TestRow {
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+
+        fn column_name(&self, index: usize) -> &str {
+            self.0[index].0
+        }
+
+        fn field(&self, index: usize) -> Result<Field<'_>> {
+            Ok(self.0[index].1.clone())
+        }
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Status {
+        Active,
+        Banned,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Settings {
+        theme: String,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct User {
+        id: i64,
+        name: String,
+        active: bool,
+        avatar: Vec<u8>,
+        settings: Settings,
+        status: Status,
+        nickname: Option<String>,
+    }
+
+    fn user_row() -> TestRow {
+        TestRow(vec![
+            ("id", Field::I64(7)),
+            ("name", Field::Str("Jane")),
+            ("active", Field::I64(1)),
+            ("avatar", Field::Bytes(&[1, 2, 3])),
+            ("settings", Field::Str(r#"{"theme": "dark"}"#)),
+            ("status", Field::Str("banned")),
+            ("nickname", Field::Null),
+            ("ignored", Field::Unsupported("INTERVAL")),
+        ])
+    }
+
+    #[test]
+    fn test_struct_from_columns() {
+        let raw = user_row();
+        let user: User = Row::new(&raw).deserialize().unwrap();
+
+        assert_eq!(
+            user,
+            User {
+                id: 7,
+                name: "Jane".to_string(),
+                active: true,
+                avatar: vec![1, 2, 3],
+                settings: Settings {
+                    theme: "dark".to_string()
+                },
+                status: Status::Banned,
+                nickname: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_tuples_scalars_and_json_values() {
+        let id = TestRow(vec![("id", Field::Bytes(b"0123456789abcdef"))]);
+        assert_eq!(
+            Row::new(&id).get::<String>("id").unwrap(),
+            "0123456789abcdef"
+        );
+
+        let raw = TestRow(vec![
+            ("count", Field::I64(3)),
+            ("total", Field::String("12.50".to_string())),
+        ]);
+        let (count, total): (i32, String) = Row::new(&raw).deserialize().unwrap();
+        assert_eq!((count, total.as_str()), (3, "12.50"));
+
+        let single = TestRow(vec![("count", Field::I64(42))]);
+        assert_eq!(Row::new(&single).deserialize::<i64>().unwrap(), 42);
+        assert!(Row::new(&raw).deserialize::<i64>().is_err());
+
+        let tags = TestRow(vec![
+            ("tags", Field::List(vec![Field::Str("a"), Field::Null])),
+            ("doc", Field::Json(r#"{"k": [1, 2]}"#)),
+        ]);
+        let value: serde_json::Value = Row::new(&tags).deserialize().unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"tags": ["a", null], "doc": {"k": [1, 2]}})
+        );
+    }
+
+    #[test]
+    fn test_row_getters_and_errors() {
+        let raw = user_row();
+        let row = Row::new(&raw);
+
+        assert_eq!(row.len(), 8);
+        assert_eq!(row.columns().nth(1), Some("name"));
+        assert_eq!(row.get::<&str>("name").unwrap(), "Jane");
+        assert_eq!(row.get_index::<u8>(0).unwrap(), 7);
+        assert_eq!(row.get::<Option<String>>("nickname").unwrap(), None);
+
+        let err = row.get::<i64>("name").unwrap_err().to_string();
+        assert!(
+            err.starts_with("Decode error: column `name`: invalid type"),
+            "{}",
+            err
+        );
+        let err = row.get::<String>("ignored").unwrap_err().to_string();
+        assert!(
+            err.contains("unsupported column type `INTERVAL`"),
+            "{}",
+            err
+        );
+        assert!(row.get::<i64>("missing").is_err());
+        assert!(row.get_index::<i64>(8).is_err());
+    }
+
+    #[test]
+    fn test_from_row() {
+        struct Named {
+            id: i64,
+            name: String,
+        }
+
+        impl FromRow for Named {
+            fn from_row(row: &Row<'_>) -> Result<Self> {
+                Ok(Self {
+                    id: row.get("id")?,
+                    name: row.get("name")?,
+                })
+            }
+        }
+
+        let raw = user_row();
+        let named = Named::from_row(&Row::new(&raw)).unwrap();
+        assert_eq!((named.id, named.name.as_str()), (7, "Jane"));
+    }
+
+    #[test]
+    fn test_json_object_rows() {
+        let object = serde_json::json!({"id": 1, "tags": ["x"], "score": 1.5});
+        let raw = object.as_object().unwrap();
+        let row = Row::new(raw);
+
+        assert_eq!(row.get::<i64>("id").unwrap(), 1);
+        assert_eq!(row.get::<Vec<String>>("tags").unwrap(), vec!["x"]);
+        assert_eq!(row.get::<f64>("score").unwrap(), 1.5);
+    }
+
+    #[test]
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
+    fn test_time_formatting() {
+        assert_eq!(format_time(9, 5, 0, 0), "09:05:00");
+        assert_eq!(format_time(23, 59, 59, 120_000), "23:59:59.12");
+    }
+
+    #[test]
+    #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    fn test_iso_datetime() {
+        assert_eq!(
+            iso_datetime("2024-02-29 12:30:00").unwrap(),
+            "2024-02-29T12:30:00"
+        );
+        assert_eq!(
+            iso_datetime("2024-02-29 12:30:00.5").unwrap(),
+            "2024-02-29T12:30:00.5"
+        );
+        assert!(iso_datetime("2024-02-29").is_none());
+        assert!(iso_datetime("not a timestamp at all").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "postgres")]
+    fn test_date_formatting() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(10_957), "2000-01-01");
+        assert_eq!(format_date(19_782), "2024-02-29");
+        assert_eq!(format_date(-1), "1969-12-31");
+        assert_eq!(
+            format_timestamp(1_700_000_000_123_456),
+            "2023-11-14T22:13:20.123456"
+        );
+        assert_eq!(format_timestamp(-1_000_000), "1969-12-31T23:59:59");
+    }
+}
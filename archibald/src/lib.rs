@@ -4,32 +4,83 @@
 //! with compile-time guarantees and an intuitive builder pattern.
 
 pub mod builder;
+pub mod dialect;
 pub mod error;
 pub mod executor;
 pub mod operator;
+pub mod schema;
 pub mod value;
 
 // Re-export main types
 pub use builder::common::{
-    AggregateFunction, IntoCondition, JoinType, QueryBuilder, SortDirection, WhereCondition,
-    WhereConnector,
+    AggregateFunction, IntoColumnRef, IntoCondition, JoinBuilder, JoinOperand, JoinType,
+    QueryBuilder, SortDirection, WhereCondition, WhereConnector, WhereGroupBuilder, WhereNode,
+};
+pub use builder::select::{
+    ColumnSelector, SelectBuilderComplete, SelectBuilderInitial, SelectQuery, Subquery,
 };
-pub use builder::select::{ColumnSelector, SelectBuilderComplete, SelectBuilderInitial, Subquery};
 pub use builder::{
-    DeleteBuilderComplete, DeleteBuilderInitial, InsertBuilderComplete, InsertBuilderInitial,
-    UpdateBuilderComplete, UpdateBuilderInitial, UpdateBuilderWithSet,
+    Column, ColumnCondition, CompoundSelectBuilder, Cte, DeleteBuilderComplete,
+    DeleteBuilderInitial, FrameBound, FrameUnits, InsertBuilderComplete, InsertBuilderInitial,
+    InsertBuilderWithColumns, IntoInsertData, IntoUpdateData, OnConflictBuilder, Raw,
+    ReturningBuilder, SetOperator, Table, TableRef, UpdateBuilderComplete, UpdateBuilderInitial,
+    UpdateBuilderWithSet, Window, WindowFrame, WindowFunction,
 };
+#[cfg(feature = "derive")]
+pub use archibald_derive::{IntoInsertData, IntoUpdateData, Table};
+pub use dialect::Dialect;
 pub use error::{Error, Result};
 pub use executor::{
-    transaction, ConnectionPool, ExecutableModification, ExecutableQuery, IsolationLevel,
-    Transaction, TransactionalPool,
+    transaction, ConnectionPool, ExecutableModification, ExecutableQuery, FromRow, IsolationLevel,
+    Row, Transaction, TransactionalPool,
 };
 pub use operator::{op, IntoOperator, Operator};
 pub use value::Value;
 
 /// Create a new SELECT query builder for the given table
-pub fn from(name: &str) -> SelectBuilderInitial {
-    builder::select::SelectBuilderInitial::new(name)
+///
+/// Takes a table name or a [`TableRef`], such as an aliased table from
+/// [`table`].
+pub fn from<T>(table: T) -> SelectBuilderInitial
+where
+    T: Into<TableRef>,
+{
+    builder::select::SelectBuilderInitial::new(table)
+}
+
+/// Create a new SELECT query builder over a subquery, aliased as `alias`
+///
+/// # Examples
+///
+/// ```
+/// use archibald::{from, from_subquery, op, QueryBuilder};
+///
+/// let big = from("orders").select(("user_id", "total")).where_(("total", op::GT, 100));
+/// let query = from_subquery(big, "big").select("user_id").distinct();
+/// assert_eq!(
+///     query.to_sql().unwrap(),
+///     "SELECT DISTINCT user_id FROM (SELECT user_id, total FROM orders WHERE total > ?) AS big"
+/// );
+/// ```
+pub fn from_subquery<Q>(query: Q, alias: &str) -> SelectBuilderInitial
+where
+    Q: Into<SelectQuery>,
+{
+    from(TableRef::subquery(query, alias))
+}
+
+/// Create a table reference, which can be aliased with [`TableRef::as_`]
+///
+/// # Examples
+///
+/// ```
+/// use archibald::{from, table, QueryBuilder};
+///
+/// let query = from(table("users").as_("u")).select("u.name");
+/// assert_eq!(query.to_sql().unwrap(), "SELECT u.name FROM users AS u");
+/// ```
+pub fn table(name: &str) -> TableRef {
+    TableRef::table(name)
 }
 
 /// Create a new UPDATE query builder for the given table
@@ -47,6 +98,32 @@ pub fn insert(name: &str) -> InsertBuilderInitial {
     builder::InsertBuilderInitial::new(name)
 }
 
+/// Create a raw SQL query or fragment with values bound to its `?` placeholders
+///
+/// The result can be executed on its own like any other query, or passed to
+/// builder methods such as `where_raw` and `select_raw`. See [`Raw`].
+///
+/// # Examples
+///
+/// ```
+/// use archibald::{raw, QueryBuilder, Value};
+/// use archibald::dialect::PostgresDialect;
+///
+/// let params = [Value::from(7), Value::from("ada@example.com")];
+/// let query = raw("SELECT * FROM users WHERE id = ? OR email = ?", params);
+/// assert_eq!(
+///     query.to_sql_with(&PostgresDialect).unwrap(),
+///     "SELECT * FROM users WHERE id = $1 OR email = $2"
+/// );
+/// ```
+pub fn raw<I>(sql: &str, params: I) -> Raw
+where
+    I: IntoIterator,
+    I::Item: Into<Value>,
+{
+    params.into_iter().fold(Raw::new(sql), Raw::bind)
+}
+
 /// Create a column selector for aliasing
 /// 
 /// # Examples
@@ -0,0 +1,320 @@
+//! Column definitions for the schema builder
+
+use crate::dialect::Dialect;
+use crate::{Error, Result, Value};
+
+/// Portable column types, mapped to concrete SQL types by each [`Dialect`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnType {
+    /// Auto-incrementing integer primary key
+    Increments,
+    /// Auto-incrementing 64-bit integer primary key
+    BigIncrements,
+    SmallInteger,
+    Integer,
+    BigInteger,
+    /// Variable-length string with a maximum length
+    String(u32),
+    Text,
+    Boolean,
+    Float,
+    Double,
+    /// Exact numeric with precision and scale
+    Decimal(u8, u8),
+    Date,
+    Time,
+    Timestamp,
+    /// Timestamp with time zone
+    TimestampTz,
+    Json,
+    /// Binary JSON (PostgreSQL `JSONB`; plain JSON elsewhere)
+    Jsonb,
+    Binary,
+    Uuid,
+    /// A database-specific type, rendered verbatim
+    Custom(String),
+}
+
+impl ColumnType {
+    /// Whether this type already renders as an auto-incrementing primary key
+    pub fn is_increments(&self) -> bool {
+        matches!(self, ColumnType::Increments | ColumnType::BigIncrements)
+    }
+}
+
+/// Action taken on referencing rows when a referenced row changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferentialAction {
+    Cascade,
+    Restrict,
+    SetNull,
+    SetDefault,
+    NoAction,
+}
+
+impl ReferentialAction {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::SetDefault => "SET DEFAULT",
+            ReferentialAction::NoAction => "NO ACTION",
+        }
+    }
+}
+
+/// Default value of a column
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnDefault {
+    /// A literal value
+    Value(Value),
+    /// A raw SQL expression such as `CURRENT_TIMESTAMP`
+    Raw(String),
+}
+
+/// A column-level foreign key reference
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnReference {
+    pub table: String,
+    pub column: String,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+}
+
+/// A column being created, configured through chained modifiers
+///
+/// # Examples
+/// ```
+/// use archibald::schema::{create_table, SchemaBuilder};
+///
+/// let table = create_table("users", |t| {
+///     t.string("email", 255).not_null().unique();
+///     t.integer("age").default(0);
+/// });
+/// assert_eq!(
+///     table.to_sql().unwrap(),
+///     "CREATE TABLE users (email VARCHAR(255) NOT NULL UNIQUE, age INTEGER DEFAULT 0)"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnDefinition {
+    pub name: String,
+    pub column_type: ColumnType,
+    pub not_null: bool,
+    pub unique: bool,
+    pub primary: bool,
+    pub default: Option<ColumnDefault>,
+    pub references: Option<ColumnReference>,
+}
+
+impl ColumnDefinition {
+    pub fn new(name: &str, column_type: ColumnType) -> Self {
+        Self {
+            name: name.to_string(),
+            column_type,
+            not_null: false,
+            unique: false,
+            primary: false,
+            default: None,
+            references: None,
+        }
+    }
+
+    /// Add a NOT NULL constraint
+    pub fn not_null(&mut self) -> &mut Self {
+        self.not_null = true;
+        self
+    }
+
+    /// Allow NULL values (the default)
+    pub fn nullable(&mut self) -> &mut Self {
+        self.not_null = false;
+        self
+    }
+
+    /// Add a UNIQUE constraint
+    pub fn unique(&mut self) -> &mut Self {
+        self.unique = true;
+        self
+    }
+
+    /// Make this column the primary key
+    pub fn primary(&mut self) -> &mut Self {
+        self.primary = true;
+        self
+    }
+
+    /// Set a literal default value
+    pub fn default<V>(&mut self, value: V) -> &mut Self
+    where
+        V: Into<Value>,
+    {
+        self.default = Some(ColumnDefault::Value(value.into()));
+        self
+    }
+
+    /// Set a raw SQL default expression, e.g. `CURRENT_TIMESTAMP`
+    pub fn default_raw(&mut self, expression: &str) -> &mut Self {
+        self.default = Some(ColumnDefault::Raw(expression.to_string()));
+        self
+    }
+
+    /// Reference a column of another table
+    ///
+    /// # Examples
+    /// ```
+    /// use archibald::schema::{create_table, ReferentialAction, SchemaBuilder};
+    ///
+    /// let table = create_table("posts", |t| {
+    ///     t.integer("user_id")
+    ///         .not_null()
+    ///         .references("users", "id")
+    ///         .on_delete(ReferentialAction::Cascade);
+    /// });
+    /// assert_eq!(
+    ///     table.to_sql().unwrap(),
+    ///     "CREATE TABLE posts (user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE)"
+    /// );
+    /// ```
+    pub fn references(&mut self, table: &str, column: &str) -> &mut Self {
+        self.references = Some(ColumnReference {
+            table: table.to_string(),
+            column: column.to_string(),
+            on_delete: None,
+            on_update: None,
+        });
+        self
+    }
+
+    /// Set the ON DELETE action of the column's reference
+    pub fn on_delete(&mut self, action: ReferentialAction) -> &mut Self {
+        if let Some(reference) = &mut self.references {
+            reference.on_delete = Some(action);
+        }
+        self
+    }
+
+    /// Set the ON UPDATE action of the column's reference
+    pub fn on_update(&mut self, action: ReferentialAction) -> &mut Self {
+        if let Some(reference) = &mut self.references {
+            reference.on_update = Some(action);
+        }
+        self
+    }
+
+    /// Render the column definition, optionally leaving out its UNIQUE constraint
+    pub(crate) fn render(&self, dialect: &dyn Dialect, with_unique: bool) -> Result<String> {
+        let mut sql = format!(
+            "{} {}",
+            dialect.quote_identifier(&self.name),
+            dialect.column_type(&self.column_type)
+        );
+
+        if self.not_null && !self.column_type.is_increments() {
+            sql.push_str(" NOT NULL");
+        }
+
+        if let Some(default) = &self.default {
+            sql.push_str(" DEFAULT ");
+            match default {
+                ColumnDefault::Value(value) => sql.push_str(&literal(dialect, value)?),
+                ColumnDefault::Raw(expression) => sql.push_str(expression),
+            }
+        }
+
+        if self.unique && with_unique {
+            sql.push_str(" UNIQUE");
+        }
+
+        if self.primary && !self.column_type.is_increments() {
+            sql.push_str(" PRIMARY KEY");
+        }
+
+        if let Some(reference) = &self.references {
+            sql.push_str(&format!(
+                " REFERENCES {} ({})",
+                dialect.quote_identifier(&reference.table),
+                dialect.quote_identifier(&reference.column)
+            ));
+            sql.push_str(&referential_actions(reference.on_delete, reference.on_update));
+        }
+
+        Ok(sql)
+    }
+}
+
+/// Render ON DELETE / ON UPDATE clauses, including the leading space
+pub(crate) fn referential_actions(
+    on_delete: Option<ReferentialAction>,
+    on_update: Option<ReferentialAction>,
+) -> String {
+    let mut sql = String::new();
+    if let Some(action) = on_delete {
+        sql.push_str(" ON DELETE ");
+        sql.push_str(action.as_sql());
+    }
+    if let Some(action) = on_update {
+        sql.push_str(" ON UPDATE ");
+        sql.push_str(action.as_sql());
+    }
+    sql
+}
+
+/// Render a value as an SQL literal; DDL statements can't take bound parameters
+fn literal(dialect: &dyn Dialect, value: &Value) -> Result<String> {
+    match value {
+        Value::Null => Ok("NULL".to_string()),
+        Value::Bool(b) => Ok(dialect.boolean_literal(*b).to_string()),
+        Value::I32(i) => Ok(i.to_string()),
+        Value::I64(i) => Ok(i.to_string()),
+        Value::F32(f) => Ok(f.to_string()),
+        Value::F64(f) => Ok(f.to_string()),
+        Value::String(s) => Ok(format!("'{}'", s.replace('\'', "''"))),
+        Value::Json(j) => Ok(format!("'{}'", j.to_string().replace('\'', "''"))),
+        other => Err(Error::invalid_query(format!(
+            "{} values can't be used as a column default",
+            other.type_name()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{GenericDialect, PostgresDialect, SqliteDialect};
+
+    #[test]
+    fn test_column_render() {
+        let mut column = ColumnDefinition::new("name", ColumnType::String(100));
+        column.not_null().default("anonymous");
+
+        assert_eq!(
+            column.render(&PostgresDialect, true).unwrap(),
+            r#""name" VARCHAR(100) NOT NULL DEFAULT 'anonymous'"#
+        );
+    }
+
+    #[test]
+    fn test_increments_are_primary_keys() {
+        let mut column = ColumnDefinition::new("id", ColumnType::Increments);
+        column.primary().not_null();
+
+        assert_eq!(
+            column.render(&SqliteDialect, true).unwrap(),
+            r#""id" INTEGER PRIMARY KEY AUTOINCREMENT"#
+        );
+        assert_eq!(
+            column.render(&PostgresDialect, true).unwrap(),
+            r#""id" SERIAL PRIMARY KEY"#
+        );
+    }
+
+    #[test]
+    fn test_default_literals() {
+        assert_eq!(literal(&GenericDialect, &"it's".into()).unwrap(), "'it''s'");
+        assert_eq!(literal(&SqliteDialect, &true.into()).unwrap(), "1");
+        assert_eq!(literal(&PostgresDialect, &Value::Null).unwrap(), "NULL");
+        assert!(literal(&PostgresDialect, &Value::Bytes(vec![1])).is_err());
+    }
+}
@@ -0,0 +1,481 @@
+//! Schema builder for CREATE, ALTER, DROP and RENAME TABLE statements
+//!
+//! Table definitions are collected through a closure receiving a
+//! [`TableBuilder`], then rendered to DDL for a [`Dialect`]. A single builder
+//! can produce several statements (indexes are created separately from the
+//! table), so [`SchemaBuilder::execute`] runs them one after another.
+//!
+//! # Examples
+//! ```
+//! use archibald::dialect::PostgresDialect;
+//! use archibald::schema::{create_table, SchemaBuilder};
+//!
+//! let users = create_table("users", |t| {
+//!     t.increments("id");
+//!     t.string("email", 255).not_null().unique();
+//!     t.timestamps();
+//! });
+//!
+//! assert_eq!(
+//!     users.to_sql_with(&PostgresDialect).unwrap(),
+//!     r#"CREATE TABLE "users" ("id" SERIAL PRIMARY KEY, "email" VARCHAR(255) NOT NULL UNIQUE, "#
+//!         .to_string()
+//!         + r#""created_at" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP, "#
+//!         + r#""updated_at" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP)"#
+//! );
+//! ```
+
+pub mod column;
+pub mod table;
+
+pub use column::{ColumnDefault, ColumnDefinition, ColumnReference, ColumnType, ReferentialAction};
+pub use table::{ForeignKeyDefinition, IndexDefinition, TableBuilder, TableItem};
+
+use crate::dialect::{Dialect, GenericDialect};
+use crate::executor::{ConnectionPool, Transaction};
+use crate::{Error, Result};
+use std::future::Future;
+use table::quote_all;
+
+/// Common functionality for schema statements
+pub trait SchemaBuilder {
+    /// Render the DDL statements, in execution order, for a dialect
+    fn to_statements(&self, dialect: &dyn Dialect) -> Result<Vec<String>>;
+
+    /// Render the DDL with the generic dialect, statements separated by `;\n`
+    fn to_sql(&self) -> Result<String> {
+        self.to_sql_with(&GenericDialect)
+    }
+
+    /// Render the DDL for a dialect, statements separated by `;\n`
+    fn to_sql_with(&self, dialect: &dyn Dialect) -> Result<String> {
+        Ok(self.to_statements(dialect)?.join(";\n"))
+    }
+
+    /// Execute every statement on the pool, in order
+    fn execute<P>(self, pool: &P) -> impl Future<Output = Result<()>> + Send
+    where
+        P: ConnectionPool,
+        Self: Sized,
+    {
+        let statements = self.to_statements(pool.dialect());
+        async move {
+            for statement in statements? {
+                pool.execute(&statement, &[]).await?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Execute every statement within a transaction, in order
+    fn execute_tx<Tx>(self, tx: &mut Tx) -> impl Future<Output = Result<()>> + Send
+    where
+        Tx: Transaction,
+        Self: Sized,
+    {
+        let statements = self.to_statements(tx.dialect());
+        async move {
+            for statement in statements? {
+                tx.execute(&statement, &[]).await?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Create a table, defining its columns in the closure
+pub fn create_table<F>(name: &str, define: F) -> CreateTable
+where
+    F: FnOnce(&mut TableBuilder),
+{
+    let mut table = TableBuilder::new();
+    define(&mut table);
+    CreateTable {
+        name: name.to_string(),
+        if_not_exists: false,
+        table,
+    }
+}
+
+/// Alter an existing table: add, drop and rename columns, indexes and constraints
+///
+/// # Examples
+/// ```
+/// use archibald::schema::{alter_table, SchemaBuilder};
+///
+/// let statements = alter_table("users", |t| {
+///     t.string("nickname", 50);
+///     t.rename_column("name", "full_name");
+///     t.index("nickname");
+/// })
+/// .to_statements(&archibald::dialect::GenericDialect)
+/// .unwrap();
+///
+/// assert_eq!(statements, vec![
+///     "ALTER TABLE users ADD COLUMN nickname VARCHAR(50)",
+///     "ALTER TABLE users RENAME COLUMN name TO full_name",
+///     "CREATE INDEX users_nickname_index ON users (nickname)",
+/// ]);
+/// ```
+pub fn alter_table<F>(name: &str, define: F) -> AlterTable
+where
+    F: FnOnce(&mut TableBuilder),
+{
+    let mut table = TableBuilder::new();
+    define(&mut table);
+    AlterTable {
+        name: name.to_string(),
+        table,
+    }
+}
+
+/// Drop a table
+pub fn drop_table(name: &str) -> DropTable {
+    DropTable {
+        name: name.to_string(),
+        if_exists: false,
+    }
+}
+
+/// Drop a table if it exists
+pub fn drop_table_if_exists(name: &str) -> DropTable {
+    DropTable {
+        name: name.to_string(),
+        if_exists: true,
+    }
+}
+
+/// Rename a table
+pub fn rename_table(from: &str, to: &str) -> RenameTable {
+    RenameTable {
+        from: from.to_string(),
+        to: to.to_string(),
+    }
+}
+
+/// CREATE TABLE statement, followed by CREATE INDEX for its indexes
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateTable {
+    name: String,
+    if_not_exists: bool,
+    table: TableBuilder,
+}
+
+impl CreateTable {
+    /// Only create the table (and its indexes) if it doesn't exist yet
+    pub fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+}
+
+impl SchemaBuilder for CreateTable {
+    fn to_statements(&self, dialect: &dyn Dialect) -> Result<Vec<String>> {
+        let mut definitions = Vec::new();
+        let mut indexes = Vec::new();
+
+        for item in &self.table.items {
+            match item {
+                TableItem::Column(column) => definitions.push(column.render(dialect, true)?),
+                TableItem::Primary(columns) => {
+                    definitions.push(format!("PRIMARY KEY ({})", quote_all(dialect, columns)))
+                }
+                TableItem::Foreign(foreign) => {
+                    definitions.push(foreign.render(&self.name, dialect))
+                }
+                TableItem::Index(index) => {
+                    indexes.push(index.render(&self.name, self.if_not_exists, dialect))
+                }
+                TableItem::DropColumn(_)
+                | TableItem::RenameColumn(_, _)
+                | TableItem::DropIndex(_) => {
+                    return Err(Error::invalid_query(
+                        "drop_column, rename_column and drop_index are only valid in alter_table",
+                    ));
+                }
+            }
+        }
+
+        if definitions.is_empty() {
+            return Err(Error::invalid_query(format!(
+                "CREATE TABLE {} requires at least one column",
+                self.name
+            )));
+        }
+
+        let mut statements = vec![format!(
+            "CREATE TABLE {}{} ({})",
+            if self.if_not_exists { "IF NOT EXISTS " } else { "" },
+            dialect.quote_identifier(&self.name),
+            definitions.join(", ")
+        )];
+        statements.extend(indexes);
+
+        Ok(statements)
+    }
+}
+
+/// ALTER TABLE statements, one per change, in the order they were defined
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlterTable {
+    name: String,
+    table: TableBuilder,
+}
+
+impl AlterTable {
+    fn check_constraints(&self, dialect: &dyn Dialect) -> Result<()> {
+        if dialect.supports_alter_constraints() {
+            Ok(())
+        } else {
+            Err(Error::invalid_query(format!(
+                "the {} dialect can't add constraints to an existing table",
+                dialect.name()
+            )))
+        }
+    }
+}
+
+impl SchemaBuilder for AlterTable {
+    fn to_statements(&self, dialect: &dyn Dialect) -> Result<Vec<String>> {
+        let table = dialect.quote_identifier(&self.name);
+        let mut statements = Vec::new();
+
+        for item in &self.table.items {
+            match item {
+                TableItem::Column(column) => {
+                    // Not every database can add a UNIQUE column, so the
+                    // constraint becomes a unique index instead
+                    statements.push(format!(
+                        "ALTER TABLE {} ADD COLUMN {}",
+                        table,
+                        column.render(dialect, false)?
+                    ));
+                    if column.unique {
+                        let index = IndexDefinition {
+                            name: None,
+                            columns: vec![column.name.clone()],
+                            unique: true,
+                        };
+                        statements.push(index.render(&self.name, false, dialect));
+                    }
+                }
+                TableItem::Primary(columns) => {
+                    self.check_constraints(dialect)?;
+                    statements.push(format!(
+                        "ALTER TABLE {} ADD PRIMARY KEY ({})",
+                        table,
+                        quote_all(dialect, columns)
+                    ));
+                }
+                TableItem::Foreign(foreign) => {
+                    self.check_constraints(dialect)?;
+                    statements.push(format!(
+                        "ALTER TABLE {} ADD {}",
+                        table,
+                        foreign.render(&self.name, dialect)
+                    ));
+                }
+                TableItem::Index(index) => {
+                    statements.push(index.render(&self.name, false, dialect))
+                }
+                TableItem::DropColumn(name) => statements.push(format!(
+                    "ALTER TABLE {} DROP COLUMN {}",
+                    table,
+                    dialect.quote_identifier(name)
+                )),
+                TableItem::RenameColumn(from, to) => statements.push(format!(
+                    "ALTER TABLE {} RENAME COLUMN {} TO {}",
+                    table,
+                    dialect.quote_identifier(from),
+                    dialect.quote_identifier(to)
+                )),
+                TableItem::DropIndex(name) => {
+                    let mut sql = format!("DROP INDEX {}", dialect.quote_identifier(name));
+                    if dialect.drop_index_requires_table() {
+                        sql.push_str(&format!(" ON {}", table));
+                    }
+                    statements.push(sql);
+                }
+            }
+        }
+
+        if statements.is_empty() {
+            return Err(Error::invalid_query(format!(
+                "ALTER TABLE {} has no changes",
+                self.name
+            )));
+        }
+
+        Ok(statements)
+    }
+}
+
+/// DROP TABLE statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropTable {
+    name: String,
+    if_exists: bool,
+}
+
+impl SchemaBuilder for DropTable {
+    fn to_statements(&self, dialect: &dyn Dialect) -> Result<Vec<String>> {
+        Ok(vec![format!(
+            "DROP TABLE {}{}",
+            if self.if_exists { "IF EXISTS " } else { "" },
+            dialect.quote_identifier(&self.name)
+        )])
+    }
+}
+
+/// ALTER TABLE ... RENAME TO statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenameTable {
+    from: String,
+    to: String,
+}
+
+impl SchemaBuilder for RenameTable {
+    fn to_statements(&self, dialect: &dyn Dialect) -> Result<Vec<String>> {
+        Ok(vec![format!(
+            "ALTER TABLE {} RENAME TO {}",
+            dialect.quote_identifier(&self.from),
+            dialect.quote_identifier(&self.to)
+        )])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MySqlDialect, PostgresDialect, SqliteDialect};
+
+    fn users_table() -> CreateTable {
+        create_table("users", |t| {
+            t.increments("id");
+            t.string("email", 255).not_null().unique();
+            t.boolean("active").not_null().default(true);
+            t.jsonb("settings");
+            t.index("active");
+        })
+    }
+
+    #[test]
+    fn test_create_table_postgres() {
+        let statements = users_table().to_statements(&PostgresDialect).unwrap();
+
+        assert_eq!(
+            statements,
+            vec![
+                r#"CREATE TABLE "users" ("id" SERIAL PRIMARY KEY, "email" VARCHAR(255) NOT NULL UNIQUE, "active" BOOLEAN NOT NULL DEFAULT TRUE, "settings" JSONB)"#,
+                r#"CREATE INDEX "users_active_index" ON "users" ("active")"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_table_sqlite() {
+        let statements = users_table()
+            .if_not_exists()
+            .to_statements(&SqliteDialect)
+            .unwrap();
+
+        assert_eq!(
+            statements,
+            vec![
+                r#"CREATE TABLE IF NOT EXISTS "users" ("id" INTEGER PRIMARY KEY AUTOINCREMENT, "email" VARCHAR(255) NOT NULL UNIQUE, "active" BOOLEAN NOT NULL DEFAULT 1, "settings" TEXT)"#,
+                r#"CREATE INDEX IF NOT EXISTS "users_active_index" ON "users" ("active")"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_table_with_composite_primary_key() {
+        let table = create_table("user_roles", |t| {
+            t.integer("user_id").not_null();
+            t.integer("role_id").not_null();
+            t.primary(("user_id", "role_id"));
+        });
+
+        assert_eq!(
+            table.to_sql().unwrap(),
+            "CREATE TABLE user_roles (user_id INTEGER NOT NULL, role_id INTEGER NOT NULL, PRIMARY KEY (user_id, role_id))"
+        );
+    }
+
+    #[test]
+    fn test_create_table_rejects_alter_items() {
+        let table = create_table("users", |t| {
+            t.increments("id");
+            t.drop_column("name");
+        });
+        assert!(matches!(table.to_sql(), Err(Error::InvalidQuery { .. })));
+
+        let empty = create_table("users", |_| {});
+        assert!(empty.to_sql().is_err());
+    }
+
+    #[test]
+    fn test_alter_table_unique_column_becomes_index() {
+        let statements = alter_table("users", |t| {
+            t.string("username", 50).unique();
+            t.drop_column("legacy");
+            t.drop_index("users_old_index");
+        })
+        .to_statements(&SqliteDialect)
+        .unwrap();
+
+        assert_eq!(
+            statements,
+            vec![
+                r#"ALTER TABLE "users" ADD COLUMN "username" VARCHAR(50)"#,
+                r#"CREATE UNIQUE INDEX "users_username_unique" ON "users" ("username")"#,
+                r#"ALTER TABLE "users" DROP COLUMN "legacy""#,
+                r#"DROP INDEX "users_old_index""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_drop_index_names_the_table_on_mysql() {
+        let alter = alter_table("users", |t| t.drop_index("users_old_index"));
+
+        assert_eq!(
+            alter.to_sql_with(&PostgresDialect).unwrap(),
+            r#"DROP INDEX "users_old_index""#
+        );
+        assert_eq!(
+            alter.to_sql_with(&MySqlDialect).unwrap(),
+            "DROP INDEX `users_old_index` ON `users`"
+        );
+    }
+
+    #[test]
+    fn test_alter_table_constraints() {
+        let alter = alter_table("posts", |t| {
+            t.foreign("user_id").references("users", "id");
+        });
+
+        assert_eq!(
+            alter.to_sql_with(&PostgresDialect).unwrap(),
+            r#"ALTER TABLE "posts" ADD CONSTRAINT "posts_user_id_foreign" FOREIGN KEY ("user_id") REFERENCES "users" ("id")"#
+        );
+
+        let err = alter.to_sql_with(&SqliteDialect).unwrap_err();
+        assert!(err.to_string().contains("sqlite"));
+    }
+
+    #[test]
+    fn test_drop_and_rename_table() {
+        assert_eq!(drop_table("users").to_sql().unwrap(), "DROP TABLE users");
+        assert_eq!(
+            drop_table_if_exists("users")
+                .to_sql_with(&PostgresDialect)
+                .unwrap(),
+            r#"DROP TABLE IF EXISTS "users""#
+        );
+        assert_eq!(
+            rename_table("users", "accounts").to_sql().unwrap(),
+            "ALTER TABLE users RENAME TO accounts"
+        );
+    }
+}
@@ -0,0 +1,387 @@
+//! Table builder passed to `create_table` and `alter_table` closures
+
+use super::column::{referential_actions, ColumnDefinition, ColumnType, ReferentialAction};
+use crate::builder::common::IntoColumns;
+use crate::dialect::Dialect;
+
+/// An index on one or more columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexDefinition {
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    pub unique: bool,
+}
+
+impl IndexDefinition {
+    /// Override the generated index name (`{table}_{columns}_index` or `_unique`)
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub(crate) fn index_name(&self, table: &str) -> String {
+        let suffix = if self.unique { "unique" } else { "index" };
+        self.name
+            .clone()
+            .unwrap_or_else(|| generated_name(table, &self.columns, suffix))
+    }
+
+    pub(crate) fn render(&self, table: &str, if_not_exists: bool, dialect: &dyn Dialect) -> String {
+        format!(
+            "CREATE {}INDEX {}{} ON {} ({})",
+            if self.unique { "UNIQUE " } else { "" },
+            if if_not_exists { "IF NOT EXISTS " } else { "" },
+            dialect.quote_identifier(&self.index_name(table)),
+            dialect.quote_identifier(table),
+            quote_all(dialect, &self.columns)
+        )
+    }
+}
+
+/// A table-level foreign key constraint
+///
+/// # Examples
+/// ```
+/// use archibald::schema::{create_table, ReferentialAction, SchemaBuilder};
+///
+/// let table = create_table("memberships", |t| {
+///     t.integer("team_id");
+///     t.foreign("team_id")
+///         .references("teams", "id")
+///         .on_delete(ReferentialAction::Cascade);
+/// });
+/// assert_eq!(
+///     table.to_sql().unwrap(),
+///     "CREATE TABLE memberships (team_id INTEGER, CONSTRAINT memberships_team_id_foreign \
+///      FOREIGN KEY (team_id) REFERENCES teams (id) ON DELETE CASCADE)"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignKeyDefinition {
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    pub foreign_table: String,
+    pub foreign_columns: Vec<String>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+}
+
+impl ForeignKeyDefinition {
+    /// Set the referenced table and columns
+    pub fn references<C>(&mut self, table: &str, columns: C) -> &mut Self
+    where
+        C: IntoColumns,
+    {
+        self.foreign_table = table.to_string();
+        self.foreign_columns = columns.into_columns();
+        self
+    }
+
+    /// Set the ON DELETE action
+    pub fn on_delete(&mut self, action: ReferentialAction) -> &mut Self {
+        self.on_delete = Some(action);
+        self
+    }
+
+    /// Set the ON UPDATE action
+    pub fn on_update(&mut self, action: ReferentialAction) -> &mut Self {
+        self.on_update = Some(action);
+        self
+    }
+
+    /// Override the generated constraint name (`{table}_{columns}_foreign`)
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Render as `CONSTRAINT name FOREIGN KEY (...) REFERENCES ...`
+    pub(crate) fn render(&self, table: &str, dialect: &dyn Dialect) -> String {
+        let name = self
+            .name
+            .clone()
+            .unwrap_or_else(|| generated_name(table, &self.columns, "foreign"));
+
+        format!(
+            "CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({}){}",
+            dialect.quote_identifier(&name),
+            quote_all(dialect, &self.columns),
+            dialect.quote_identifier(&self.foreign_table),
+            quote_all(dialect, &self.foreign_columns),
+            referential_actions(self.on_delete, self.on_update)
+        )
+    }
+}
+
+/// A single change collected by a [`TableBuilder`], kept in call order
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableItem {
+    Column(ColumnDefinition),
+    Primary(Vec<String>),
+    Index(IndexDefinition),
+    Foreign(ForeignKeyDefinition),
+    DropColumn(String),
+    RenameColumn(String, String),
+    DropIndex(String),
+}
+
+/// Collects columns, indexes and constraints for a table
+///
+/// Column methods return the new [`ColumnDefinition`] so modifiers can be
+/// chained. `drop_column`, `rename_column` and `drop_index` are only valid in
+/// [`alter_table`](super::alter_table).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableBuilder {
+    pub(crate) items: Vec<TableItem>,
+}
+
+impl TableBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a column of any type
+    pub fn column(&mut self, name: &str, column_type: ColumnType) -> &mut ColumnDefinition {
+        self.items
+            .push(TableItem::Column(ColumnDefinition::new(name, column_type)));
+        match self.items.last_mut() {
+            Some(TableItem::Column(column)) => column,
+            _ => unreachable!("a column was just pushed"),
+        }
+    }
+
+    /// Auto-incrementing integer primary key
+    pub fn increments(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Increments)
+    }
+
+    /// Auto-incrementing 64-bit integer primary key
+    pub fn big_increments(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::BigIncrements)
+    }
+
+    pub fn small_integer(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::SmallInteger)
+    }
+
+    pub fn integer(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Integer)
+    }
+
+    pub fn big_integer(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::BigInteger)
+    }
+
+    /// VARCHAR column with a maximum length
+    pub fn string(&mut self, name: &str, length: u32) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::String(length))
+    }
+
+    pub fn text(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Text)
+    }
+
+    pub fn boolean(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Boolean)
+    }
+
+    pub fn float(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Float)
+    }
+
+    pub fn double(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Double)
+    }
+
+    /// Exact numeric with the given precision and scale
+    pub fn decimal(&mut self, name: &str, precision: u8, scale: u8) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Decimal(precision, scale))
+    }
+
+    pub fn date(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Date)
+    }
+
+    pub fn time(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Time)
+    }
+
+    pub fn timestamp(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Timestamp)
+    }
+
+    pub fn timestamp_tz(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::TimestampTz)
+    }
+
+    pub fn json(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Json)
+    }
+
+    pub fn jsonb(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Jsonb)
+    }
+
+    pub fn binary(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Binary)
+    }
+
+    pub fn uuid(&mut self, name: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Uuid)
+    }
+
+    /// Column with a database-specific type, rendered verbatim
+    pub fn specific_type(&mut self, name: &str, sql_type: &str) -> &mut ColumnDefinition {
+        self.column(name, ColumnType::Custom(sql_type.to_string()))
+    }
+
+    /// `created_at` and `updated_at` timestamps defaulting to the current time
+    pub fn timestamps(&mut self) {
+        for name in ["created_at", "updated_at"] {
+            self.timestamp(name)
+                .not_null()
+                .default_raw("CURRENT_TIMESTAMP");
+        }
+    }
+
+    /// Composite primary key
+    pub fn primary<C>(&mut self, columns: C)
+    where
+        C: IntoColumns,
+    {
+        self.items.push(TableItem::Primary(columns.into_columns()));
+    }
+
+    /// Index on one or more columns
+    pub fn index<C>(&mut self, columns: C) -> &mut IndexDefinition
+    where
+        C: IntoColumns,
+    {
+        self.push_index(columns.into_columns(), false)
+    }
+
+    /// Unique index on one or more columns
+    pub fn unique<C>(&mut self, columns: C) -> &mut IndexDefinition
+    where
+        C: IntoColumns,
+    {
+        self.push_index(columns.into_columns(), true)
+    }
+
+    fn push_index(&mut self, columns: Vec<String>, unique: bool) -> &mut IndexDefinition {
+        self.items.push(TableItem::Index(IndexDefinition {
+            name: None,
+            columns,
+            unique,
+        }));
+        match self.items.last_mut() {
+            Some(TableItem::Index(index)) => index,
+            _ => unreachable!("an index was just pushed"),
+        }
+    }
+
+    /// Foreign key constraint on one or more columns
+    pub fn foreign<C>(&mut self, columns: C) -> &mut ForeignKeyDefinition
+    where
+        C: IntoColumns,
+    {
+        self.items.push(TableItem::Foreign(ForeignKeyDefinition {
+            name: None,
+            columns: columns.into_columns(),
+            foreign_table: String::new(),
+            foreign_columns: Vec::new(),
+            on_delete: None,
+            on_update: None,
+        }));
+        match self.items.last_mut() {
+            Some(TableItem::Foreign(foreign)) => foreign,
+            _ => unreachable!("a foreign key was just pushed"),
+        }
+    }
+
+    /// Drop a column (alter_table only)
+    pub fn drop_column(&mut self, name: &str) {
+        self.items.push(TableItem::DropColumn(name.to_string()));
+    }
+
+    /// Rename a column (alter_table only)
+    pub fn rename_column(&mut self, from: &str, to: &str) {
+        self.items
+            .push(TableItem::RenameColumn(from.to_string(), to.to_string()));
+    }
+
+    /// Drop an index by name (alter_table only)
+    pub fn drop_index(&mut self, name: &str) {
+        self.items.push(TableItem::DropIndex(name.to_string()));
+    }
+}
+
+fn generated_name(table: &str, columns: &[String], suffix: &str) -> String {
+    format!("{}_{}_{}", table, columns.join("_"), suffix)
+}
+
+pub(crate) fn quote_all(dialect: &dyn Dialect, names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| dialect.quote_identifier(name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::PostgresDialect;
+
+    #[test]
+    fn test_items_keep_call_order() {
+        let mut t = TableBuilder::new();
+        t.rename_column("name", "full_name");
+        t.string("nickname", 50);
+        t.drop_column("age");
+
+        assert!(matches!(t.items[0], TableItem::RenameColumn(_, _)));
+        assert!(matches!(t.items[1], TableItem::Column(_)));
+        assert!(matches!(t.items[2], TableItem::DropColumn(_)));
+    }
+
+    #[test]
+    fn test_index_names() {
+        let mut t = TableBuilder::new();
+        t.index(("last_name", "first_name"));
+        t.unique("email").name("users_email_key");
+
+        let sql: Vec<String> = t
+            .items
+            .iter()
+            .map(|item| match item {
+                TableItem::Index(index) => index.render("users", false, &PostgresDialect),
+                _ => unreachable!(),
+            })
+            .collect();
+
+        assert_eq!(
+            sql[0],
+            r#"CREATE INDEX "users_last_name_first_name_index" ON "users" ("last_name", "first_name")"#
+        );
+        assert_eq!(
+            sql[1],
+            r#"CREATE UNIQUE INDEX "users_email_key" ON "users" ("email")"#
+        );
+    }
+
+    #[test]
+    fn test_timestamps() {
+        let mut t = TableBuilder::new();
+        t.timestamps();
+
+        assert_eq!(t.items.len(), 2);
+        match &t.items[1] {
+            TableItem::Column(column) => {
+                assert_eq!(column.name, "updated_at");
+                assert!(column.not_null);
+            }
+            other => panic!("unexpected item {:?}", other),
+        }
+    }
+}
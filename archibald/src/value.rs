@@ -25,6 +25,24 @@ pub enum Value {
     Json(serde_json::Value),
     /// Array of values
     Array(Vec<Value>),
+    /// UUID value
+    #[cfg(feature = "uuid-support")]
+    Uuid(uuid::Uuid),
+    /// Calendar date without a time zone
+    #[cfg(feature = "datetime-support")]
+    Date(chrono::NaiveDate),
+    /// Time of day without a time zone
+    #[cfg(feature = "datetime-support")]
+    Time(chrono::NaiveTime),
+    /// Date and time without a time zone
+    #[cfg(feature = "datetime-support")]
+    Timestamp(chrono::NaiveDateTime),
+    /// Date and time in UTC
+    #[cfg(feature = "datetime-support")]
+    TimestampTz(chrono::DateTime<chrono::Utc>),
+    /// Exact decimal number
+    #[cfg(feature = "decimal-support")]
+    Decimal(rust_decimal::Decimal),
     /// Subquery placeholder (actual subquery stored separately)
     SubqueryPlaceholder,
 }
@@ -47,10 +65,40 @@ impl Value {
             Value::Bytes(_) => "BYTEA",
             Value::Json(_) => "JSON",
             Value::Array(_) => "ARRAY",
+            #[cfg(feature = "uuid-support")]
+            Value::Uuid(_) => "UUID",
+            #[cfg(feature = "datetime-support")]
+            Value::Date(_) => "DATE",
+            #[cfg(feature = "datetime-support")]
+            Value::Time(_) => "TIME",
+            #[cfg(feature = "datetime-support")]
+            Value::Timestamp(_) => "TIMESTAMP",
+            #[cfg(feature = "datetime-support")]
+            Value::TimestampTz(_) => "TIMESTAMPTZ",
+            #[cfg(feature = "decimal-support")]
+            Value::Decimal(_) => "NUMERIC",
             Value::SubqueryPlaceholder => "SUBQUERY",
         }
     }
 
+    /// The SQL type shared by every element of a non-empty array of scalars
+    ///
+    /// Returns `None` for non-arrays, empty arrays, mixed element types and
+    /// elements that can't be bound as a native database array.
+    pub fn array_element_type(&self) -> Option<&'static str> {
+        let items = self.as_array()?;
+        let first = items.first()?.type_name();
+        let scalar = !matches!(
+            items[0],
+            Value::Null | Value::Json(_) | Value::Array(_) | Value::SubqueryPlaceholder
+        );
+        if scalar && items.iter().all(|item| item.type_name() == first) {
+            Some(first)
+        } else {
+            None
+        }
+    }
+
     /// Extract array values if this is an Array variant
     pub fn as_array(&self) -> Option<&Vec<Value>> {
         match self {
@@ -121,6 +169,48 @@ impl From<serde_json::Value> for Value {
     }
 }
 
+#[cfg(feature = "uuid-support")]
+impl From<uuid::Uuid> for Value {
+    fn from(val: uuid::Uuid) -> Self {
+        Value::Uuid(val)
+    }
+}
+
+#[cfg(feature = "datetime-support")]
+impl From<chrono::NaiveDate> for Value {
+    fn from(val: chrono::NaiveDate) -> Self {
+        Value::Date(val)
+    }
+}
+
+#[cfg(feature = "datetime-support")]
+impl From<chrono::NaiveTime> for Value {
+    fn from(val: chrono::NaiveTime) -> Self {
+        Value::Time(val)
+    }
+}
+
+#[cfg(feature = "datetime-support")]
+impl From<chrono::NaiveDateTime> for Value {
+    fn from(val: chrono::NaiveDateTime) -> Self {
+        Value::Timestamp(val)
+    }
+}
+
+#[cfg(feature = "datetime-support")]
+impl From<chrono::DateTime<chrono::Utc>> for Value {
+    fn from(val: chrono::DateTime<chrono::Utc>) -> Self {
+        Value::TimestampTz(val)
+    }
+}
+
+#[cfg(feature = "decimal-support")]
+impl From<rust_decimal::Decimal> for Value {
+    fn from(val: rust_decimal::Decimal) -> Self {
+        Value::Decimal(val)
+    }
+}
+
 impl<T> From<Vec<T>> for Value
 where
     T: Into<Value>,
@@ -202,4 +292,56 @@ mod tests {
         assert_eq!(Value::Bool(true).type_name(), "BOOLEAN");
         assert_eq!(Value::Null.type_name(), "NULL");
     }
+
+    #[test]
+    fn test_array_element_type() {
+        assert_eq!(
+            Value::from(vec![1, 2]).array_element_type(),
+            Some("INTEGER")
+        );
+        assert_eq!(
+            Value::from(vec!["a", "b"]).array_element_type(),
+            Some("TEXT")
+        );
+        assert_eq!(
+            Value::Array(vec![Value::I32(1), Value::String("a".into())]).array_element_type(),
+            None
+        );
+        assert_eq!(Value::Array(vec![]).array_element_type(), None);
+        assert_eq!(Value::Array(vec![Value::Null]).array_element_type(), None);
+        assert_eq!(Value::I32(1).array_element_type(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "uuid-support")]
+    fn test_uuid_values() {
+        let id = uuid::Uuid::nil();
+        assert_eq!(Value::from(id), Value::Uuid(id));
+        assert_eq!(Value::from(id).type_name(), "UUID");
+        assert_eq!(Value::from(vec![id, id]).array_element_type(), Some("UUID"));
+    }
+
+    #[test]
+    #[cfg(feature = "datetime-support")]
+    fn test_datetime_values() {
+        use chrono::{NaiveDate, TimeZone, Utc};
+
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        let timestamp = date.and_hms_opt(12, 30, 0).unwrap();
+        assert_eq!(Value::from(date).type_name(), "DATE");
+        assert_eq!(Value::from(timestamp.time()).type_name(), "TIME");
+        assert_eq!(Value::from(timestamp), Value::Timestamp(timestamp));
+        assert_eq!(
+            Value::from(Utc.from_utc_datetime(&timestamp)).type_name(),
+            "TIMESTAMPTZ"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "decimal-support")]
+    fn test_decimal_values() {
+        let price = rust_decimal::Decimal::new(1250, 2);
+        assert_eq!(Value::from(price), Value::Decimal(price));
+        assert_eq!(Value::from(Some(price)).type_name(), "NUMERIC");
+    }
 }